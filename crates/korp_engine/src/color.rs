@@ -1,4 +1,4 @@
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Hash, Debug)]
 pub struct Color {
     pub r: u8,
     pub g: u8,
@@ -20,9 +20,6 @@ impl Color {
 
 impl From<Color> for u32 {
     fn from(value: Color) -> Self {
-        (value.r as u32) << 24
-            | (value.g as u32) << 16
-            | (value.b as u32) << 8
-            | (value.a as u32) << 0
+        (value.r as u32) << 24 | (value.g as u32) << 16 | (value.b as u32) << 8 | (value.a as u32)
     }
 }
@@ -34,6 +34,10 @@ pub struct Engine<T: Core> {
     title: String,
 }
 
+// touchpads scroll in pixels, mouse wheels in lines
+const PIXELS_PER_LINE: f64 = 40.0;
+
+#[allow(clippy::large_enum_variant)]
 enum State {
     Uninitialized,
     Initialized {
@@ -112,7 +116,18 @@ impl<T: Core> winit::application::ApplicationHandler for Engine<T> {
                 event_loop.exit();
             }
             winit::event::WindowEvent::CursorMoved { position, .. } => {
-                self.input.mouse = Vec2::new(position.x as f32, position.y as f32);
+                self.input
+                    .move_mouse(Vec2::new(position.x as f32, position.y as f32));
+            }
+            winit::event::WindowEvent::MouseWheel { delta, .. } => {
+                let lines = match delta {
+                    winit::event::MouseScrollDelta::LineDelta(_, y) => y,
+                    winit::event::MouseScrollDelta::PixelDelta(position) => {
+                        (position.y / PIXELS_PER_LINE) as f32
+                    }
+                };
+
+                self.input.add_scroll(lines);
             }
             winit::event::WindowEvent::KeyboardInput {
                 event:
@@ -125,13 +140,8 @@ impl<T: Core> winit::application::ApplicationHandler for Engine<T> {
                 ..
             } => {
                 match state {
-                    winit::event::ElementState::Pressed => {
-                        self.input.keyboard.new.insert(key_code);
-                        self.input.keyboard_down.insert(key_code);
-                    }
-                    winit::event::ElementState::Released => {
-                        self.input.keyboard.new.remove(&key_code);
-                    }
+                    winit::event::ElementState::Pressed => self.input.press(key_code),
+                    winit::event::ElementState::Released => self.input.release(key_code),
                 }
 
                 if key_code == winit::keyboard::KeyCode::Escape {
@@ -0,0 +1,91 @@
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{Core, CoreEvent, input::Input};
+
+/// Drives a core without a window or a renderer, for servers and tests.
+///
+/// Ticks happen exactly like in the engine (input, update, input cleanup)
+/// but `render` is never called. Without a tick rate it runs as fast as possible.
+pub struct Headless<T: Core> {
+    core: T,
+    input: Input,
+    timestep: Option<Duration>,
+    ticks: u64,
+}
+
+impl<T: Core> Headless<T> {
+    pub fn new(core: T) -> Self {
+        Self {
+            core,
+            input: Input::new(),
+            timestep: None,
+            ticks: 0,
+        }
+    }
+
+    pub fn with_tps(tps: u8, core: T) -> Self {
+        Self {
+            timestep: Some(Duration::from_secs_f32(1.0 / tps as f32)),
+            ..Self::new(core)
+        }
+    }
+
+    pub fn core(&self) -> &T {
+        &self.core
+    }
+
+    pub fn core_mut(&mut self) -> &mut T {
+        &mut self.core
+    }
+
+    pub fn input_mut(&mut self) -> &mut Input {
+        &mut self.input
+    }
+
+    pub fn ticks(&self) -> u64 {
+        self.ticks
+    }
+
+    pub fn event(&mut self, event: &CoreEvent) {
+        self.core.event(event);
+    }
+
+    pub fn step(&mut self) {
+        self.core.input(&self.input);
+        self.core.update();
+        self.input.update();
+
+        self.ticks += 1;
+    }
+
+    pub fn run(&mut self, ticks: u64) {
+        self.run_with(ticks, |_, _| ());
+    }
+
+    /// Runs the given amount of ticks, the script is called before every tick
+    /// with the tick about to happen so it can press and release keys.
+    pub fn run_with<F>(&mut self, ticks: u64, mut script: F)
+    where
+        F: FnMut(u64, &mut Input),
+    {
+        // advanced by a timestep every tick, multiplying would overflow on long runs
+        let mut next = Instant::now();
+
+        for _ in 0..ticks {
+            script(self.ticks, &mut self.input);
+            self.step();
+
+            if let Some(timestep) = self.timestep {
+                next += timestep;
+                let now = Instant::now();
+
+                if next > now {
+                    thread::sleep(next - now);
+                }
+            }
+        }
+    }
+}
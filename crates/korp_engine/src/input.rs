@@ -9,6 +9,8 @@ pub struct Input {
     pub(crate) keyboard: Morph<HashSet<KeyCode>>,
     pub(crate) keyboard_down: HashSet<KeyCode>,
     pub mouse: Vec2<f32>,
+    // lines scrolled since the last tick, positive is away from the user
+    pub scroll: f32,
 }
 
 impl Input {
@@ -17,6 +19,7 @@ impl Input {
             keyboard: Morph::new(HashSet::new(), HashSet::new()),
             keyboard_down: HashSet::new(),
             mouse: Vec2::new(0.0, 0.0),
+            scroll: 0.0,
         }
     }
 
@@ -24,6 +27,24 @@ impl Input {
         self.keyboard.old.clear();
         self.keyboard.old.extend(&self.keyboard.new);
         self.keyboard_down.clear();
+        self.scroll = 0.0;
+    }
+
+    pub fn press(&mut self, key: KeyCode) {
+        self.keyboard.new.insert(key);
+        self.keyboard_down.insert(key);
+    }
+
+    pub fn release(&mut self, key: KeyCode) {
+        self.keyboard.new.remove(&key);
+    }
+
+    pub fn move_mouse(&mut self, position: Vec2<f32>) {
+        self.mouse = position;
+    }
+
+    pub fn add_scroll(&mut self, lines: f32) {
+        self.scroll += lines;
     }
 
     pub fn is_pressed(&self, key: &KeyCode) -> bool {
@@ -46,3 +67,20 @@ impl Input {
         self.was_down(key) || self.is_down(key)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scroll_adds_up_until_the_tick_ends() {
+        let mut input = Input::new();
+
+        input.add_scroll(1.0);
+        input.add_scroll(-0.25);
+        assert_eq!(input.scroll, 0.75);
+
+        input.update();
+        assert_eq!(input.scroll, 0.0);
+    }
+}
@@ -1,8 +1,10 @@
 pub mod color;
 mod engine;
+mod headless;
 pub mod input;
 pub mod misc;
 pub mod renderer;
 pub mod shapes;
 
 pub use engine::*;
+pub use headless::*;
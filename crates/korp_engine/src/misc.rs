@@ -1,4 +1,4 @@
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Hash, Debug)]
 pub struct Morph<T> {
     pub old: T,
     pub new: T,
@@ -1,41 +1,45 @@
 mod camera;
+mod gpu;
+mod recorder;
 mod uniform;
 mod vertex;
 
 pub use camera::*;
 use korp_math::Vec2;
+pub use recorder::*;
+pub use vertex::Vertex;
 
 use crate::{
     color::Color,
-    renderer::{
-        uniform::{Uniform, UniformBuffer},
-        vertex::Vertex,
-    },
-    shapes::{Line, Rectangle, Triangle},
+    renderer::gpu::Gpu,
+    shapes::{Circle, Line, Rectangle, Triangle},
 };
 
-pub(crate) struct RawRenderer {
-    surface: wgpu::Surface<'static>,
-    surface_configuration: wgpu::SurfaceConfiguration,
-    device: wgpu::Device,
-    queue: wgpu::Queue,
-    shader: wgpu::ShaderModule,
-    pipeline: wgpu::RenderPipeline,
-    buffer: wgpu::Buffer,
-    uniform: Uniform,
-    clear_color: wgpu::Color,
+// edges of the polygon drawn in place of a circle
+const CIRCLE_SEGMENTS: usize = 32;
+
+/// Receives every finished frame, drawing it is up to the backend.
+pub trait Backend {
+    fn submit(&mut self, frame: &Frame);
+}
+
+/// Everything a frame draws, built on the cpu regardless of the backend.
+#[derive(Clone, Debug)]
+pub struct Frame {
     vertices: Vec<Vertex>,
-    vertices_max: usize,
     batches: Vec<RenderBatch>,
-    camera: Camera,
     view_projections: Vec<[[f32; 4]; 4]>,
     view_projection_default: [[f32; 4]; 4],
-    view_projection_stride: u32,
-    view_projections_max: usize,
+}
+
+pub(crate) struct RawRenderer {
+    frame: Frame,
+    gpu: Gpu,
 }
 
 pub struct Renderer<'a> {
-    raw: &'a mut RawRenderer,
+    frame: &'a mut Frame,
+    backend: &'a mut dyn Backend,
 }
 
 pub struct CameraRenderer<'a, 'b>
@@ -46,146 +50,69 @@ where
     pub camera: &'a Camera,
 }
 
-struct RenderBatch {
-    start: u32,
-    end: u32,
-    view_projection_idx: u32,
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RenderBatch {
+    pub start: u32,
+    pub end: u32,
+    pub view_projection_idx: u32,
 }
 
 impl Drop for CameraRenderer<'_, '_> {
     fn drop(&mut self) {
-        let idx = self.renderer.raw.batches.len() - 1;
-        let len = self.renderer.raw.vertices.len() as u32;
+        let batches = &mut self.renderer.frame.batches;
+        let idx = batches.len() - 1;
+        let len = self.renderer.frame.vertices.len() as u32;
 
         // end the current batch
-        self.renderer.raw.batches[idx].end = len;
+        batches[idx].end = len;
 
         // restore the previous batch
-        self.renderer.raw.batches.push(RenderBatch {
+        batches.push(RenderBatch {
             start: len,
             end: 0,
-            view_projection_idx: self.renderer.raw.batches[idx - 1].view_projection_idx,
+            view_projection_idx: batches[idx - 1].view_projection_idx,
         });
     }
 }
 
 impl Drop for Renderer<'_> {
     fn drop(&mut self) {
-        let idx = self.raw.batches.len() - 1;
-        let len = self.raw.vertices.len() as u32;
+        let idx = self.frame.batches.len() - 1;
+        let len = self.frame.vertices.len() as u32;
 
         // end the current batch
-        self.raw.batches[idx].end = len;
+        self.frame.batches[idx].end = len;
 
         // remove empty batches
-        self.raw.batches.retain(|x| x.start != x.end);
+        self.frame.batches.retain(|x| x.start != x.end);
 
-        self.raw.render();
+        self.backend.submit(self.frame);
     }
 }
 
-impl RawRenderer {
-    pub(crate) async fn new(
-        target: impl Into<wgpu::SurfaceTarget<'static>>,
-        width: u32,
-        height: u32,
-    ) -> Self {
-        let instance = wgpu::Instance::default();
-        let surface = instance
-            .create_surface(target)
-            .expect("could not create surface");
-
-        let adapter = instance
-            .request_adapter(&wgpu::RequestAdapterOptions {
-                power_preference: wgpu::PowerPreference::default(),
-                force_fallback_adapter: false,
-                compatible_surface: Some(&surface),
-            })
-            .await
-            .expect("could not request adapter");
-
-        let surface_capabilities = surface.get_capabilities(&adapter);
-        let surface_format = surface_capabilities.formats[0];
-        let surface_configuration = wgpu::SurfaceConfiguration {
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
-            format: surface_format,
-            width,
-            height,
-            present_mode: surface_capabilities.present_modes[0],
-            desired_maximum_frame_latency: 2,
-            alpha_mode: surface_capabilities.alpha_modes[0],
-            view_formats: Vec::new(),
-        };
-
-        let (mut device, queue) = adapter
-            .request_device(&wgpu::DeviceDescriptor {
-                label: Some("device"),
-                required_features: wgpu::Features::default(),
-                required_limits: wgpu::Limits::downlevel_defaults(),
-                experimental_features: wgpu::ExperimentalFeatures::disabled(),
-                memory_hints: wgpu::MemoryHints::default(),
-                trace: wgpu::Trace::Off,
-            })
-            .await
-            .expect("could not request device");
-
-        surface.configure(&device, &surface_configuration);
-
-        let view_projections_max = 1;
-        let uniform = Uniform::new(&mut device, view_projections_max);
-
-        let shader = device.create_shader_module(wgpu::include_wgsl!("shader.wgsl"));
-
-        let pipeline =
-            Self::create_pipeline(&mut device, &uniform, &shader, &surface_configuration);
-
-        let vertices_max = 8;
-        let buffer = Self::create_buffer(&mut device, vertices_max);
-
-        let view_projection_stride = device.limits().min_uniform_buffer_offset_alignment;
-
-        let mut camera = Camera::new(width as f32, height as f32);
-        camera.reposition(Vec2::new(width as f32 / 2.0, height as f32 / 2.0));
-
-        let view_projection_default = camera.view_projection();
-
-        Self {
-            surface,
-            surface_configuration,
-            device,
-            queue,
-            shader,
-            pipeline,
-            buffer,
-            uniform,
-            clear_color: wgpu::Color::BLACK,
+impl Frame {
+    pub fn new(width: u32, height: u32) -> Self {
+        let mut frame = Self {
             vertices: Vec::new(),
-            vertices_max,
             batches: Vec::new(),
-            camera,
             view_projections: Vec::new(),
-            view_projection_default,
-            view_projection_stride,
-            view_projections_max,
-        }
+            view_projection_default: [[0.0; 4]; 4],
+        };
+
+        frame.resize(width, height);
+        frame
     }
 
     pub fn resize(&mut self, width: u32, height: u32) {
-        self.surface_configuration.width = width;
-        self.surface_configuration.height = height;
-
-        self.surface
-            .configure(&self.device, &self.surface_configuration);
-
-        self.camera.resize(width as f32, height as f32);
-        self.camera
-            .reposition(Vec2::new(width as f32 / 2.0, height as f32 / 2.0));
+        let mut camera = Camera::new(width as f32, height as f32);
+        camera.reposition(Vec2::new(width as f32 / 2.0, height as f32 / 2.0));
 
-        self.view_projection_default = self.camera.view_projection();
+        self.view_projection_default = camera.view_projection();
     }
 
-    pub fn begin(&mut self) -> Renderer<'_> {
+    pub fn begin<'a>(&'a mut self, backend: &'a mut dyn Backend) -> Renderer<'a> {
         self.vertices.clear();
+        self.batches.clear();
         self.view_projections.clear();
 
         self.view_projections.push(self.view_projection_default);
@@ -196,179 +123,69 @@ impl RawRenderer {
             view_projection_idx: (self.view_projections.len() - 1) as u32,
         });
 
-        Renderer { raw: self }
-    }
-
-    fn render(&mut self) {
-        if self.view_projections.len() > self.view_projections_max {
-            // recreate uniform and pipeline to ensure we can support
-            // the required amount of view projections
-            while self.view_projections_max < self.view_projections.len() {
-                self.view_projections_max *= 2;
-            }
-
-            self.uniform = Uniform::new(&mut self.device, self.view_projections_max);
-            self.pipeline = Self::create_pipeline(
-                &mut self.device,
-                &self.uniform,
-                &self.shader,
-                &self.surface_configuration,
-            );
-        }
-
-        if self.vertices.len() > self.vertices_max {
-            // recreate vertex buffer to ensure we can support
-            // the required amount of vertices
-            while self.vertices_max < self.vertices.len() {
-                self.vertices_max *= 2;
-            }
-
-            self.buffer = Self::create_buffer(&mut self.device, self.vertices_max);
+        Renderer {
+            frame: self,
+            backend,
         }
+    }
 
-        let mut encoder = self
-            .device
-            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                label: Some("encoder"),
-            });
-
-        let surface_texture = self
-            .surface
-            .get_current_texture()
-            .expect("could not get current texture");
-
-        let surface_view = surface_texture
-            .texture
-            .create_view(&wgpu::TextureViewDescriptor::default());
-
-        {
-            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: Some("render_pass"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &surface_view,
-                    depth_slice: None,
-                    resolve_target: None,
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(self.clear_color),
-                        store: wgpu::StoreOp::Store,
-                    },
-                })],
-                depth_stencil_attachment: None,
-                timestamp_writes: None,
-                occlusion_query_set: None,
-                multiview_mask: None,
-            });
-
-            self.queue
-                .write_buffer(&self.buffer, 0, bytemuck::cast_slice(&self.vertices));
-
-            for (i, view_projection) in self.view_projections.iter().enumerate() {
-                let offset = i as u32 * self.view_projection_stride;
-
-                self.queue.write_buffer(
-                    &self.uniform.buffer,
-                    offset as u64,
-                    bytemuck::cast_slice(&[UniformBuffer {
-                        view_projection: *view_projection,
-                    }]),
-                );
-            }
-
-            // TODO: read more about viewport
-            // render_pass.set_viewport(x, y, w, h, min_depth, max_depth);
-
-            render_pass.set_pipeline(&self.pipeline);
-            render_pass.set_vertex_buffer(0, self.buffer.slice(..));
+    pub fn vertices(&self) -> &[Vertex] {
+        &self.vertices
+    }
 
-            for batch in self.batches.iter() {
-                render_pass.set_bind_group(
-                    0,
-                    &self.uniform.bind_group,
-                    &[batch.view_projection_idx * self.view_projection_stride],
-                );
+    pub fn batches(&self) -> &[RenderBatch] {
+        &self.batches
+    }
 
-                render_pass.draw(batch.start..batch.end, 0..1);
-            }
+    pub fn view_projections(&self) -> &[[[f32; 4]; 4]] {
+        &self.view_projections
+    }
+}
 
-            self.batches.clear();
+impl RawRenderer {
+    pub(crate) async fn new(
+        target: impl Into<wgpu::SurfaceTarget<'static>>,
+        width: u32,
+        height: u32,
+    ) -> Self {
+        Self {
+            frame: Frame::new(width, height),
+            gpu: Gpu::new(target, width, height).await,
         }
-
-        // self.window.pre_present_notify();
-        self.queue.submit(std::iter::once(encoder.finish()));
-        surface_texture.present();
     }
 
-    fn create_pipeline(
-        device: &mut wgpu::Device,
-        uniform: &Uniform,
-        shader: &wgpu::ShaderModule,
-        surface_configuration: &wgpu::SurfaceConfiguration,
-    ) -> wgpu::RenderPipeline {
-        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-            label: Some("pipeline_layout"),
-            bind_group_layouts: &[&uniform.bind_group_layout],
-            immediate_size: 0,
-        });
-
-        device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("pipeline"),
-            layout: Some(&pipeline_layout),
-            vertex: wgpu::VertexState {
-                module: &shader,
-                entry_point: Some("vs_main"),
-                compilation_options: wgpu::PipelineCompilationOptions::default(),
-                buffers: &[Vertex::description()],
-            },
-            primitive: wgpu::PrimitiveState::default(),
-            depth_stencil: None,
-            multisample: wgpu::MultisampleState::default(),
-            fragment: Some(wgpu::FragmentState {
-                module: &shader,
-                entry_point: Some("fs_main"),
-                compilation_options: wgpu::PipelineCompilationOptions::default(),
-                targets: &[Some(wgpu::ColorTargetState {
-                    format: surface_configuration.format,
-                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-            }),
-            multiview_mask: None,
-            cache: None,
-        })
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.gpu.resize(width, height);
+        self.frame.resize(width, height);
     }
 
-    fn create_buffer(device: &mut wgpu::Device, vertices_max: usize) -> wgpu::Buffer {
-        device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("buffer"),
-            size: vertices_max as u64 * std::mem::size_of::<Vertex>() as u64,
-            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
-            mapped_at_creation: false,
-        })
+    pub fn begin(&mut self) -> Renderer<'_> {
+        self.frame.begin(&mut self.gpu)
     }
 }
 
 impl<'a, 'b> Renderer<'b> {
     pub fn begin(&'a mut self, camera: &'a Camera) -> CameraRenderer<'a, 'b> {
-        let idx = self.raw.batches.len() - 1;
-        let len = self.raw.vertices.len() as u32;
+        let idx = self.frame.batches.len() - 1;
+        let len = self.frame.vertices.len() as u32;
 
         // end the current batch
-        self.raw.batches[idx].end = len;
+        self.frame.batches[idx].end = len;
 
         // try to reuse view projection if it exists
         let view_projection = camera.view_projection();
         let vp_idx = self
-            .raw
+            .frame
             .view_projections
             .iter()
             .position(|x| *x == view_projection)
             .unwrap_or_else(|| {
-                self.raw.view_projections.push(view_projection);
-                self.raw.view_projections.len() - 1
+                self.frame.view_projections.push(view_projection);
+                self.frame.view_projections.len() - 1
             });
 
         // create new batch
-        self.raw.batches.push(RenderBatch {
+        self.frame.batches.push(RenderBatch {
             start: len,
             end: 0,
             view_projection_idx: vp_idx as u32,
@@ -417,12 +234,12 @@ impl<'a, 'b> Renderer<'b> {
             ..v0
         };
 
-        self.raw.vertices.push(v0);
-        self.raw.vertices.push(v1);
-        self.raw.vertices.push(v2);
-        self.raw.vertices.push(v2);
-        self.raw.vertices.push(v3);
-        self.raw.vertices.push(v0);
+        self.frame.vertices.push(v0);
+        self.frame.vertices.push(v1);
+        self.frame.vertices.push(v2);
+        self.frame.vertices.push(v2);
+        self.frame.vertices.push(v3);
+        self.frame.vertices.push(v0);
     }
 
     pub fn draw_triangle_filled(
@@ -449,9 +266,9 @@ impl<'a, 'b> Renderer<'b> {
             ..top
         };
 
-        self.raw.vertices.push(top);
-        self.raw.vertices.push(left);
-        self.raw.vertices.push(right);
+        self.frame.vertices.push(top);
+        self.frame.vertices.push(left);
+        self.frame.vertices.push(right);
     }
 
     pub fn draw_triangle_lines(
@@ -509,12 +326,12 @@ impl<'a, 'b> Renderer<'b> {
             ..v0
         };
 
-        self.raw.vertices.push(v0);
-        self.raw.vertices.push(v1);
-        self.raw.vertices.push(v2);
-        self.raw.vertices.push(v1);
-        self.raw.vertices.push(v3);
-        self.raw.vertices.push(v2);
+        self.frame.vertices.push(v0);
+        self.frame.vertices.push(v1);
+        self.frame.vertices.push(v2);
+        self.frame.vertices.push(v1);
+        self.frame.vertices.push(v3);
+        self.frame.vertices.push(v2);
     }
 
     pub fn draw_rectangle_lines(
@@ -570,4 +387,84 @@ impl<'a, 'b> Renderer<'b> {
             color,
         );
     }
+
+    pub fn draw_circle_filled(
+        &mut self,
+        circle: Circle<f32>,
+        rotation: Vec2<f32>,
+        origin: Vec2<f32>,
+        color: Color,
+    ) {
+        self.draw_polygon_filled(&circle_corners(circle), rotation, origin, color);
+    }
+
+    pub fn draw_circle_lines(
+        &mut self,
+        circle: Circle<f32>,
+        rotation: Vec2<f32>,
+        origin: Vec2<f32>,
+        color: Color,
+    ) {
+        self.draw_polygon_lines(&circle_corners(circle), rotation, origin, color);
+    }
+
+    /// Draws a convex polygon, the corners are in order around its edges.
+    pub fn draw_polygon_filled(
+        &mut self,
+        corners: &[Vec2<f32>],
+        rotation: Vec2<f32>,
+        origin: Vec2<f32>,
+        color: Color,
+    ) {
+        let Some((&first, rest)) = corners.split_first() else {
+            return;
+        };
+
+        let v0 = Vertex {
+            position: first.into(),
+            rotation: rotation.into(),
+            origin: origin.into(),
+            color: color.into(),
+        };
+
+        // a fan of triangles around the first corner
+        for pair in rest.windows(2) {
+            self.frame.vertices.push(v0);
+            self.frame.vertices.push(Vertex {
+                position: pair[0].into(),
+                ..v0
+            });
+            self.frame.vertices.push(Vertex {
+                position: pair[1].into(),
+                ..v0
+            });
+        }
+    }
+
+    pub fn draw_polygon_lines(
+        &mut self,
+        corners: &[Vec2<f32>],
+        rotation: Vec2<f32>,
+        origin: Vec2<f32>,
+        color: Color,
+    ) {
+        for i in 0..corners.len() {
+            self.draw_line(
+                Line {
+                    start: corners[i],
+                    end: corners[(i + 1) % corners.len()],
+                },
+                rotation,
+                origin,
+                color,
+            );
+        }
+    }
+}
+
+fn circle_corners(circle: Circle<f32>) -> [Vec2<f32>; CIRCLE_SEGMENTS] {
+    core::array::from_fn(|i| {
+        let angle = i as f32 * std::f32::consts::TAU / CIRCLE_SEGMENTS as f32;
+        circle.center + Vec2::<f32>::from_angle(angle) * circle.radius
+    })
 }
@@ -1,10 +1,15 @@
-use korp_math::Vec2;
+use korp_math::{Transform2, Vec2};
 
 #[derive(Clone)]
 pub struct Camera {
     half_width: f32,
     half_height: f32,
     position: Vec2<f32>,
+    // displacement on top of the position, e.g. for shaking
+    offset: Vec2<f32>,
+    zoom: f32,
+    // radians, the world is rotated the opposite way on screen
+    rotation: f32,
 }
 
 impl Camera {
@@ -13,6 +18,9 @@ impl Camera {
             half_width: width * 0.5,
             half_height: height * 0.5,
             position: Vec2::new(0.0, 0.0),
+            offset: Vec2::new(0.0, 0.0),
+            zoom: 1.0,
+            rotation: 0.0,
         }
     }
 
@@ -25,28 +33,61 @@ impl Camera {
         self.half_height = height * 0.5;
     }
 
+    pub fn displace(&mut self, offset: Vec2<f32>) {
+        self.offset = offset;
+    }
+
+    pub fn zoom(&mut self, zoom: f32) {
+        self.zoom = zoom.max(f32::EPSILON);
+    }
+
+    pub fn rotate(&mut self, rotation: f32) {
+        self.rotation = rotation;
+    }
+
+    /// Size of the window, in pixels.
+    pub fn size(&self) -> Vec2<f32> {
+        Vec2::new(self.half_width * 2.0, self.half_height * 2.0)
+    }
+
+    /// Maps a position in the window, in pixels from the top left, onto the world.
+    pub fn screen_to_world(&self, screen: Vec2<f32>) -> Vec2<f32> {
+        let center = Vec2::new(self.half_width, self.half_height);
+
+        self.transform().apply(screen - center)
+    }
+
+    pub fn world_to_screen(&self, world: Vec2<f32>) -> Vec2<f32> {
+        let center = Vec2::new(self.half_width, self.half_height);
+
+        self.view().apply(world) + center
+    }
+
     pub(crate) fn view_projection(&self) -> [[f32; 4]; 4] {
-        let left = self.position.x - self.half_width;
-        let right = self.position.x + self.half_width;
-        let top = self.position.y + self.half_height;
-        let bottom = self.position.y - self.half_height;
-        let near = 0.0;
-        let far = 1.0;
+        // the y axis points down, like the window
+        let projection =
+            Transform2::<f32>::scale(Vec2::new(1.0 / self.half_width, -1.0 / self.half_height));
 
-        ortho(left, right, bottom, top, near, far)
+        (projection * self.view()).to_mat4()
     }
-}
 
-fn ortho(left: f32, right: f32, top: f32, bottom: f32, near: f32, far: f32) -> [[f32; 4]; 4] {
-    [
-        [2.0 / (right - left), 0.0, 0.0, 0.0],
-        [0.0, 2.0 / (top - bottom), 0.0, 0.0],
-        [0.0, 0.0, 1.0 / (far - near), 0.0],
-        [
-            -(right + left) / (right - left),
-            -(top + bottom) / (top - bottom),
-            -(near / (far - near)),
-            1.0,
-        ],
-    ]
+    // from the view, in pixels around the center of the window, to the world
+    fn transform(&self) -> Transform2<f32> {
+        let rotation = Vec2::<f32>::from_angle(self.rotation);
+        let scale = Transform2::<f32>::scale(Vec2::new(1.0 / self.zoom, 1.0 / self.zoom));
+
+        Transform2::<f32>::new(self.eye(), rotation) * scale
+    }
+
+    // from the world to the view, the inverse of the transform
+    fn view(&self) -> Transform2<f32> {
+        let rotation = Vec2::<f32>::from_angle(-self.rotation);
+        let scale = Transform2::<f32>::scale(Vec2::new(self.zoom, self.zoom));
+
+        scale * Transform2::<f32>::rotation(rotation) * Transform2::<f32>::translation(-self.eye())
+    }
+
+    fn eye(&self) -> Vec2<f32> {
+        self.position + self.offset
+    }
 }
@@ -0,0 +1,252 @@
+use crate::renderer::{
+    Backend, Frame,
+    uniform::{Uniform, UniformBuffer},
+    vertex::Vertex,
+};
+
+pub(super) struct Gpu {
+    surface: wgpu::Surface<'static>,
+    surface_configuration: wgpu::SurfaceConfiguration,
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    shader: wgpu::ShaderModule,
+    pipeline: wgpu::RenderPipeline,
+    buffer: wgpu::Buffer,
+    uniform: Uniform,
+    clear_color: wgpu::Color,
+    vertices_max: usize,
+    view_projection_stride: u32,
+    view_projections_max: usize,
+}
+
+impl Gpu {
+    pub(super) async fn new(
+        target: impl Into<wgpu::SurfaceTarget<'static>>,
+        width: u32,
+        height: u32,
+    ) -> Self {
+        let instance = wgpu::Instance::default();
+        let surface = instance
+            .create_surface(target)
+            .expect("could not create surface");
+
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::default(),
+                force_fallback_adapter: false,
+                compatible_surface: Some(&surface),
+            })
+            .await
+            .expect("could not request adapter");
+
+        let surface_capabilities = surface.get_capabilities(&adapter);
+        let surface_format = surface_capabilities.formats[0];
+        let surface_configuration = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format: surface_format,
+            width,
+            height,
+            present_mode: surface_capabilities.present_modes[0],
+            desired_maximum_frame_latency: 2,
+            alpha_mode: surface_capabilities.alpha_modes[0],
+            view_formats: Vec::new(),
+        };
+
+        let (mut device, queue) = adapter
+            .request_device(&wgpu::DeviceDescriptor {
+                label: Some("device"),
+                required_features: wgpu::Features::default(),
+                required_limits: wgpu::Limits::downlevel_defaults(),
+                experimental_features: wgpu::ExperimentalFeatures::disabled(),
+                memory_hints: wgpu::MemoryHints::default(),
+                trace: wgpu::Trace::Off,
+            })
+            .await
+            .expect("could not request device");
+
+        surface.configure(&device, &surface_configuration);
+
+        let view_projections_max = 1;
+        let uniform = Uniform::new(&mut device, view_projections_max);
+
+        let shader = device.create_shader_module(wgpu::include_wgsl!("../shader.wgsl"));
+
+        let pipeline =
+            Self::create_pipeline(&mut device, &uniform, &shader, &surface_configuration);
+
+        let vertices_max = 8;
+        let buffer = Self::create_buffer(&mut device, vertices_max);
+
+        let view_projection_stride = device.limits().min_uniform_buffer_offset_alignment;
+
+        Self {
+            surface,
+            surface_configuration,
+            device,
+            queue,
+            shader,
+            pipeline,
+            buffer,
+            uniform,
+            clear_color: wgpu::Color::BLACK,
+            vertices_max,
+            view_projection_stride,
+            view_projections_max,
+        }
+    }
+
+    pub(super) fn resize(&mut self, width: u32, height: u32) {
+        self.surface_configuration.width = width;
+        self.surface_configuration.height = height;
+
+        self.surface
+            .configure(&self.device, &self.surface_configuration);
+    }
+
+    fn create_pipeline(
+        device: &mut wgpu::Device,
+        uniform: &Uniform,
+        shader: &wgpu::ShaderModule,
+        surface_configuration: &wgpu::SurfaceConfiguration,
+    ) -> wgpu::RenderPipeline {
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("pipeline_layout"),
+            bind_group_layouts: &[&uniform.bind_group_layout],
+            immediate_size: 0,
+        });
+
+        device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: shader,
+                entry_point: Some("vs_main"),
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+                buffers: &[Vertex::description()],
+            },
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            fragment: Some(wgpu::FragmentState {
+                module: shader,
+                entry_point: Some("fs_main"),
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: surface_configuration.format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            multiview_mask: None,
+            cache: None,
+        })
+    }
+
+    fn create_buffer(device: &mut wgpu::Device, vertices_max: usize) -> wgpu::Buffer {
+        device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("buffer"),
+            size: vertices_max as u64 * std::mem::size_of::<Vertex>() as u64,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        })
+    }
+}
+
+impl Backend for Gpu {
+    fn submit(&mut self, frame: &Frame) {
+        if frame.view_projections.len() > self.view_projections_max {
+            // recreate uniform and pipeline to ensure we can support
+            // the required amount of view projections
+            while self.view_projections_max < frame.view_projections.len() {
+                self.view_projections_max *= 2;
+            }
+
+            self.uniform = Uniform::new(&mut self.device, self.view_projections_max);
+            self.pipeline = Self::create_pipeline(
+                &mut self.device,
+                &self.uniform,
+                &self.shader,
+                &self.surface_configuration,
+            );
+        }
+
+        if frame.vertices.len() > self.vertices_max {
+            // recreate vertex buffer to ensure we can support
+            // the required amount of vertices
+            while self.vertices_max < frame.vertices.len() {
+                self.vertices_max *= 2;
+            }
+
+            self.buffer = Self::create_buffer(&mut self.device, self.vertices_max);
+        }
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("encoder"),
+            });
+
+        let surface_texture = self
+            .surface
+            .get_current_texture()
+            .expect("could not get current texture");
+
+        let surface_view = surface_texture
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("render_pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &surface_view,
+                    depth_slice: None,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(self.clear_color),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+                multiview_mask: None,
+            });
+
+            self.queue
+                .write_buffer(&self.buffer, 0, bytemuck::cast_slice(&frame.vertices));
+
+            for (i, view_projection) in frame.view_projections.iter().enumerate() {
+                let offset = i as u32 * self.view_projection_stride;
+
+                self.queue.write_buffer(
+                    &self.uniform.buffer,
+                    offset as u64,
+                    bytemuck::cast_slice(&[UniformBuffer {
+                        view_projection: *view_projection,
+                    }]),
+                );
+            }
+
+            // TODO: read more about viewport
+            // render_pass.set_viewport(x, y, w, h, min_depth, max_depth);
+
+            render_pass.set_pipeline(&self.pipeline);
+            render_pass.set_vertex_buffer(0, self.buffer.slice(..));
+
+            for batch in frame.batches.iter() {
+                render_pass.set_bind_group(
+                    0,
+                    &self.uniform.bind_group,
+                    &[batch.view_projection_idx * self.view_projection_stride],
+                );
+
+                render_pass.draw(batch.start..batch.end, 0..1);
+            }
+        }
+
+        // self.window.pre_present_notify();
+        self.queue.submit(std::iter::once(encoder.finish()));
+        surface_texture.present();
+    }
+}
@@ -0,0 +1,233 @@
+use crate::renderer::{Backend, Frame, Renderer};
+
+/// Keeps the last submitted frame instead of drawing it,
+/// so whatever a frame would draw can be inspected without a gpu.
+pub struct Recorder {
+    frame: Frame,
+    recording: Recording,
+}
+
+struct Recording {
+    frame: Option<Frame>,
+    frames: u64,
+}
+
+impl Recorder {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            frame: Frame::new(width, height),
+            recording: Recording {
+                frame: None,
+                frames: 0,
+            },
+        }
+    }
+
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.frame.resize(width, height);
+    }
+
+    pub fn begin(&mut self) -> Renderer<'_> {
+        self.frame.begin(&mut self.recording)
+    }
+
+    pub fn frame(&self) -> Option<&Frame> {
+        self.recording.frame.as_ref()
+    }
+
+    pub fn frames(&self) -> u64 {
+        self.recording.frames
+    }
+}
+
+impl Backend for Recording {
+    fn submit(&mut self, frame: &Frame) {
+        match &mut self.frame {
+            Some(recorded) => recorded.clone_from(frame),
+            None => self.frame = Some(frame.clone()),
+        }
+
+        self.frames += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use korp_math::Vec2;
+
+    use super::*;
+    use crate::{
+        color::Color,
+        renderer::{Camera, RenderBatch, Vertex},
+        shapes::{Circle, Line, Rectangle, Triangle},
+    };
+
+    const UP: Vec2<f32> = Vec2::new(1.0, 0.0);
+
+    fn positions(vertices: &[Vertex]) -> Vec<[f32; 2]> {
+        vertices.iter().map(|vertex| vertex.position).collect()
+    }
+
+    #[test]
+    fn records_a_scene() {
+        let mut recorder = Recorder::new(800, 600);
+        let mut camera = Camera::new(800.0, 600.0);
+        camera.reposition(Vec2::new(100.0, 50.0));
+
+        assert!(recorder.frame().is_none());
+
+        {
+            let mut renderer = recorder.begin();
+
+            renderer.draw_rectangle_filled(
+                Rectangle {
+                    x: 10.0,
+                    y: 20.0,
+                    width: 30.0,
+                    height: 40.0,
+                },
+                UP,
+                Vec2::new(25.0, 40.0),
+                Color::GREEN,
+            );
+
+            {
+                let scope = renderer.begin(&camera);
+                scope.renderer.draw_triangle_filled(
+                    Triangle {
+                        top: Vec2::new(0.0, -10.0),
+                        left: Vec2::new(-5.0, 5.0),
+                        right: Vec2::new(5.0, 5.0),
+                    },
+                    UP,
+                    Vec2::new(100.0, 50.0),
+                    Color::RED,
+                );
+                scope.renderer.draw_circle_filled(
+                    Circle {
+                        center: Vec2::new(3.0, 4.0),
+                        radius: 2.0,
+                    },
+                    UP,
+                    Vec2::new(0.0, 0.0),
+                    Color::BLUE,
+                );
+            }
+
+            renderer.draw_line(
+                Line {
+                    start: Vec2::new(0.0, 0.0),
+                    end: Vec2::new(10.0, 0.0),
+                },
+                UP,
+                Vec2::new(0.0, 0.0),
+                Color::WHITE,
+            );
+        }
+
+        assert_eq!(recorder.frames(), 1);
+        let frame = recorder.frame().unwrap();
+        let vertices = frame.vertices();
+
+        // 6 for the rectangle, 3 for the triangle, a fan of 30 triangles for
+        // the circle and 6 for the line
+        assert_eq!(vertices.len(), 6 + 3 + 90 + 6);
+
+        assert_eq!(
+            positions(&vertices[..6]),
+            [
+                [10.0, 20.0],
+                [40.0, 20.0],
+                [10.0, 60.0],
+                [40.0, 20.0],
+                [40.0, 60.0],
+                [10.0, 60.0],
+            ]
+        );
+        assert!(vertices[..6].iter().all(|vertex| {
+            vertex.color == u32::from(Color::GREEN) && vertex.origin == [25.0, 40.0]
+        }));
+
+        assert_eq!(
+            positions(&vertices[6..9]),
+            [[0.0, -10.0], [-5.0, 5.0], [5.0, 5.0]]
+        );
+        assert!(vertices[6..9].iter().all(|vertex| {
+            vertex.color == u32::from(Color::RED) && vertex.origin == [100.0, 50.0]
+        }));
+
+        for vertex in vertices[9..99].iter() {
+            let [x, y] = vertex.position;
+            let distance = Vec2::new(x - 3.0, y - 4.0).len();
+
+            assert!((distance - 2.0).abs() < 1e-5, "{:?}", vertex);
+            assert_eq!(vertex.color, u32::from(Color::BLUE));
+        }
+
+        // a line is a quad half a pixel to either side
+        assert_eq!(
+            positions(&vertices[99..]),
+            [
+                [0.0, 0.5],
+                [10.0, 0.5],
+                [10.0, -0.5],
+                [10.0, -0.5],
+                [0.0, -0.5],
+                [0.0, 0.5],
+            ]
+        );
+
+        // the camera batch sits between two batches drawn without it
+        assert_eq!(
+            frame.batches(),
+            [
+                RenderBatch {
+                    start: 0,
+                    end: 6,
+                    view_projection_idx: 0,
+                },
+                RenderBatch {
+                    start: 6,
+                    end: 99,
+                    view_projection_idx: 1,
+                },
+                RenderBatch {
+                    start: 99,
+                    end: 105,
+                    view_projection_idx: 0,
+                },
+            ]
+        );
+        assert_eq!(frame.view_projections().len(), 2);
+        assert_eq!(frame.view_projections()[1], camera.view_projection());
+    }
+
+    #[test]
+    fn keeps_only_the_last_frame() {
+        let mut recorder = Recorder::new(800, 600);
+        let triangle = Triangle {
+            top: Vec2::new(0.0, -1.0),
+            left: Vec2::new(-1.0, 1.0),
+            right: Vec2::new(1.0, 1.0),
+        };
+
+        for count in 1..=3 {
+            let mut renderer = recorder.begin();
+
+            for _ in 0..count {
+                renderer.draw_triangle_filled(triangle, UP, Vec2::new(0.0, 0.0), Color::WHITE);
+            }
+        }
+
+        assert_eq!(recorder.frames(), 3);
+        assert_eq!(recorder.frame().unwrap().vertices().len(), 9);
+
+        // nothing drawn still submits a frame, without any batches
+        recorder.begin();
+
+        assert_eq!(recorder.frames(), 4);
+        let frame = recorder.frame().unwrap();
+        assert!(frame.vertices().is_empty());
+        assert!(frame.batches().is_empty());
+    }
+}
@@ -1,10 +1,10 @@
 #[repr(C)]
-#[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable, Default)]
-pub(super) struct Vertex {
-    pub(super) position: [f32; 2],
-    pub(super) rotation: [f32; 2],
-    pub(super) origin: [f32; 2],
-    pub(super) color: u32,
+#[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable, Default, Debug, PartialEq)]
+pub struct Vertex {
+    pub position: [f32; 2],
+    pub rotation: [f32; 2],
+    pub origin: [f32; 2],
+    pub color: u32,
 }
 
 impl Vertex {
@@ -14,6 +14,12 @@ pub struct Triangle<T> {
 }
 
 #[derive(Copy, Clone, Debug)]
+pub struct Circle<T> {
+    pub center: Vec2<T>,
+    pub radius: T,
+}
+
+#[derive(Copy, Clone, Hash, Debug)]
 pub struct Rectangle<T> {
     pub x: T,
     pub y: T,
@@ -38,6 +44,15 @@ impl Triangle<f32> {
     }
 }
 
+impl Circle<f32> {
+    pub fn from(radius: impl Into<f32>, centroid: impl Into<Vec2<f32>>) -> Circle<f32> {
+        Circle {
+            center: centroid.into(),
+            radius: radius.into(),
+        }
+    }
+}
+
 impl Rectangle<f32> {
     pub fn from(
         width: impl Into<f32>,
@@ -66,13 +81,13 @@ impl Rectangle<Flint> {
     }
 }
 
-impl Into<Rectangle<f32>> for Rectangle<Flint> {
-    fn into(self) -> Rectangle<f32> {
+impl From<Rectangle<Flint>> for Rectangle<f32> {
+    fn from(value: Rectangle<Flint>) -> Self {
         Rectangle {
-            x: self.x.into(),
-            y: self.y.into(),
-            width: self.width.into(),
-            height: self.height.into(),
+            x: value.x.into(),
+            y: value.y.into(),
+            width: value.width.into(),
+            height: value.height.into(),
         }
     }
 }
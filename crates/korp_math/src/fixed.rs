@@ -0,0 +1,358 @@
+#[macro_use]
+mod arithmetic;
+#[macro_use]
+mod exponential;
+#[macro_use]
+mod format;
+#[macro_use]
+mod trigonometry;
+
+pub use format::ParseFixedError;
+pub(crate) use format::{parse, split};
+
+// constants are kept in 2.62 and rounded down to the precision of each type
+pub(crate) const PRECISION: u32 = 62;
+
+pub(crate) const PI: u64 = 0xc90f_daa2_2168_c235;
+pub(crate) const LN_2: u64 = 0x2c5c_85fd_f473_de6b;
+
+// product of the cos(atan(2^-i)), undoes the growth of the cordic rotations
+pub(crate) const CORDIC_GAIN: u64 = 0x26dd_3b6a_10d7_969a;
+// atan(2^-i), enough iterations for 32 fractional bits
+pub(crate) const CORDIC_ATAN: [u64; 32] = [
+    0x3243_f6a8_885a_308d,
+    0x1dac_6705_61bb_4f69,
+    0x0fad_bafc_9640_6eb1,
+    0x07f5_6ea6_ab0b_db72,
+    0x03fe_ab76_e59f_bd39,
+    0x01ff_d55b_ba97_624b,
+    0x00ff_faaa_dddb_94d6,
+    0x007f_ff55_56ee_ea5d,
+    0x003f_ffea_aab7_776e,
+    0x001f_fffd_5555_bbbc,
+    0x000f_ffff_aaaa_adde,
+    0x0007_ffff_f555_556f,
+    0x0003_ffff_feaa_aaab,
+    0x0001_ffff_ffd5_5555,
+    0x0000_ffff_ffff_aaab,
+    0x0000_7fff_ffff_5555,
+    0x0000_3fff_ffff_eaab,
+    0x0000_1fff_ffff_fd55,
+    0x0000_0fff_ffff_ffab,
+    0x0000_07ff_ffff_fff5,
+    0x0000_03ff_ffff_ffff,
+    0x0000_0200_0000_0000,
+    0x0000_0100_0000_0000,
+    0x0000_0080_0000_0000,
+    0x0000_0040_0000_0000,
+    0x0000_0020_0000_0000,
+    0x0000_0010_0000_0000,
+    0x0000_0008_0000_0000,
+    0x0000_0004_0000_0000,
+    0x0000_0002_0000_0000,
+    0x0000_0001_0000_0000,
+    0x0000_0000_8000_0000,
+];
+
+/// Rounds a 2.62 constant to the nearest with the given fractional bits.
+pub(crate) const fn constant(value: u64, shift: u32) -> u64 {
+    let dropped = PRECISION - shift;
+
+    if dropped == 0 {
+        return value;
+    }
+
+    (value + (1 << (dropped - 1))) >> dropped
+}
+
+/// Defines a fixed point number backed by a signed integer, with `shift`
+/// fractional bits and every operation carried out in the `wide` integers,
+/// which must be twice as large.
+macro_rules! fixed {
+    (
+        $(#[$meta:meta])*
+        $name:ident {
+            raw: $raw:ty,
+            wide: $wide:ty,
+            unsigned_wide: $uwide:ty,
+            integer: $int:ty,
+            fraction: $fraction:ty,
+            shift: $shift:literal,
+            from_integer: $from_int:ident,
+            to_integer: $to_int:ident $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Copy, Clone, Default, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+        pub struct $name {
+            pub raw: $raw,
+        }
+
+        impl $name {
+            pub const POINT_FIVE: $fraction = Self::HALF_SCALE as $fraction;
+            pub const POINT_ONE: $fraction = Self::POINT_FIVE / 5;
+
+            pub const ZERO_FIVE: Self = Self::new(0, Self::POINT_FIVE);
+
+            pub const ZERO: Self = Self::new(0, 0);
+            pub const ONE: Self = Self::new(1, 0);
+            pub const NEG_ONE: Self = Self::new(-1, 0);
+
+            pub const MIN: Self = Self::from_raw(<$raw>::MIN);
+            pub const MAX: Self = Self::from_raw(<$raw>::MAX);
+
+            pub const PI: Self =
+                Self::from_raw($crate::fixed::constant($crate::fixed::PI, $shift) as $raw);
+            pub const FRAC_PI_2: Self = Self::from_raw(Self::PI.raw / 2);
+            pub const TAU: Self =
+                Self::from_raw($crate::fixed::constant($crate::fixed::PI, $shift + 1) as $raw);
+            pub const DEG2RAD: Self =
+                Self::from_raw($crate::fixed::constant($crate::fixed::PI / 180, $shift) as $raw);
+            // divided by pi at its full precision, the rounded one is off by a few steps
+            pub const RAD2DEG: Self = Self::from_raw(
+                (((180 << ($crate::fixed::PRECISION + $shift)) + ($crate::fixed::PI as u128 >> 1))
+                    / $crate::fixed::PI as u128) as $raw,
+            );
+
+            const SHIFT: u32 = $shift;
+            const SCALE: $raw = 1 << Self::SHIFT;
+            const HALF_SCALE: $raw = Self::SCALE >> 1;
+            const INV_SCALE: f64 = 1.0 / Self::SCALE as f64;
+
+            const CORDIC_GAIN: $raw =
+                $crate::fixed::constant($crate::fixed::CORDIC_GAIN, $shift) as $raw;
+            const CORDIC_ATAN: [$raw; $shift] = {
+                let mut table = [0; $shift];
+                let mut i = 0;
+
+                while i < $shift {
+                    table[i] = $crate::fixed::constant($crate::fixed::CORDIC_ATAN[i], $shift) as $raw;
+                    i += 1;
+                }
+
+                table
+            };
+
+            #[inline]
+            pub const fn new(value: $int, fraction: $fraction) -> Self {
+                Self {
+                    raw: (value as $raw) << Self::SHIFT | fraction as $raw,
+                }
+            }
+
+            #[inline]
+            pub const fn from_raw(raw: $raw) -> Self {
+                Self { raw }
+            }
+
+            #[inline]
+            pub const fn $from_int(value: $int) -> Self {
+                Self {
+                    raw: (value as $raw) << Self::SHIFT,
+                }
+            }
+
+            #[inline]
+            pub const fn $to_int(self) -> $int {
+                (self.raw >> Self::SHIFT) as $int
+            }
+
+            #[inline]
+            pub const fn to_f32(self) -> f32 {
+                self.to_f64() as f32
+            }
+
+            #[inline]
+            pub const fn to_f64(self) -> f64 {
+                self.raw as f64 * Self::INV_SCALE
+            }
+
+            /// Rounds to the nearest, saturates out of range and maps NaN to zero.
+            /// Usable in constants, e.g. `const DRAG: Flint = Flint::from_f64(0.2);`
+            #[inline]
+            pub const fn from_f64(value: f64) -> Self {
+                Self {
+                    raw: (value * Self::SCALE as f64).round() as $raw,
+                }
+            }
+
+            #[inline]
+            pub const fn from_f32(value: f32) -> Self {
+                Self::from_f64(value as f64)
+            }
+
+            #[inline]
+            pub const fn abs(self) -> Self {
+                Self {
+                    raw: self.raw.abs(),
+                }
+            }
+
+            #[inline]
+            pub fn sqrt(self) -> Self {
+                if self.raw <= 0 {
+                    return Self::ZERO;
+                }
+
+                Self {
+                    raw: ((self.raw as $uwide) << Self::SHIFT).isqrt() as $raw,
+                }
+            }
+
+            /// Length of the vector (x, y), saturates when it does not fit.
+            #[inline]
+            pub fn hypot(x: Self, y: Self) -> Self {
+                // squared in the wide integers where it cannot overflow
+                let x = x.raw.unsigned_abs() as $uwide;
+                let y = y.raw.unsigned_abs() as $uwide;
+                let raw = (x * x + y * y).isqrt();
+
+                Self {
+                    raw: raw.min(<$raw>::MAX as $uwide) as $raw,
+                }
+            }
+
+            #[inline]
+            pub fn to_radians(self) -> Self {
+                self * Self::DEG2RAD
+            }
+
+            #[inline]
+            pub fn to_degrees(self) -> Self {
+                self * Self::RAD2DEG
+            }
+
+            #[inline]
+            pub fn sin_cos(self) -> (Self, Self) {
+                // into [-PI, PI) with a single modulo, then half a turn away
+                // from the range where the rotations converge at most
+                let mut z = self.raw.rem_euclid(Self::TAU.raw);
+                let mut negative = false;
+
+                if z >= Self::PI.raw {
+                    z -= Self::TAU.raw;
+                }
+
+                if z > Self::FRAC_PI_2.raw {
+                    z -= Self::PI.raw;
+                    negative = true;
+                } else if z < -Self::FRAC_PI_2.raw {
+                    z += Self::PI.raw;
+                    negative = true;
+                }
+
+                let mut x = Self::CORDIC_GAIN;
+                let mut y = 0;
+
+                for (i, atan) in Self::CORDIC_ATAN.iter().enumerate() {
+                    let direction = if z < 0 { -1 } else { 1 };
+                    let xx = x;
+
+                    x -= direction * (y >> i);
+                    y += direction * (xx >> i);
+                    z -= direction * atan;
+                }
+
+                if negative {
+                    x = -x;
+                    y = -y;
+                }
+
+                (Self { raw: y }, Self { raw: x })
+            }
+        }
+
+        impl std::ops::Add for $name {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self::Output {
+                Self {
+                    raw: self.raw + rhs.raw,
+                }
+            }
+        }
+
+        impl std::ops::AddAssign for $name {
+            fn add_assign(&mut self, rhs: Self) {
+                self.raw += rhs.raw;
+            }
+        }
+
+        impl std::ops::AddAssign<$int> for $name {
+            fn add_assign(&mut self, rhs: $int) {
+                self.raw += (rhs as $raw) << Self::SHIFT;
+            }
+        }
+
+        impl std::ops::SubAssign for $name {
+            fn sub_assign(&mut self, rhs: Self) {
+                self.raw -= rhs.raw;
+            }
+        }
+
+        impl std::ops::Mul<$int> for $name {
+            type Output = Self;
+
+            fn mul(self, rhs: $int) -> Self::Output {
+                self * Self::$from_int(rhs)
+            }
+        }
+
+        impl std::ops::Sub for $name {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self::Output {
+                Self {
+                    raw: self.raw - rhs.raw,
+                }
+            }
+        }
+
+        impl std::ops::Mul for $name {
+            type Output = Self;
+
+            fn mul(self, rhs: Self) -> Self::Output {
+                Self::truncate(self.widened_mul(rhs), "mul")
+            }
+        }
+
+        impl std::ops::Div for $name {
+            type Output = Self;
+
+            fn div(self, rhs: Self) -> Self::Output {
+                Self::truncate(self.widened_div(rhs), "div")
+            }
+        }
+
+        impl std::ops::DivAssign for $name {
+            fn div_assign(&mut self, rhs: Self) {
+                *self = Self::truncate(self.widened_div(rhs), "div");
+            }
+        }
+
+        impl std::ops::Neg for $name {
+            type Output = Self;
+
+            fn neg(self) -> Self::Output {
+                Self { raw: -self.raw }
+            }
+        }
+
+        impl From<$int> for $name {
+            fn from(value: $int) -> Self {
+                Self::$from_int(value)
+            }
+        }
+
+        impl From<$name> for f32 {
+            fn from(value: $name) -> Self {
+                value.to_f32()
+            }
+        }
+
+        fixed_arithmetic!($name, $raw, $wide);
+        fixed_exponential!($name, $raw, $wide, $shift);
+        fixed_format!($name, $raw);
+        fixed_trigonometry!($name, $raw, $wide, $uwide);
+    };
+}
@@ -0,0 +1,295 @@
+// every operation is carried out in the wide integers, where products cannot
+// overflow, and only narrowed back to the raw integer at the end
+macro_rules! fixed_arithmetic {
+    ($name:ident, $raw:ty, $wide:ty) => {
+        impl $name {
+            #[inline]
+            pub const fn checked_add(self, rhs: Self) -> Option<Self> {
+                match self.raw.checked_add(rhs.raw) {
+                    Some(raw) => Some(Self { raw }),
+                    None => None,
+                }
+            }
+
+            #[inline]
+            pub const fn checked_sub(self, rhs: Self) -> Option<Self> {
+                match self.raw.checked_sub(rhs.raw) {
+                    Some(raw) => Some(Self { raw }),
+                    None => None,
+                }
+            }
+
+            #[inline]
+            pub const fn checked_mul(self, rhs: Self) -> Option<Self> {
+                Self::narrow(Self::widened_mul(self, rhs))
+            }
+
+            /// `None` when dividing by zero or when the quotient does not fit.
+            #[inline]
+            pub const fn checked_div(self, rhs: Self) -> Option<Self> {
+                if rhs.raw == 0 {
+                    return None;
+                }
+
+                Self::narrow(Self::widened_div(self, rhs))
+            }
+
+            #[inline]
+            pub const fn checked_neg(self) -> Option<Self> {
+                match self.raw.checked_neg() {
+                    Some(raw) => Some(Self { raw }),
+                    None => None,
+                }
+            }
+
+            #[inline]
+            pub const fn saturating_add(self, rhs: Self) -> Self {
+                Self {
+                    raw: self.raw.saturating_add(rhs.raw),
+                }
+            }
+
+            #[inline]
+            pub const fn saturating_sub(self, rhs: Self) -> Self {
+                Self {
+                    raw: self.raw.saturating_sub(rhs.raw),
+                }
+            }
+
+            #[inline]
+            pub const fn saturating_mul(self, rhs: Self) -> Self {
+                Self::clamp_wide(Self::widened_mul(self, rhs))
+            }
+
+            /// Dividing by zero saturates towards the sign of the dividend, zero by zero is zero.
+            #[inline]
+            pub const fn saturating_div(self, rhs: Self) -> Self {
+                if rhs.raw == 0 {
+                    return match self.raw {
+                        0 => Self::ZERO,
+                        raw if raw > 0 => Self::MAX,
+                        _ => Self::MIN,
+                    };
+                }
+
+                Self::clamp_wide(Self::widened_div(self, rhs))
+            }
+
+            #[inline]
+            pub const fn saturating_neg(self) -> Self {
+                Self {
+                    raw: self.raw.saturating_neg(),
+                }
+            }
+
+            #[inline]
+            pub const fn wrapping_add(self, rhs: Self) -> Self {
+                Self {
+                    raw: self.raw.wrapping_add(rhs.raw),
+                }
+            }
+
+            #[inline]
+            pub const fn wrapping_sub(self, rhs: Self) -> Self {
+                Self {
+                    raw: self.raw.wrapping_sub(rhs.raw),
+                }
+            }
+
+            #[inline]
+            pub const fn wrapping_mul(self, rhs: Self) -> Self {
+                Self {
+                    raw: Self::widened_mul(self, rhs) as $raw,
+                }
+            }
+
+            /// Panics when dividing by zero, like the integer one.
+            #[inline]
+            pub const fn wrapping_div(self, rhs: Self) -> Self {
+                Self {
+                    raw: Self::widened_div(self, rhs) as $raw,
+                }
+            }
+
+            #[inline]
+            pub const fn wrapping_neg(self) -> Self {
+                Self {
+                    raw: self.raw.wrapping_neg(),
+                }
+            }
+
+            #[inline]
+            const fn widened_mul(self, rhs: Self) -> $wide {
+                (self.raw as $wide * rhs.raw as $wide) / Self::SCALE as $wide
+            }
+
+            #[inline]
+            const fn widened_div(self, rhs: Self) -> $wide {
+                (self.raw as $wide * Self::SCALE as $wide) / rhs.raw as $wide
+            }
+
+            // narrows the result of an operator, asserting it fits when overflow checks are enabled
+            #[inline]
+            fn truncate(raw: $wide, operation: &str) -> Self {
+                #[cfg(feature = "overflow-checks")]
+                debug_assert!(
+                    raw >= <$raw>::MIN as $wide && raw <= <$raw>::MAX as $wide,
+                    "{} overflow in {operation}",
+                    stringify!($name)
+                );
+                #[cfg(not(feature = "overflow-checks"))]
+                let _ = operation;
+
+                Self { raw: raw as $raw }
+            }
+
+            #[inline]
+            const fn narrow(raw: $wide) -> Option<Self> {
+                if raw < <$raw>::MIN as $wide || raw > <$raw>::MAX as $wide {
+                    return None;
+                }
+
+                Some(Self { raw: raw as $raw })
+            }
+
+            #[inline]
+            const fn clamp_wide(raw: $wide) -> Self {
+                if raw < <$raw>::MIN as $wide {
+                    Self::MIN
+                } else if raw > <$raw>::MAX as $wide {
+                    Self::MAX
+                } else {
+                    Self { raw: raw as $raw }
+                }
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    // the same edges for every fixed point type, only the widths differ
+    macro_rules! edges {
+        ($module:ident, $name:ident) => {
+            mod $module {
+                use crate::{Vec2, $name};
+
+                const MIN: $name = $name::MIN;
+                const MAX: $name = $name::MAX;
+                const NEG_ONE: $name = $name::NEG_ONE;
+                const ONE: $name = $name::ONE;
+                const ZERO: $name = $name::ZERO;
+                // the smallest step either way
+                const EPSILON: $name = $name::from_raw(1);
+                const NEG_MAX: $name = $name::from_raw(-$name::MAX.raw);
+
+                #[test]
+                fn checked() {
+                    assert_eq!(MAX.checked_add(EPSILON), None);
+                    assert_eq!(MAX.checked_add(ZERO), Some(MAX));
+                    assert_eq!(MIN.checked_add(MAX), Some($name::from_raw(-1)));
+                    assert_eq!(MIN.checked_sub(EPSILON), None);
+                    assert_eq!($name::from_raw(-1).checked_sub(MAX), Some(MIN));
+
+                    assert_eq!(MIN.checked_neg(), None);
+                    assert_eq!(MAX.checked_neg(), Some(NEG_MAX));
+                    assert_eq!(NEG_ONE.checked_neg(), Some(ONE));
+
+                    assert_eq!(MIN.checked_mul(NEG_ONE), None);
+                    assert_eq!(MIN.checked_mul(ONE), Some(MIN));
+                    assert_eq!(MAX.checked_mul(NEG_ONE), Some(NEG_MAX));
+                    assert_eq!(MAX.checked_mul(MAX), None);
+                    assert_eq!(NEG_ONE.checked_mul(NEG_ONE), Some(ONE));
+
+                    assert_eq!(ONE.checked_div(ZERO), None);
+                    assert_eq!(ZERO.checked_div(ZERO), None);
+                    assert_eq!(MIN.checked_div(NEG_ONE), None);
+                    assert_eq!(MIN.checked_div(ONE), Some(MIN));
+                    assert_eq!(MAX.checked_div(NEG_ONE), Some(NEG_MAX));
+                    assert_eq!(ONE.checked_div(EPSILON), None);
+                    assert_eq!(NEG_ONE.checked_div(NEG_ONE), Some(ONE));
+                }
+
+                #[test]
+                fn saturating() {
+                    assert_eq!(MAX.saturating_add(EPSILON), MAX);
+                    assert_eq!(MIN.saturating_add(MAX), $name::from_raw(-1));
+                    assert_eq!(MIN.saturating_sub(EPSILON), MIN);
+                    assert_eq!(MAX.saturating_sub(NEG_ONE), MAX);
+
+                    assert_eq!(MIN.saturating_neg(), MAX);
+                    assert_eq!(MAX.saturating_neg(), NEG_MAX);
+
+                    assert_eq!(MIN.saturating_mul(NEG_ONE), MAX);
+                    assert_eq!(MAX.saturating_mul(MAX), MAX);
+                    assert_eq!(MIN.saturating_mul(MAX), MIN);
+                    assert_eq!(MAX.saturating_mul(NEG_ONE), NEG_MAX);
+
+                    assert_eq!(MAX.saturating_div(ZERO), MAX);
+                    assert_eq!(NEG_ONE.saturating_div(ZERO), MIN);
+                    assert_eq!(ZERO.saturating_div(ZERO), ZERO);
+                    assert_eq!(MIN.saturating_div(NEG_ONE), MAX);
+                    assert_eq!(NEG_ONE.saturating_div(EPSILON), MIN);
+                }
+
+                #[test]
+                fn wrapping() {
+                    assert_eq!(MAX.wrapping_add(EPSILON), MIN);
+                    assert_eq!(MIN.wrapping_sub(EPSILON), MAX);
+                    assert_eq!(MIN.wrapping_neg(), MIN);
+                    assert_eq!(NEG_ONE.wrapping_neg(), ONE);
+
+                    assert_eq!(MIN.wrapping_mul(NEG_ONE), MIN);
+                    assert_eq!(MAX.wrapping_mul(NEG_ONE), NEG_MAX);
+                    assert_eq!(MIN.wrapping_div(NEG_ONE), MIN);
+                    assert_eq!(MIN.wrapping_div(ONE), MIN);
+                }
+
+                #[test]
+                #[should_panic]
+                fn wrapping_division_by_zero_panics() {
+                    let _ = ONE.wrapping_div(ZERO);
+                }
+
+                #[test]
+                fn len_saturates() {
+                    assert_eq!(Vec2::new(MAX, ZERO).len(), MAX);
+                    assert_eq!(Vec2::new(ZERO, MIN).len(), MAX);
+                    assert_eq!(Vec2::new(MAX, MAX).len(), MAX);
+                    assert_eq!(Vec2::new(MIN, MIN).len(), MAX);
+                    assert_eq!(Vec2::new(NEG_MAX, ZERO).len(), MAX);
+
+                    // just below where it saturates
+                    let half = $name::from_raw($name::MAX.raw / 2);
+                    let len = Vec2::new(half, half).len();
+                    assert!(len < MAX);
+                    assert!((len.to_f64() - half.to_f64() * std::f64::consts::SQRT_2).abs() < 1e-4);
+                }
+
+                #[test]
+                #[cfg(all(feature = "overflow-checks", debug_assertions))]
+                #[should_panic(expected = "overflow in mul")]
+                fn overflowing_multiplication_is_caught() {
+                    let _ = MAX * (ONE + ONE);
+                }
+
+                #[test]
+                #[cfg(all(feature = "overflow-checks", debug_assertions))]
+                #[should_panic(expected = "overflow in div")]
+                fn overflowing_division_is_caught() {
+                    let _ = MIN / NEG_ONE;
+                }
+
+                #[test]
+                #[cfg(not(feature = "overflow-checks"))]
+                fn operators_wrap_without_overflow_checks() {
+                    assert_eq!(MAX * (ONE + ONE), MAX.wrapping_mul(ONE + ONE));
+                    assert_eq!(MIN / NEG_ONE, MIN);
+                }
+            }
+        };
+    }
+
+    edges!(flint, Flint);
+    edges!(flint64, Flint64);
+}
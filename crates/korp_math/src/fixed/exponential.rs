@@ -0,0 +1,185 @@
+// intermediate results keep two integer bits in the wide integers, so the
+// series converge well below the precision of the result
+macro_rules! fixed_exponential {
+    ($name:ident, $raw:ty, $wide:ty, $shift:literal) => {
+        impl $name {
+            const WIDE_SHIFT: u32 = 2 * $shift - 2;
+            const WIDE_ONE: $wide = 1 << Self::WIDE_SHIFT;
+            const WIDE_LN_2: $wide =
+                $crate::fixed::constant($crate::fixed::LN_2, Self::WIDE_SHIFT) as $wide;
+            // from the raw precision to the wide one
+            const WIDEN: u32 = Self::WIDE_SHIFT - Self::SHIFT;
+
+            /// Saturates to `MAX` when the result does not fit.
+            pub fn exp(self) -> Self {
+                let x = (self.raw as $wide) << Self::WIDEN;
+
+                // exp(x) = 2^k * exp(r) with r in [0, ln 2)
+                let k = x.div_euclid(Self::WIDE_LN_2);
+                let r = x.rem_euclid(Self::WIDE_LN_2);
+
+                let mut term = Self::WIDE_ONE;
+                let mut sum = Self::WIDE_ONE;
+                let mut i = 1;
+
+                while term != 0 {
+                    term = ((term * r) >> Self::WIDE_SHIFT) / i;
+                    sum += term;
+                    i += 1;
+                }
+
+                // back to the raw precision, scaled by 2^k
+                let shift = Self::WIDEN as $wide - k;
+
+                if shift <= 0 {
+                    if -shift > 2 {
+                        return Self::MAX;
+                    }
+
+                    return Self {
+                        raw: (sum << -shift).min(<$raw>::MAX as $wide) as $raw,
+                    };
+                }
+
+                if shift >= <$wide>::BITS as $wide - 1 {
+                    return Self::ZERO;
+                }
+
+                Self {
+                    raw: Self::round_wide(sum, shift as u32) as $raw,
+                }
+            }
+
+            /// Natural logarithm, saturates to `MIN` for zero and negative numbers.
+            pub fn ln(self) -> Self {
+                if self.raw <= 0 {
+                    return Self::MIN;
+                }
+
+                // ln(x) = k * ln 2 + ln(m) with m in [1, 2)
+                let msb = (<$raw>::BITS - 1 - self.raw.leading_zeros()) as $wide;
+                let k = msb - Self::SHIFT as $wide;
+                let m = (self.raw as $wide) << (Self::WIDE_SHIFT as $wide - msb);
+
+                // ln(m) = 2 * atanh(s) with s = (m - 1) / (m + 1) in [0, 1 / 3)
+                let s = ((m - Self::WIDE_ONE) << Self::WIDE_SHIFT) / (m + Self::WIDE_ONE);
+                let squared = (s * s) >> Self::WIDE_SHIFT;
+
+                let mut term = s;
+                let mut sum = 0;
+                let mut n = 1;
+
+                while term != 0 {
+                    sum += term / n;
+                    term = (term * squared) >> Self::WIDE_SHIFT;
+                    n += 2;
+                }
+
+                Self {
+                    raw: Self::round_wide(2 * sum + k * Self::WIDE_LN_2, Self::WIDEN) as $raw,
+                }
+            }
+
+            /// Integer power by squaring, saturates when the result does not fit.
+            pub fn powi(self, n: i32) -> Self {
+                let mut base = self;
+                let mut exponent = n.unsigned_abs();
+                let mut result = Self::ONE;
+
+                while exponent > 0 {
+                    if exponent & 1 == 1 {
+                        result = result.saturating_mul(base);
+                    }
+
+                    exponent >>= 1;
+
+                    if exponent > 0 {
+                        base = base.saturating_mul(base);
+                    }
+                }
+
+                if n < 0 {
+                    return Self::ONE.saturating_div(result);
+                }
+
+                result
+            }
+
+            // shifts right rounding to the nearest
+            fn round_wide(value: $wide, shift: u32) -> $wide {
+                (value + (1 << (shift - 1))) >> shift
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    // errors are in units of the last place, the raw step of each type, and
+    // relative to the result where it grows beyond one
+    macro_rules! accuracy {
+        ($module:ident, $name:ident) => {
+            mod $module {
+                use crate::$name;
+
+                const STEPS: i32 = 4000;
+
+                fn ulps(actual: $name, expected: f64) -> f64 {
+                    (actual.to_f64() - expected).abs()
+                        / expected.abs().max(1.0)
+                        / $name::from_raw(1).to_f64()
+                }
+
+                // evenly spaced over [-range, range]
+                fn sweep(range: f64) -> impl Iterator<Item = $name> {
+                    (-STEPS..=STEPS).map(move |i| $name::from_f64(range * i as f64 / STEPS as f64))
+                }
+
+                #[test]
+                fn exp_rounds_to_the_nearest() {
+                    let mut error = 0.0_f64;
+
+                    for x in sweep(10.0) {
+                        error = error.max(ulps(x.exp(), x.to_f64().exp()));
+                    }
+
+                    assert!(error <= 0.5 + 1e-3, "{error} ulps");
+                }
+
+                #[test]
+                fn ln_rounds_to_the_nearest() {
+                    let mut error = 0.0_f64;
+
+                    for range in [0.0001, 0.01, 1.0, 100.0, 30000.0] {
+                        for x in sweep(range).filter(|x| x.raw > 0) {
+                            error = error.max(ulps(x.ln(), x.to_f64().ln()));
+                        }
+                    }
+
+                    assert!(error <= 0.5 + 1e-3, "{error} ulps");
+                }
+
+                #[test]
+                fn powi_within_32_ulps() {
+                    let mut error = 0.0_f64;
+
+                    for x in sweep(2.0) {
+                        for n in -4..=8 {
+                            // small bases lose their precision before they are inverted
+                            if n < 0 && x.to_f64().abs() < 0.5 {
+                                continue;
+                            }
+
+                            error = error.max(ulps(x.powi(n), x.to_f64().powi(n)));
+                        }
+                    }
+
+                    assert!(error <= 32.0, "{error} ulps");
+                }
+            }
+        };
+    }
+
+    accuracy!(flint, Flint);
+    accuracy!(flint64, Flint64);
+}
@@ -0,0 +1,305 @@
+use std::fmt;
+
+// beyond that the digits of a fraction no longer fit the u128 arithmetic
+const DIGITS_EXACT_MAX: u32 = 28;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseFixedError {
+    Empty,
+    InvalidDigit,
+    Overflow,
+}
+
+impl fmt::Display for ParseFixedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseFixedError::Empty => write!(f, "cannot parse fixed point from empty string"),
+            ParseFixedError::InvalidDigit => write!(f, "invalid digit found in string"),
+            ParseFixedError::Overflow => write!(f, "number too large to fit in fixed point"),
+        }
+    }
+}
+
+impl std::error::Error for ParseFixedError {}
+
+/// Parses an unsigned decimal literal like `12`, `1.3` or `.5` into raw
+/// units with the given fractional bits, rounding to the nearest.
+pub(crate) fn parse(s: &str, shift: u32) -> Result<u128, ParseFixedError> {
+    let (integer, fraction) = s.split_once('.').unwrap_or((s, ""));
+
+    if integer.is_empty() && fraction.is_empty() {
+        return Err(ParseFixedError::Empty);
+    }
+
+    let digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+
+    if !digits(integer) || !digits(fraction) {
+        return Err(ParseFixedError::InvalidDigit);
+    }
+
+    let mut raw: u128 = 0;
+
+    for b in integer.bytes() {
+        raw = raw * 10 + (b - b'0') as u128;
+
+        // already past any raw integer
+        if raw > 1 << 64 {
+            return Err(ParseFixedError::Overflow);
+        }
+    }
+
+    raw <<= shift;
+
+    // halfway between two raw units takes exactly shift + 1 digits, cutting
+    // off any further ones keeps the fraction on the same side of it
+    let fraction = &fraction[..fraction.len().min(shift as usize + 1)];
+
+    if !fraction.is_empty() {
+        let numerator = fraction.parse::<u128>().unwrap_or(0);
+        let digits = fraction.len() as u32;
+
+        // n / 10^d * 2^(shift + 1), with the twos cancelled so it fits
+        let doubled = (numerator << (shift + 1 - digits)) / 5u128.pow(digits);
+
+        raw += (doubled + 1) >> 1;
+    }
+
+    Ok(raw)
+}
+
+/// Splits raw units into an integer and a fraction rounded half away from
+/// zero to the given digits, or fewer when the rest would all be zeros.
+/// The sign is left to the caller.
+pub(crate) fn split(magnitude: u128, shift: u32, digits: u32) -> (u128, u128, usize) {
+    let digits = digits.min(shift).min(DIGITS_EXACT_MAX);
+    let one = 1u128 << shift;
+    let scale = 10u128.pow(digits);
+
+    let integer = magnitude >> shift;
+    let fraction = ((magnitude & (one - 1)) * scale * 2 + one) / (one * 2);
+
+    if fraction == scale {
+        return (integer + 1, 0, digits as usize);
+    }
+
+    (integer, fraction, digits as usize)
+}
+
+macro_rules! fixed_format {
+    ($name:ident, $raw:ty) => {
+        impl $name {
+            // enough to tell any two values apart, and to parse back the same one
+            const DIGITS: usize = (Self::SHIFT as usize * 30103).div_ceil(100_000);
+        }
+
+        /// Accepts decimal literals like `-12`, `1.3` or `.5`, rounding to the nearest.
+        impl std::str::FromStr for $name {
+            type Err = $crate::fixed::ParseFixedError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let (negative, s) = match s.as_bytes().first() {
+                    Some(b'-') => (true, &s[1..]),
+                    Some(b'+') => (false, &s[1..]),
+                    _ => (false, s),
+                };
+
+                let magnitude = $crate::fixed::parse(s, Self::SHIFT)?;
+                let limit = <$raw>::MAX as u128 + negative as u128;
+
+                if magnitude > limit {
+                    return Err($crate::fixed::ParseFixedError::Overflow);
+                }
+
+                let raw = magnitude as $raw;
+
+                Ok(Self {
+                    raw: if negative { raw.wrapping_neg() } else { raw },
+                })
+            }
+        }
+
+        /// Rounds to the precision of the formatter when there is one, otherwise
+        /// to as many digits as the fraction needs, without trailing zeros.
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                let precision = f.precision();
+                let digits = precision.unwrap_or(Self::DIGITS);
+                let magnitude = self.raw.unsigned_abs() as u128;
+                let (integer, fraction, exact) =
+                    $crate::fixed::split(magnitude, Self::SHIFT, digits as u32);
+
+                let mut text = integer.to_string();
+
+                match precision {
+                    Some(precision) if precision > 0 => {
+                        text.push_str(&format!(".{fraction:0exact$}"));
+                        text.extend(std::iter::repeat_n('0', precision - exact));
+                    }
+                    Some(_) => {}
+                    None => {
+                        let fraction = format!("{fraction:0exact$}");
+                        let fraction = fraction.trim_end_matches('0');
+
+                        if !fraction.is_empty() {
+                            text.push('.');
+                            text.push_str(fraction);
+                        }
+                    }
+                }
+
+                let zero = integer == 0 && fraction == 0;
+
+                f.pad_integral(self.raw >= 0 || zero, "", &text)
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    macro_rules! literals {
+        ($module:ident, $name:ident) => {
+            mod $module {
+                use crate::{ParseFixedError, $name};
+
+                const SHIFT: u32 = $name::ONE.raw.trailing_zeros();
+
+                fn parse(s: &str) -> Result<$name, ParseFixedError> {
+                    s.parse()
+                }
+
+                // the exact decimal of raw / 2^(shift + 1), halfway between two raw units
+                fn halfway(raw: u128) -> String {
+                    let digits = SHIFT as usize + 1;
+
+                    format!("0.{:0digits$}", raw * 5u128.pow(SHIFT + 1))
+                }
+
+                #[test]
+                fn literals_are_parsed() {
+                    assert_eq!(parse("12"), Ok($name::from_raw(12 << SHIFT)));
+                    assert_eq!(parse("-1.5"), Ok(-$name::ONE - $name::ZERO_FIVE));
+                    assert_eq!(parse("+.5"), Ok($name::ZERO_FIVE));
+                    assert_eq!(parse("-0"), Ok($name::ZERO));
+                    assert_eq!(parse("1."), Ok($name::ONE));
+
+                    assert_eq!(parse(""), Err(ParseFixedError::Empty));
+                    assert_eq!(parse("-."), Err(ParseFixedError::Empty));
+                    assert_eq!(parse("1.2.3"), Err(ParseFixedError::InvalidDigit));
+                    assert_eq!(parse("1e3"), Err(ParseFixedError::InvalidDigit));
+                    assert_eq!(parse("--1"), Err(ParseFixedError::InvalidDigit));
+                }
+
+                #[test]
+                fn literals_saturate_only_at_the_edges() {
+                    assert_eq!(parse(&$name::MAX.to_string()), Ok($name::MAX));
+                    assert_eq!(parse(&$name::MIN.to_string()), Ok($name::MIN));
+
+                    let past = ((<$name>::MAX.raw as i128 >> SHIFT) + 1).to_string();
+
+                    assert_eq!(parse(&past), Err(ParseFixedError::Overflow));
+                    assert_eq!(parse(&format!("-{past}")), Ok($name::MIN));
+                    assert_eq!(
+                        parse("123456789012345678901234567890"),
+                        Err(ParseFixedError::Overflow)
+                    );
+                }
+
+                #[test]
+                fn literals_round_to_the_nearest() {
+                    // exactly halfway rounds up, every digit of it counts
+                    assert_eq!(parse(&halfway(1)), Ok($name::from_raw(1)));
+                    assert_eq!(parse(&halfway(3)), Ok($name::from_raw(2)));
+
+                    let below = halfway(1).replace("5", "4") + "9999999";
+
+                    assert_eq!(parse(&below), Ok($name::ZERO));
+                    assert_eq!(parse(&(halfway(1) + "0000001")), Ok($name::from_raw(1)));
+                    assert_eq!(parse(&format!("-{}", halfway(1))), Ok($name::from_raw(-1)));
+                }
+
+                #[test]
+                fn displayed_values_parse_back() {
+                    let mut raw: i128 = <$name>::MIN.raw as i128;
+
+                    // an uneven step through the whole range, and every small value
+                    while raw < <$name>::MAX.raw as i128 {
+                        let value = $name::from_raw(raw as _);
+
+                        assert_eq!(parse(&value.to_string()), Ok(value));
+                        raw += <$name>::MAX.raw as i128 / 997;
+                    }
+
+                    for raw in -1000..1000 {
+                        let value = $name::from_raw(raw);
+
+                        assert_eq!(parse(&value.to_string()), Ok(value));
+                    }
+                }
+
+                #[test]
+                fn display_honours_the_precision() {
+                    let value = $name::ONE + $name::ZERO_FIVE;
+
+                    assert_eq!(value.to_string(), "1.5");
+                    assert_eq!((-value).to_string(), "-1.5");
+                    assert_eq!(format!("{value:.3}"), "1.500");
+                    assert_eq!(format!("{value:.0}"), "2");
+                    assert_eq!(format!("{:.0}", -value), "-2");
+                    assert_eq!(format!("{value:>6}"), "   1.5");
+                    assert_eq!(format!("{value:+}"), "+1.5");
+                    assert_eq!($name::ZERO.to_string(), "0");
+
+                    // rounded away to zero loses its sign
+                    assert_eq!(format!("{:.2}", $name::from_raw(-1)), "0.00");
+
+                    let third = $name::ONE / $name::from_raw(3 << SHIFT);
+
+                    assert_eq!(format!("{third:.4}"), "0.3333");
+                    assert_eq!(format!("{:.4}", $name::ONE - third), "0.6667");
+                }
+
+                #[test]
+                fn floats_round_to_the_nearest_and_saturate() {
+                    let step = $name::from_raw(1).to_f64();
+
+                    assert_eq!(
+                        $name::from_f64(1.25),
+                        $name::ONE + $name::ZERO_FIVE * $name::ZERO_FIVE
+                    );
+                    assert_eq!($name::from_f64(step * 0.5), $name::from_raw(1));
+                    assert_eq!($name::from_f64(step * 0.49), $name::ZERO);
+                    assert_eq!($name::from_f64(-step * 0.5), $name::from_raw(-1));
+                    assert_eq!($name::from_f64(-step * 1.6), $name::from_raw(-2));
+
+                    assert_eq!($name::from_f64(1e30), $name::MAX);
+                    assert_eq!($name::from_f64(-1e30), $name::MIN);
+                    assert_eq!($name::from_f64(f64::INFINITY), $name::MAX);
+                    assert_eq!($name::from_f64(f64::NAN), $name::ZERO);
+
+                    assert_eq!($name::from_f32(-2.75), $name::from_f64(-2.75));
+                    assert_eq!($name::from_f32(f32::MAX), $name::MAX);
+                    assert_eq!($name::from_f32(f32::NEG_INFINITY), $name::MIN);
+                }
+
+                #[test]
+                fn degrees_and_radians_convert() {
+                    let step = $name::from_raw(1).to_f64();
+
+                    // regression, it was off by several steps in Flint
+                    assert!(($name::RAD2DEG.to_f64() - 180.0 / std::f64::consts::PI).abs() <= step);
+                    assert!(($name::RAD2DEG.to_f64() - 57.2958).abs() < 1e-4);
+                    assert!(($name::PI.to_degrees().to_f64() - 180.0).abs() <= 180.0 * step);
+
+                    // DEG2RAD is only rounded to a step, times 90
+                    let right = $name::from_raw(90 << SHIFT).to_radians();
+
+                    assert!((right - $name::FRAC_PI_2).abs() <= $name::from_raw(45));
+                }
+            }
+        };
+    }
+
+    literals!(flint, Flint);
+    literals!(flint64, Flint64);
+}
@@ -0,0 +1,195 @@
+macro_rules! fixed_trigonometry {
+    ($name:ident, $raw:ty, $wide:ty, $uwide:ty) => {
+        impl $name {
+            /// Angle of the vector (x, y) in radians, in the range [-PI, PI].
+            pub fn atan2(y: Self, x: Self) -> Self {
+                let (mut x, mut y) = (x.raw as $wide, y.raw as $wide);
+
+                // exact on the axes, where the iterations would leave a residual
+                match (x.signum(), y.signum()) {
+                    (0, 0) | (1, 0) => return Self::ZERO,
+                    (-1, 0) => return Self::PI,
+                    (0, 1) => return Self::FRAC_PI_2,
+                    (0, -1) => return -Self::FRAC_PI_2,
+                    _ => {}
+                }
+
+                // small vectors are scaled up so every iteration keeps its precision,
+                // up to the size of the raw integer, the wide one absorbs the growth
+                let shift = x.abs().max(y.abs()).leading_zeros() as $wide
+                    - (<$wide>::BITS - <$raw>::BITS + 1) as $wide;
+
+                if shift > 0 {
+                    x <<= shift;
+                    y <<= shift;
+                }
+
+                let mut z = 0;
+
+                // vectoring only converges in the right half plane
+                if x < 0 {
+                    z = if y >= 0 { Self::PI.raw } else { -Self::PI.raw };
+                    x = -x;
+                    y = -y;
+                }
+
+                for (i, atan) in Self::CORDIC_ATAN.iter().enumerate() {
+                    let xx = x;
+
+                    if y > 0 {
+                        x += y >> i;
+                        y -= xx >> i;
+                        z += atan;
+                    } else {
+                        x -= y >> i;
+                        y += xx >> i;
+                        z -= atan;
+                    }
+                }
+
+                Self { raw: z }
+            }
+
+            /// Clamped to [-1, 1] first, the result is in [-PI / 2, PI / 2].
+            pub fn asin(self) -> Self {
+                let x = self.clamp(Self::NEG_ONE, Self::ONE);
+
+                Self::atan2(x, x.complement())
+            }
+
+            /// Clamped to [-1, 1] first, the result is in [0, PI].
+            pub fn acos(self) -> Self {
+                let x = self.clamp(Self::NEG_ONE, Self::ONE);
+
+                Self::atan2(x.complement(), x)
+            }
+
+            /// Saturates where the cosine is zero.
+            pub fn tan(self) -> Self {
+                let (sin, cos) = self.sin_cos();
+
+                sin.saturating_div(cos)
+            }
+
+            // sqrt(1 - x^2) for x in [-1, 1], at full precision
+            fn complement(self) -> Self {
+                let one = Self::SCALE as $uwide;
+                let x = self.raw.unsigned_abs() as $uwide;
+
+                Self {
+                    raw: (one * one - x * x).isqrt() as $raw,
+                }
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    // errors are in units of the last place, the raw step of each type
+    macro_rules! accuracy {
+        ($module:ident, $name:ident) => {
+            mod $module {
+                use crate::$name;
+                use std::f64::consts::PI;
+
+                const STEPS: i32 = 4000;
+
+                fn ulps(actual: $name, expected: f64) -> f64 {
+                    (actual.to_f64() - expected).abs() / $name::from_raw(1).to_f64()
+                }
+
+                // evenly spaced over [-range, range]
+                fn sweep(range: f64) -> impl Iterator<Item = $name> {
+                    (-STEPS..=STEPS).map(move |i| $name::from_f64(range * i as f64 / STEPS as f64))
+                }
+
+                #[test]
+                fn sin_cos_within_16_ulps() {
+                    let (mut sin, mut cos) = (0.0_f64, 0.0_f64);
+
+                    for x in sweep(4.0 * PI) {
+                        let (actual_sin, actual_cos) = x.sin_cos();
+
+                        sin = sin.max(ulps(actual_sin, x.to_f64().sin()));
+                        cos = cos.max(ulps(actual_cos, x.to_f64().cos()));
+                    }
+
+                    assert!(sin <= 16.0, "sin {sin} ulps");
+                    assert!(cos <= 16.0, "cos {cos} ulps");
+                }
+
+                #[test]
+                fn sin_cos_repeats_every_turn() {
+                    let turns = [1, -1, 1000, -1000].map(|n| $name::TAU * n);
+
+                    for x in sweep(PI) {
+                        for turn in turns {
+                            assert_eq!((x + turn).sin_cos(), x.sin_cos());
+                        }
+                    }
+                }
+
+                #[test]
+                fn sin_cos_at_the_edges_of_the_range() {
+                    // reduced by the turn of the type, not the exact one
+                    let tau = $name::TAU.to_f64();
+
+                    for x in [$name::MIN, $name::MAX] {
+                        let (sin, cos) = x.sin_cos();
+                        let reduced = x.to_f64().rem_euclid(tau);
+
+                        assert!(ulps(sin, reduced.sin()) <= 16.0);
+                        assert!(ulps(cos, reduced.cos()) <= 16.0);
+                    }
+                }
+
+                #[test]
+                fn atan2_within_8_ulps() {
+                    let mut error = 0.0_f64;
+
+                    for radius in [0.001, 0.1, 1.0, 100.0, 10000.0] {
+                        for angle in sweep(PI) {
+                            let angle = angle.to_f64();
+                            let x = $name::from_f64(radius * angle.cos());
+                            let y = $name::from_f64(radius * angle.sin());
+                            let expected = y.to_f64().atan2(x.to_f64());
+
+                            error = error.max(ulps($name::atan2(y, x), expected));
+                        }
+                    }
+
+                    assert!(error <= 8.0, "{error} ulps");
+                }
+
+                #[test]
+                fn asin_and_acos_within_8_ulps() {
+                    let (mut asin, mut acos) = (0.0_f64, 0.0_f64);
+
+                    for x in sweep(1.0) {
+                        asin = asin.max(ulps(x.asin(), x.to_f64().asin()));
+                        acos = acos.max(ulps(x.acos(), x.to_f64().acos()));
+                    }
+
+                    assert!(asin <= 8.0, "asin {asin} ulps");
+                    assert!(acos <= 8.0, "acos {acos} ulps");
+                }
+
+                #[test]
+                fn tan_within_64_ulps() {
+                    let mut error = 0.0_f64;
+
+                    // the error grows with the slope, bounded where it stays below 4
+                    for x in sweep(1.0) {
+                        error = error.max(ulps(x.tan(), x.to_f64().tan()));
+                    }
+
+                    assert!(error <= 64.0, "{error} ulps");
+                }
+            }
+        };
+    }
+
+    accuracy!(flint, Flint);
+    accuracy!(flint64, Flint64);
+}
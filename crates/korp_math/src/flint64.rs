@@ -0,0 +1,23 @@
+use crate::Flint;
+
+fixed! {
+    /// 32.32 fixed point, for worlds larger than what `Flint` can address.
+    Flint64 {
+        raw: i64,
+        wide: i128,
+        unsigned_wide: u128,
+        integer: i32,
+        fraction: u32,
+        shift: 32,
+        from_integer: from_i32,
+        to_integer: to_i32,
+    }
+}
+
+impl From<Flint> for Flint64 {
+    fn from(value: Flint) -> Self {
+        Self {
+            raw: (value.raw as i64) << 16,
+        }
+    }
+}
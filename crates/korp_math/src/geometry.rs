@@ -0,0 +1,40 @@
+mod circle;
+mod hull;
+mod polygon;
+mod ray;
+mod sat;
+
+use crate::{Flint, Vec2};
+
+pub use circle::*;
+pub use hull::*;
+pub use polygon::*;
+pub use ray::*;
+pub use sat::*;
+
+// fractional bits of a flint
+const SHIFT: u32 = 16;
+
+// twice the signed area of the triangle a, b, c in raw squared units, positive
+// when counterclockwise, exact for any coordinates
+fn orientation(a: Vec2<Flint>, b: Vec2<Flint>, c: Vec2<Flint>) -> i128 {
+    let (abx, aby) = (
+        b.x.raw as i128 - a.x.raw as i128,
+        b.y.raw as i128 - a.y.raw as i128,
+    );
+    let (acx, acy) = (
+        c.x.raw as i128 - a.x.raw as i128,
+        c.y.raw as i128 - a.y.raw as i128,
+    );
+
+    abx * acy - aby * acx
+}
+
+// every edge of a closed polygon, the last vertex connects back to the first
+fn edges(vertices: &[Vec2<Flint>]) -> impl Iterator<Item = (Vec2<Flint>, Vec2<Flint>)> + '_ {
+    (0..vertices.len()).map(|i| (vertices[i], vertices[(i + 1) % vertices.len()]))
+}
+
+fn saturate(raw: i128) -> Flint {
+    Flint::from_raw(raw.clamp(i32::MIN as i128, i32::MAX as i128) as i32)
+}
@@ -0,0 +1,165 @@
+use crate::{
+    Flint, Mtv, Vec2,
+    geometry::{
+        project_polygon,
+        sat::{center, normals},
+    },
+};
+
+/// Overlap of two circles, the axis points from the first towards the second.
+pub fn circle_mtv(
+    center1: Vec2<Flint>,
+    radius1: Flint,
+    center2: Vec2<Flint>,
+    radius2: Flint,
+) -> Option<Mtv> {
+    let offset = center2 - center1;
+    let depth = radius1 + radius2 - offset.len();
+
+    if depth < Flint::ZERO {
+        return None;
+    }
+
+    // any direction separates circles sharing a center
+    let axis = offset
+        .try_normalized()
+        .unwrap_or(Vec2::new(Flint::ONE, Flint::ZERO));
+
+    Some(Mtv { axis, depth })
+}
+
+/// Overlap of a circle and a convex polygon, the axis points from the circle
+/// towards the polygon.
+pub fn circle_polygon_mtv(
+    circle: Vec2<Flint>,
+    radius: Flint,
+    vertices: &[Vec2<Flint>],
+) -> Option<Mtv> {
+    // besides the edge normals the circle can only be separated along the
+    // direction to the closest vertex
+    let closest = vertices
+        .iter()
+        .min_by_key(|vertex| vertex.distance(&circle))?;
+    let mut least: Option<Mtv> = None;
+
+    for axis in normals(vertices).chain((*closest - circle).try_normalized()) {
+        let projection = axis.dot(&circle);
+        let (min1, max1) = (projection - radius, projection + radius);
+        let (min2, max2) = project_polygon(vertices, axis);
+
+        let depth = max1.min(max2) - min1.max(min2);
+        if depth < Flint::ZERO {
+            return None;
+        }
+
+        if least.is_none_or(|least| depth < least.depth) {
+            least = Some(Mtv { axis, depth });
+        }
+    }
+
+    let least = least?;
+
+    if (center(vertices) - circle).dot(&least.axis) < Flint::ZERO {
+        return Some(Mtv {
+            axis: -least.axis,
+            depth: least.depth,
+        });
+    }
+
+    Some(least)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Flint, Mtv, Vec2, circle_mtv, circle_polygon_mtv};
+
+    fn point(x: f32, y: f32) -> Vec2<Flint> {
+        Vec2::new(Flint::from_f32(x), Flint::from_f32(y))
+    }
+
+    fn mtv(x: f32, y: f32, depth: f32) -> Option<Mtv> {
+        Some(Mtv {
+            axis: point(x, y),
+            depth: Flint::from_f32(depth),
+        })
+    }
+
+    #[test]
+    fn circles_overlap_along_their_centers() {
+        let (one, two) = (Flint::ONE, Flint::from_i16(2));
+
+        assert_eq!(
+            circle_mtv(point(0.0, 0.0), one, point(0.0, 2.5), two),
+            mtv(0.0, 1.0, 0.5)
+        );
+        assert_eq!(
+            circle_mtv(point(0.0, 2.5), two, point(0.0, 0.0), one),
+            mtv(0.0, -1.0, 0.5)
+        );
+        assert_eq!(
+            circle_mtv(point(0.0, 0.0), one, point(3.0, 0.0), two),
+            mtv(1.0, 0.0, 0.0)
+        );
+        assert_eq!(circle_mtv(point(0.0, 0.0), one, point(3.5, 0.0), two), None);
+        // shared centers still get a direction
+        assert_eq!(
+            circle_mtv(point(1.0, 1.0), one, point(1.0, 1.0), two),
+            mtv(1.0, 0.0, 3.0)
+        );
+    }
+
+    #[test]
+    fn circles_overlap_polygon_edges() {
+        let square = [
+            point(0.0, 0.0),
+            point(2.0, 0.0),
+            point(2.0, 2.0),
+            point(0.0, 2.0),
+        ];
+
+        assert_eq!(
+            circle_polygon_mtv(point(-0.5, 1.0), Flint::ONE, &square),
+            mtv(1.0, 0.0, 0.5)
+        );
+        assert_eq!(
+            circle_polygon_mtv(point(1.0, 2.75), Flint::ONE, &square),
+            mtv(0.0, -1.0, 0.25)
+        );
+        assert_eq!(
+            circle_polygon_mtv(point(-1.5, 1.0), Flint::ONE, &square),
+            None
+        );
+    }
+
+    #[test]
+    fn circles_are_separated_from_corners_along_the_closest_vertex() {
+        let square = [
+            point(0.0, 0.0),
+            point(2.0, 0.0),
+            point(2.0, 2.0),
+            point(0.0, 2.0),
+        ];
+
+        // overlaps along both edge normals, yet apart diagonally
+        assert_eq!(
+            circle_polygon_mtv(point(-0.8, -0.8), Flint::ONE, &square),
+            None
+        );
+
+        let mtv = circle_polygon_mtv(point(-0.5, -0.5), Flint::ONE, &square).unwrap();
+        let diagonal = Flint::from_f32(std::f32::consts::FRAC_1_SQRT_2);
+
+        assert!(
+            (mtv.axis.x - diagonal).abs() <= Flint::from_raw(2),
+            "{mtv:?}"
+        );
+        assert!(
+            (mtv.axis.y - diagonal).abs() <= Flint::from_raw(2),
+            "{mtv:?}"
+        );
+        assert!(
+            (mtv.depth.to_f32() - (1.0 - diagonal.to_f32())).abs() < 1e-3,
+            "{mtv:?}"
+        );
+    }
+}
@@ -0,0 +1,121 @@
+use crate::{Flint, Vec2, geometry::orientation};
+
+/// Convex hull of the points, counterclockwise starting from the lowest x,
+/// without duplicate or collinear points.
+pub fn convex_hull(points: &[Vec2<Flint>]) -> Vec<Vec2<Flint>> {
+    let mut sorted = points.to_vec();
+    sorted.sort_by_key(|point| (point.x, point.y));
+    sorted.dedup();
+
+    if sorted.len() < 3 {
+        return sorted;
+    }
+
+    // monotone chain, the lower half left to right and then the upper half back
+    let mut hull: Vec<Vec2<Flint>> = Vec::with_capacity(sorted.len() + 1);
+
+    for &point in sorted.iter() {
+        while hull.len() >= 2 && orientation(hull[hull.len() - 2], hull[hull.len() - 1], point) <= 0
+        {
+            hull.pop();
+        }
+
+        hull.push(point);
+    }
+
+    let floor = hull.len() + 1;
+
+    for &point in sorted.iter().rev().skip(1) {
+        while hull.len() >= floor
+            && orientation(hull[hull.len() - 2], hull[hull.len() - 1], point) <= 0
+        {
+            hull.pop();
+        }
+
+        hull.push(point);
+    }
+
+    // the upper half ends on the first point again
+    hull.pop();
+    hull
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Flint, Vec2, convex_hull, geometry::orientation};
+
+    fn point(x: f32, y: f32) -> Vec2<Flint> {
+        Vec2::new(Flint::from_f32(x), Flint::from_f32(y))
+    }
+
+    #[test]
+    fn fewer_than_three_points_are_only_sorted() {
+        let (a, b) = (point(0.0, 1.0), point(2.0, -1.0));
+
+        assert_eq!(convex_hull(&[]), vec![]);
+        assert_eq!(convex_hull(&[b]), vec![b]);
+        assert_eq!(convex_hull(&[b, a]), vec![a, b]);
+        assert_eq!(convex_hull(&[b, a, b, a]), vec![a, b]);
+    }
+
+    #[test]
+    fn collinear_points_keep_the_ends() {
+        let line = [0.0, 3.0, 1.0, 2.5, 0.5].map(|t| point(t, 2.0 * t - 1.0));
+
+        assert_eq!(convex_hull(&line), vec![point(0.0, -1.0), point(3.0, 5.0)]);
+    }
+
+    #[test]
+    fn hulls_drop_inner_duplicate_and_collinear_points() {
+        let points = [
+            point(2.0, 2.0),
+            point(0.0, 0.0),
+            point(1.0, 1.0),
+            point(2.0, 0.0),
+            point(1.0, 0.0),
+            point(0.0, 2.0),
+            point(2.0, 0.0),
+            point(0.0, 1.0),
+            point(2.0, 2.0),
+        ];
+
+        // counterclockwise from the lowest x, the lowest y breaking the tie
+        let square = vec![
+            point(0.0, 0.0),
+            point(2.0, 0.0),
+            point(2.0, 2.0),
+            point(0.0, 2.0),
+        ];
+
+        assert_eq!(convex_hull(&points), square);
+    }
+
+    #[test]
+    fn hulls_are_counterclockwise_and_enclose_every_point() {
+        let circle = (0..40)
+            .map(|i| {
+                Vec2::<Flint>::from_angle(Flint::from_f32(i as f32 * 0.7)) * Flint::from_i16(5)
+            })
+            .collect::<Vec<_>>();
+        let hull = convex_hull(&circle);
+
+        assert!(hull.len() >= 3);
+
+        for i in 0..hull.len() {
+            let (a, b, c) = (
+                hull[i],
+                hull[(i + 1) % hull.len()],
+                hull[(i + 2) % hull.len()],
+            );
+
+            assert!(orientation(a, b, c) > 0);
+        }
+
+        // and every point is on the inner side of every edge
+        for point in circle {
+            for i in 0..hull.len() {
+                assert!(orientation(hull[i], hull[(i + 1) % hull.len()], point) >= 0);
+            }
+        }
+    }
+}
@@ -0,0 +1,331 @@
+use crate::{
+    Flint, Mtv, Sweep, Transform2, Vec2, convex_hull,
+    geometry::{SHIFT, edges, orientation, polygon_mtv, polygon_sweep, project_polygon, saturate},
+};
+
+/// Strictly convex polygon with counterclockwise vertices.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ConvexPolygon {
+    vertices: Vec<Vec2<Flint>>,
+}
+
+impl ConvexPolygon {
+    /// None unless the vertices, in either winding, already form a strictly
+    /// convex polygon. They are reordered like the convex hull.
+    pub fn new(vertices: &[Vec2<Flint>]) -> Option<Self> {
+        let hull = convex_hull(vertices);
+
+        if hull.len() < 3 || hull.len() != vertices.len() {
+            return None;
+        }
+
+        // same cycle as the hull either way around, otherwise it crosses itself
+        let start = vertices.iter().position(|vertex| *vertex == hull[0])?;
+        let count = hull.len();
+        let forward = (0..count).all(|i| vertices[(start + i) % count] == hull[i]);
+        let backward = (0..count).all(|i| vertices[(start + count - i) % count] == hull[i]);
+
+        if !forward && !backward {
+            return None;
+        }
+
+        Some(Self { vertices: hull })
+    }
+
+    /// Convex hull of the points, none when they are all on one line.
+    pub fn from_points(points: &[Vec2<Flint>]) -> Option<Self> {
+        let hull = convex_hull(points);
+
+        if hull.len() < 3 {
+            return None;
+        }
+
+        Some(Self { vertices: hull })
+    }
+
+    pub fn vertices(&self) -> &[Vec2<Flint>] {
+        &self.vertices
+    }
+
+    pub fn area(&self) -> Flint {
+        saturate(self.fan().map(|(_, _, cross)| cross).sum::<i128>() / 2)
+    }
+
+    /// Center of mass of the polygon with a uniform density.
+    pub fn centroid(&self) -> Vec2<Flint> {
+        let origin = self.vertices[0];
+        let local = self.local_centroid();
+
+        Vec2::new(
+            saturate(origin.x.raw as i128 + local.0),
+            saturate(origin.y.raw as i128 + local.1),
+        )
+    }
+
+    /// Moment of inertia around the centroid for the given mass spread
+    /// uniformly over the polygon.
+    pub fn moment_of_inertia(&self, mass: Flint) -> Flint {
+        let twice_area = self.fan().map(|(_, _, cross)| cross).sum::<i128>();
+
+        // the inertia of each triangle of the fan around the first vertex
+        let sum = self
+            .fan()
+            .map(|(p, q, cross)| {
+                let dots = (p.0 * p.0 + p.1 * p.1 + p.0 * q.0 + p.1 * q.1 + q.0 * q.0 + q.1 * q.1)
+                    >> SHIFT;
+                cross * dots
+            })
+            .sum::<i128>();
+
+        if twice_area == 0 {
+            return Flint::ZERO;
+        }
+
+        // moved to the centroid with the parallel axis theorem
+        let (cx, cy) = self.local_centroid();
+        let squared = (cx * cx + cy * cy) >> SHIFT;
+
+        saturate((mass.raw as i128 * (sum / (6 * twice_area) - squared)) >> SHIFT)
+    }
+
+    /// Whether the point is inside the polygon or on its boundary.
+    pub fn contains(&self, point: Vec2<Flint>) -> bool {
+        edges(&self.vertices).all(|(a, b)| orientation(a, b, point) >= 0)
+    }
+
+    pub fn transformed(&self, transform: &Transform2<Flint>) -> Self {
+        let mut vertices = self
+            .vertices
+            .iter()
+            .map(|vertex| transform.apply(*vertex))
+            .collect::<Vec<_>>();
+
+        // mirroring flips the winding
+        if transform.determinant() < Flint::ZERO {
+            vertices.reverse();
+        }
+
+        Self { vertices }
+    }
+
+    pub fn project(&self, axis: Vec2<Flint>) -> (Flint, Flint) {
+        project_polygon(&self.vertices, axis)
+    }
+
+    pub fn mtv(&self, other: &Self) -> Option<Mtv> {
+        polygon_mtv(&self.vertices, &other.vertices)
+    }
+
+    pub fn sweep(&self, other: &Self, displacement: Vec2<Flint>) -> Option<Sweep> {
+        polygon_sweep(&self.vertices, &other.vertices, displacement)
+    }
+
+    // raw offsets of the centroid from the first vertex
+    fn local_centroid(&self) -> (i128, i128) {
+        let mut twice_area = 0;
+        let mut x = 0;
+        let mut y = 0;
+
+        for (p, q, cross) in self.fan() {
+            twice_area += cross;
+            x += cross * (p.0 + q.0);
+            y += cross * (p.1 + q.1);
+        }
+
+        // collapsed by a transform
+        if twice_area == 0 {
+            return (0, 0);
+        }
+
+        (x / (3 * twice_area), y / (3 * twice_area))
+    }
+
+    // triangles fanning out from the first vertex as raw offsets from it and
+    // twice their area, in raw units since the fractional bits are shifted away
+    fn fan(&self) -> impl Iterator<Item = ((i128, i128), (i128, i128), i128)> + '_ {
+        let origin = self.vertices[0];
+        let local = move |vertex: Vec2<Flint>| {
+            (
+                vertex.x.raw as i128 - origin.x.raw as i128,
+                vertex.y.raw as i128 - origin.y.raw as i128,
+            )
+        };
+
+        self.vertices.windows(2).skip(1).map(move |pair| {
+            let p = local(pair[0]);
+            let q = local(pair[1]);
+
+            (p, q, (p.0 * q.1 - p.1 * q.0) >> SHIFT)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ConvexPolygon, Flint, Random, Vec2};
+
+    // the smallest difference between two flints
+    const STEP: f32 = Flint::from_raw(1).to_f32();
+
+    fn point(x: f32, y: f32) -> Vec2<Flint> {
+        Vec2::new(Flint::from_f32(x), Flint::from_f32(y))
+    }
+
+    fn polygon(points: &[(f32, f32)]) -> Option<ConvexPolygon> {
+        ConvexPolygon::new(&points.iter().map(|&(x, y)| point(x, y)).collect::<Vec<_>>())
+    }
+
+    fn close(actual: Flint, expected: f32, steps: f32) -> bool {
+        (actual.to_f32() - expected).abs() <= steps * STEP
+    }
+
+    #[test]
+    fn either_winding_is_counterclockwise() {
+        let counterclockwise = polygon(&[(0.0, 0.0), (2.0, 0.0), (2.0, 1.0), (0.0, 1.0)]);
+        let clockwise = polygon(&[(2.0, 1.0), (2.0, 0.0), (0.0, 0.0), (0.0, 1.0)]);
+        let expected = [
+            point(0.0, 0.0),
+            point(2.0, 0.0),
+            point(2.0, 1.0),
+            point(0.0, 1.0),
+        ];
+
+        assert_eq!(counterclockwise.unwrap().vertices(), expected);
+        assert_eq!(clockwise.unwrap().vertices(), expected);
+    }
+
+    #[test]
+    fn only_strictly_convex_polygons_are_accepted() {
+        // the two diagonals of the square cross each other
+        assert_eq!(
+            polygon(&[(0.0, 0.0), (1.0, 1.0), (1.0, 0.0), (0.0, 1.0)]),
+            None
+        );
+        // a dent, an extra vertex along an edge and a repeated one
+        assert_eq!(
+            polygon(&[(0.0, 0.0), (2.0, 0.0), (1.0, 1.0), (2.0, 2.0), (0.0, 2.0)]),
+            None
+        );
+        assert_eq!(
+            polygon(&[(0.0, 0.0), (1.0, 0.0), (2.0, 0.0), (0.0, 2.0)]),
+            None
+        );
+        assert_eq!(
+            polygon(&[(0.0, 0.0), (2.0, 0.0), (2.0, 0.0), (0.0, 2.0)]),
+            None
+        );
+        // too few to enclose anything
+        assert_eq!(polygon(&[(0.0, 0.0), (1.0, 1.0), (2.0, 2.0)]), None);
+        assert_eq!(polygon(&[(0.0, 0.0), (1.0, 1.0)]), None);
+        assert_eq!(polygon(&[]), None);
+    }
+
+    #[test]
+    fn from_points_takes_the_hull() {
+        let points = [(0.0, 0.0), (2.0, 0.0), (1.0, 1.0), (2.0, 2.0), (0.0, 2.0)];
+        let points = points.map(|(x, y)| point(x, y));
+        let square = ConvexPolygon::from_points(&points).unwrap();
+
+        assert_eq!(square.vertices().len(), 4);
+        assert_eq!(ConvexPolygon::from_points(&points[..2]), None);
+    }
+
+    #[test]
+    fn rectangles_have_known_mass_properties() {
+        let rectangle = polygon(&[(10.0, 20.0), (14.0, 20.0), (14.0, 22.0), (10.0, 22.0)]).unwrap();
+
+        assert_eq!(rectangle.area(), Flint::from_i16(8));
+        assert_eq!(rectangle.centroid(), point(12.0, 21.0));
+        // m * (w^2 + h^2) / 12
+        let inertia = rectangle.moment_of_inertia(Flint::from_i16(3));
+
+        assert!(close(inertia, 5.0, 4.0), "{inertia}");
+    }
+
+    #[test]
+    fn triangles_have_known_mass_properties() {
+        let triangle = polygon(&[(-3.0, -1.0), (0.0, -1.0), (-3.0, 2.0)]).unwrap();
+
+        assert_eq!(triangle.area(), Flint::from_f32(4.5));
+        assert_eq!(triangle.centroid(), point(-2.0, 0.0));
+        // m * (a^2 + b^2) / 18 for a right triangle with legs a and b
+        let inertia = triangle.moment_of_inertia(Flint::from_i16(2));
+
+        assert!(close(inertia, 2.0, 4.0), "{inertia}");
+    }
+
+    #[test]
+    fn random_polygons_match_f64() {
+        let mut random = Random::new(29);
+        let range = Flint::from_i16(20);
+        let (mut area, mut centroid, mut inertia) = (0.0_f64, 0.0_f64, 0.0_f64);
+
+        for _ in 0..500 {
+            let points = (0..8)
+                .map(|_| Vec2::new(random.flint(-range, range), random.flint(-range, range)))
+                .collect::<Vec<_>>();
+            let Some(polygon) = ConvexPolygon::from_points(&points) else {
+                continue;
+            };
+
+            // the shoelace sums, and the second moment around the origin
+            let vertices = polygon
+                .vertices()
+                .iter()
+                .map(|v| (v.x.to_f64(), v.y.to_f64()));
+            let next = vertices.clone().cycle().skip(1);
+            let (mut twice, mut x, mut y, mut second) = (0.0, 0.0, 0.0, 0.0);
+
+            for ((x1, y1), (x2, y2)) in vertices.zip(next) {
+                let cross = x1 * y2 - x2 * y1;
+
+                twice += cross;
+                x += cross * (x1 + x2);
+                y += cross * (y1 + y2);
+                second += cross * (x1 * x1 + x1 * x2 + x2 * x2 + y1 * y1 + y1 * y2 + y2 * y2);
+            }
+
+            let (cx, cy) = (x / (3.0 * twice), y / (3.0 * twice));
+            let expected = second / (6.0 * twice) - (cx * cx + cy * cy);
+            let actual = polygon.centroid();
+
+            area = area.max((polygon.area().to_f64() - twice / 2.0).abs());
+            centroid = centroid.max(
+                (actual.x.to_f64() - cx)
+                    .abs()
+                    .max((actual.y.to_f64() - cy).abs()),
+            );
+            inertia =
+                inertia.max((polygon.moment_of_inertia(Flint::ONE).to_f64() - expected).abs());
+        }
+
+        let step = STEP as f64;
+
+        // a step lost to rounding for each triangle of the fan
+        assert!(area <= 6.0 * step, "area {area}");
+        assert!(centroid <= 2.0 * step, "centroid {centroid}");
+        // the squared distances lose their low bits, at up to 40 units across
+        assert!(inertia <= 128.0 * step, "moment_of_inertia {inertia}");
+    }
+
+    #[test]
+    fn the_boundary_is_contained() {
+        let triangle = polygon(&[(0.0, 0.0), (4.0, 0.0), (0.0, 4.0)]).unwrap();
+        let step = Flint::from_raw(1);
+
+        for vertex in triangle.vertices() {
+            assert!(triangle.contains(*vertex));
+        }
+
+        // along each edge, on the hypotenuse too
+        assert!(triangle.contains(point(2.0, 0.0)));
+        assert!(triangle.contains(point(0.0, 2.0)));
+        assert!(triangle.contains(point(2.0, 2.0)));
+        assert!(triangle.contains(point(1.0, 1.0)));
+
+        // a step past each of them
+        assert!(!triangle.contains(point(2.0, 0.0) - Vec2::new(Flint::ZERO, step)));
+        assert!(!triangle.contains(point(0.0, 2.0) - Vec2::new(step, Flint::ZERO)));
+        assert!(!triangle.contains(point(2.0, 2.0) + Vec2::new(step, Flint::ZERO)));
+    }
+}
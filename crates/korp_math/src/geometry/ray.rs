@@ -0,0 +1,175 @@
+use crate::{Flint, Vec2, geometry::edges};
+
+/// Half line from the origin along the direction, cut off at the range.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Ray {
+    pub origin: Vec2<Flint>,
+    // must be a unit vector
+    pub direction: Vec2<Flint>,
+    pub range: Flint,
+}
+
+impl Ray {
+    pub fn end(&self) -> Vec2<Flint> {
+        self.origin + self.direction * self.range
+    }
+
+    /// Distance to the closest edge of the polygon the ray crosses.
+    pub fn polygon(&self, vertices: &[Vec2<Flint>]) -> Option<Flint> {
+        edges(vertices)
+            .filter_map(|(a, b)| self.segment(a, b))
+            .min()
+    }
+
+    /// Distance to where the ray crosses the segment from a to b.
+    // solves origin + t * direction = a + u * edge for t, the edge is normalized
+    // so every product stays in the range of the ray and cannot overflow
+    pub fn segment(&self, a: Vec2<Flint>, b: Vec2<Flint>) -> Option<Flint> {
+        let edge = b - a;
+        let length = edge.len();
+
+        if length == Flint::ZERO {
+            return None;
+        }
+
+        let edge = edge.normalized();
+        let denominator = self.direction.cross(&edge);
+
+        // parallel
+        if denominator == Flint::ZERO {
+            return None;
+        }
+
+        let offset = a - self.origin;
+        let t = offset.cross(&edge);
+        let u = offset.cross(&self.direction);
+
+        // both must have the sign of the denominator and not exceed it scaled
+        let same = |x: Flint| x == Flint::ZERO || (x < Flint::ZERO) == (denominator < Flint::ZERO);
+        let within = |x: Flint, limit: Flint| x.abs() <= limit * denominator.abs();
+
+        if !same(t) || !same(u) || !within(t, self.range) || !within(u, length) {
+            return None;
+        }
+
+        Some(t / denominator)
+    }
+}
+
+/// Point where the segments from a1 to a2 and from b1 to b2 cross.
+pub fn segment_intersection(
+    a1: Vec2<Flint>,
+    a2: Vec2<Flint>,
+    b1: Vec2<Flint>,
+    b2: Vec2<Flint>,
+) -> Option<Vec2<Flint>> {
+    let ray = Ray {
+        origin: a1,
+        direction: (a2 - a1).try_normalized()?,
+        range: (a2 - a1).len(),
+    };
+
+    let distance = ray.segment(b1, b2)?;
+    Some(ray.origin + ray.direction * distance)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Flint, Ray, Vec2, segment_intersection};
+
+    fn point(x: f32, y: f32) -> Vec2<Flint> {
+        Vec2::new(Flint::from_f32(x), Flint::from_f32(y))
+    }
+
+    // from the origin along x
+    fn ray(range: f32) -> Ray {
+        Ray {
+            origin: point(0.0, 0.0),
+            direction: point(1.0, 0.0),
+            range: Flint::from_f32(range),
+        }
+    }
+
+    #[test]
+    fn crossing_segments_are_hit_at_their_distance() {
+        let hit = Some(Flint::from_i16(3));
+
+        assert_eq!(ray(5.0).segment(point(3.0, -1.0), point(3.0, 1.0)), hit);
+        assert_eq!(ray(5.0).segment(point(3.0, 1.0), point(3.0, -1.0)), hit);
+        assert_eq!(ray(5.0).segment(point(2.0, -1.0), point(4.0, 1.0)), hit);
+    }
+
+    #[test]
+    fn parallel_segments_are_missed() {
+        assert_eq!(ray(5.0).segment(point(1.0, 1.0), point(4.0, 1.0)), None);
+        // even lying on the ray
+        assert_eq!(ray(5.0).segment(point(1.0, 0.0), point(4.0, 0.0)), None);
+        assert_eq!(ray(5.0).segment(point(1.0, 1.0), point(1.0, 1.0)), None);
+    }
+
+    #[test]
+    fn endpoints_are_hit() {
+        let hit = Some(Flint::from_i16(3));
+
+        assert_eq!(ray(5.0).segment(point(3.0, 0.0), point(3.0, 2.0)), hit);
+        assert_eq!(ray(5.0).segment(point(3.0, -2.0), point(3.0, 0.0)), hit);
+        assert_eq!(ray(5.0).segment(point(3.0, 0.25), point(3.0, 2.0)), None);
+        assert_eq!(ray(5.0).segment(point(3.0, -2.0), point(3.0, -0.25)), None);
+    }
+
+    #[test]
+    fn segments_out_of_range_are_missed() {
+        let segment = (point(3.0, -1.0), point(3.0, 1.0));
+
+        assert_eq!(
+            ray(3.0).segment(segment.0, segment.1),
+            Some(Flint::from_i16(3))
+        );
+        assert_eq!(ray(2.75).segment(segment.0, segment.1), None);
+        // behind the origin
+        assert_eq!(ray(5.0).segment(point(-3.0, -1.0), point(-3.0, 1.0)), None);
+    }
+
+    #[test]
+    fn polygons_are_hit_on_the_closest_edge() {
+        let square = [
+            point(2.0, -1.0),
+            point(4.0, -1.0),
+            point(4.0, 1.0),
+            point(2.0, 1.0),
+        ];
+
+        assert_eq!(ray(10.0).polygon(&square), Some(Flint::from_i16(2)));
+        assert_eq!(ray(1.0).polygon(&square), None);
+    }
+
+    #[test]
+    fn segments_intersect_where_they_cross() {
+        let crossing = segment_intersection(
+            point(0.0, 0.0),
+            point(4.0, 4.0),
+            point(0.0, 4.0),
+            point(4.0, 0.0),
+        );
+        let apart = segment_intersection(
+            point(0.0, 0.0),
+            point(1.0, 1.0),
+            point(0.0, 4.0),
+            point(4.0, 0.0),
+        );
+        let collapsed = segment_intersection(
+            point(2.0, 2.0),
+            point(2.0, 2.0),
+            point(0.0, 4.0),
+            point(4.0, 0.0),
+        );
+
+        let crossing = crossing.unwrap();
+        assert!(
+            (crossing - point(2.0, 2.0)).len() <= Flint::from_raw(4),
+            "{crossing:?}"
+        );
+        assert_eq!(apart, None);
+        assert_eq!(collapsed, None);
+    }
+}
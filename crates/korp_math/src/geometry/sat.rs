@@ -0,0 +1,264 @@
+use crate::{Flint, Vec2, geometry::edges};
+
+/// Minimum translation vector, pushing the second polygon along the axis by
+/// the depth separates the two.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Mtv {
+    pub axis: Vec2<Flint>,
+    pub depth: Flint,
+}
+
+/// Earliest fraction of a tick two swept polygons touch at, together with
+/// the axis they touched along and the relative speed along it.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Sweep {
+    pub toi: Flint,
+    pub axis: Vec2<Flint>,
+    pub speed: Flint,
+}
+
+/// Smallest and largest projection of the vertices onto the axis.
+pub fn project_polygon(vertices: &[Vec2<Flint>], axis: Vec2<Flint>) -> (Flint, Flint) {
+    let mut min = axis.dot(&vertices[0]);
+    let mut max = min;
+
+    for vertex in vertices.iter().skip(1) {
+        let projection = axis.dot(vertex);
+        if projection < min {
+            min = projection;
+        } else if projection > max {
+            max = projection;
+        }
+    }
+
+    (min, max)
+}
+
+/// Separating axis test between two convex polygons, none when they are
+/// apart. The axis points from the first polygon towards the second.
+pub fn polygon_mtv(v1: &[Vec2<Flint>], v2: &[Vec2<Flint>]) -> Option<Mtv> {
+    let least1 = penetration(v1, v2, v1)?;
+    let least2 = penetration(v1, v2, v2)?;
+
+    let least = if least2.depth < least1.depth {
+        least2
+    } else {
+        least1
+    };
+
+    if (center(v2) - center(v1)).dot(&least.axis) < Flint::ZERO {
+        return Some(Mtv {
+            axis: -least.axis,
+            depth: least.depth,
+        });
+    }
+
+    Some(least)
+}
+
+/// Sweeps the first polygon by the displacement, relative to the second,
+/// towards where both are now and finds when during the tick they first
+/// touch, ignoring rotation.
+pub fn polygon_sweep(
+    v1: &[Vec2<Flint>],
+    v2: &[Vec2<Flint>],
+    displacement: Vec2<Flint>,
+) -> Option<Sweep> {
+    let mut enter = Sweep {
+        toi: Flint::NEG_ONE,
+        axis: Vec2::<Flint>::ZERO,
+        speed: Flint::ZERO,
+    };
+    let mut exit = Flint::from_i16(2);
+
+    for polygon in [v1, v2] {
+        for axis in normals(polygon) {
+            let speed = displacement.dot(&axis);
+
+            let (min1, max1) = project_polygon(v1, axis);
+            let (min2, max2) = project_polygon(v2, axis);
+
+            if speed == Flint::ZERO {
+                if max1 < min2 || max2 < min1 {
+                    return None;
+                }
+
+                continue;
+            }
+
+            // the first polygon started the tick offset by the speed along the axis
+            let (near, far) = if speed > Flint::ZERO {
+                (min2 - max1 + speed, max2 - min1 + speed)
+            } else {
+                (max2 - min1 + speed, min2 - max1 + speed)
+            };
+
+            let t_enter = fraction(near, speed);
+            let t_exit = fraction(far, speed);
+
+            if t_enter > enter.toi {
+                enter = Sweep {
+                    toi: t_enter,
+                    axis,
+                    speed,
+                };
+            }
+
+            exit = exit.min(t_exit);
+
+            if enter.toi > exit {
+                return None;
+            }
+        }
+    }
+
+    if enter.toi > Flint::ONE || exit < Flint::ZERO {
+        return None;
+    }
+
+    Some(Sweep {
+        toi: enter.toi.max(Flint::ZERO),
+        ..enter
+    })
+}
+
+// unit edge normals, normalized so depths are comparable between axes
+pub(super) fn normals(polygon: &[Vec2<Flint>]) -> impl Iterator<Item = Vec2<Flint>> + '_ {
+    edges(polygon)
+        .map(|(a, b)| b - a)
+        .filter(|edge| edge.len_sqr() != Flint::ZERO)
+        .map(|edge| edge.perp().normalized())
+}
+
+// the edge normal of the polygon with the least overlap and that overlap,
+// or none when the polygons are separated along any of them
+fn penetration(v1: &[Vec2<Flint>], v2: &[Vec2<Flint>], polygon: &[Vec2<Flint>]) -> Option<Mtv> {
+    let mut least: Option<Mtv> = None;
+
+    for axis in normals(polygon) {
+        let (min1, max1) = project_polygon(v1, axis);
+        let (min2, max2) = project_polygon(v2, axis);
+
+        let depth = max1.min(max2) - min1.max(min2);
+        if depth < Flint::ZERO {
+            return None;
+        }
+
+        if least.is_none_or(|least| depth < least.depth) {
+            least = Some(Mtv { axis, depth });
+        }
+    }
+
+    least
+}
+
+// mean of the vertices, enough to tell which side the other polygon is on
+pub(super) fn center(vertices: &[Vec2<Flint>]) -> Vec2<Flint> {
+    let mut sum = Vec2::<Flint>::ZERO;
+
+    for vertex in vertices.iter() {
+        sum += *vertex;
+    }
+
+    let count = Flint::from_i16(vertices.len() as i16);
+    Vec2::new(sum.x / count, sum.y / count)
+}
+
+// division clamped to a little beyond the range of a tick, the raw values
+// would overflow when the denominator is tiny compared to the numerator
+fn fraction(numerator: Flint, denominator: Flint) -> Flint {
+    let limit = Flint::from_i16(2);
+
+    if numerator.abs() >= denominator.abs() * limit {
+        return match (numerator < Flint::ZERO) == (denominator < Flint::ZERO) {
+            true => limit,
+            false => -limit,
+        };
+    }
+
+    numerator / denominator
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Flint, Mtv, Vec2, polygon_mtv, project_polygon};
+
+    fn point(x: f32, y: f32) -> Vec2<Flint> {
+        Vec2::new(Flint::from_f32(x), Flint::from_f32(y))
+    }
+
+    // counterclockwise, side 2 around the center
+    fn square(x: f32, y: f32) -> Vec<Vec2<Flint>> {
+        vec![
+            point(x - 1.0, y - 1.0),
+            point(x + 1.0, y - 1.0),
+            point(x + 1.0, y + 1.0),
+            point(x - 1.0, y + 1.0),
+        ]
+    }
+
+    fn mtv(x: f32, y: f32, depth: f32) -> Option<Mtv> {
+        Some(Mtv {
+            axis: point(x, y),
+            depth: Flint::from_f32(depth),
+        })
+    }
+
+    #[test]
+    fn projections_span_the_polygon() {
+        let square = square(3.0, 1.0);
+
+        assert_eq!(
+            project_polygon(&square, point(1.0, 0.0)),
+            (Flint::from_i16(2), Flint::from_i16(4))
+        );
+        assert_eq!(
+            project_polygon(&square, point(0.0, -1.0)),
+            (Flint::from_i16(-2), Flint::ZERO)
+        );
+    }
+
+    #[test]
+    fn the_axis_points_towards_the_second_polygon() {
+        let origin = square(0.0, 0.0);
+
+        assert_eq!(polygon_mtv(&origin, &square(1.5, 0.5)), mtv(1.0, 0.0, 0.5));
+        assert_eq!(polygon_mtv(&square(1.5, 0.5), &origin), mtv(-1.0, 0.0, 0.5));
+        assert_eq!(
+            polygon_mtv(&origin, &square(0.25, -1.75)),
+            mtv(0.0, -1.0, 0.25)
+        );
+        assert_eq!(
+            polygon_mtv(&origin, &square(-0.25, 1.0)),
+            mtv(0.0, 1.0, 1.0)
+        );
+    }
+
+    #[test]
+    fn the_depth_is_the_least_overlap() {
+        let triangle = [point(0.0, 0.0), point(4.0, 0.0), point(0.0, 4.0)];
+        let mtv = polygon_mtv(&triangle, &square(2.5, 2.5)).unwrap();
+        let diagonal = Flint::from_f32(std::f32::consts::FRAC_1_SQRT_2);
+
+        // along the normal of the hypotenuse, a step off from rounding
+        assert!((mtv.axis.x - diagonal).abs() <= Flint::from_raw(2));
+        assert!((mtv.axis.y - diagonal).abs() <= Flint::from_raw(2));
+        assert!(
+            (mtv.depth.to_f32() - diagonal.to_f32()).abs() < 1e-3,
+            "{}",
+            mtv.depth
+        );
+    }
+
+    #[test]
+    fn touching_polygons_overlap_by_nothing() {
+        let origin = square(0.0, 0.0);
+
+        assert_eq!(polygon_mtv(&origin, &square(2.0, 0.5)), mtv(1.0, 0.0, 0.0));
+        assert_eq!(
+            polygon_mtv(&origin, &square(2.0 + 1.0 / 65536.0, 0.5)),
+            None
+        );
+        assert_eq!(polygon_mtv(&origin, &square(3.0, 3.0)), None);
+    }
+}
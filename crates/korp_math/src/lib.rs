@@ -1,11 +1,22 @@
+#[macro_use]
+mod fixed;
 mod flint;
+mod flint64;
+mod geometry;
+mod noise;
 mod random;
+mod transform2;
 mod vec2;
 
 use std::f32::consts::{PI, TAU};
 
+pub use fixed::ParseFixedError;
 pub use flint::*;
+pub use flint64::*;
+pub use geometry::*;
+pub use noise::*;
 pub use random::*;
+pub use transform2::*;
 pub use vec2::*;
 
 pub fn lerp_angle(a: f32, b: f32, t: f32) -> f32 {
@@ -0,0 +1,400 @@
+use crate::{Flint, Random, Vec2};
+
+// skews the plane onto the simplex grid and back, (sqrt(3) - 1) / 2 and (3 - sqrt(3)) / 6
+const F2: i64 = 23988;
+const G2: i64 = 13849;
+// brings the sum of the simplex corners to roughly [-1, 1]
+const SIMPLEX_SCALE: i16 = 70;
+
+const SHIFT: i64 = 16;
+const ONE: i64 = 1 << SHIFT;
+
+// where the octaves of fbm stop moving outwards, a million cells out, the
+// rounded skew drifts off its cell by about 1e-5 of the coordinate
+const COORDINATE_MAXIMUM: i128 = 1 << 36;
+
+const GRADIENTS: [(i32, i32); 8] = [
+    (1, 1),
+    (-1, 1),
+    (1, -1),
+    (-1, -1),
+    (1, 0),
+    (-1, 0),
+    (0, 1),
+    (0, -1),
+];
+
+/// Coherent noise in 2D, identical on every platform for the same seed.
+/// Value and gradient noise repeat themselves every 256 units, simplex
+/// noise wraps on its skewed grid instead and does not repeat along the
+/// axes. The lattice is computed in 64 bits so the coordinates can be
+/// scaled past the range of a flint.
+#[derive(Clone)]
+pub struct Noise {
+    // doubled so two lookups in a row never need wrapping
+    permutation: [u8; 512],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoiseKind {
+    Value,
+    Gradient,
+    Simplex,
+}
+
+/// Octaves of fractal brownian motion, each one `lacunarity` times the
+/// frequency and `gain` times the amplitude of the previous one.
+#[derive(Debug, Clone, Copy)]
+pub struct Fractal {
+    pub octaves: u32,
+    pub lacunarity: Flint,
+    pub gain: Flint,
+}
+
+impl Default for Fractal {
+    fn default() -> Self {
+        Self {
+            octaves: 4,
+            lacunarity: Flint::from_i16(2),
+            gain: Flint::ZERO_FIVE,
+        }
+    }
+}
+
+impl Noise {
+    /// Draws the permutation from the generator, usually a fork of the
+    /// simulation one so gameplay draws are left untouched.
+    pub fn new(random: &mut Random) -> Self {
+        let mut values = [0u8; 256];
+
+        for (i, value) in values.iter_mut().enumerate() {
+            *value = i as u8;
+        }
+
+        random.shuffle(&mut values);
+
+        let mut permutation = [0; 512];
+        permutation[..256].copy_from_slice(&values);
+        permutation[256..].copy_from_slice(&values);
+
+        Self { permutation }
+    }
+
+    pub fn from_seed(seed: u64) -> Self {
+        Self::new(&mut Random::new(seed))
+    }
+
+    /// Smoothly interpolated random values at the lattice points, in [-1, 1].
+    pub fn value(&self, point: Vec2<Flint>) -> Flint {
+        self.value_raw(point.x.raw as i64, point.y.raw as i64)
+    }
+
+    /// Perlin noise, interpolated gradients at the lattice points, roughly in [-1, 1].
+    pub fn gradient(&self, point: Vec2<Flint>) -> Flint {
+        self.gradient_raw(point.x.raw as i64, point.y.raw as i64)
+    }
+
+    /// Simplex noise, fewer directional artifacts than gradient noise, roughly in [-1, 1].
+    pub fn simplex(&self, point: Vec2<Flint>) -> Flint {
+        self.simplex_raw(point.x.raw as i64, point.y.raw as i64)
+    }
+
+    pub fn sample(&self, kind: NoiseKind, point: Vec2<Flint>) -> Flint {
+        self.sample_raw(kind, point.x.raw as i64, point.y.raw as i64)
+    }
+
+    /// Sum of the octaves, normalized back to the range of a single one.
+    pub fn fbm(&self, kind: NoiseKind, point: Vec2<Flint>, fractal: &Fractal) -> Flint {
+        let maximum = COORDINATE_MAXIMUM << SHIFT;
+        let mut frequency = ONE as i128;
+        let mut amplitude = Flint::ONE;
+        let mut sum = Flint::ZERO;
+        let mut total = Flint::ZERO;
+
+        for _ in 0..fractal.octaves {
+            let scale = |raw: i32| {
+                ((raw as i128 * frequency) >> SHIFT).clamp(-COORDINATE_MAXIMUM, COORDINATE_MAXIMUM)
+                    as i64
+            };
+
+            sum += self.sample_raw(kind, scale(point.x.raw), scale(point.y.raw)) * amplitude;
+            total += amplitude;
+
+            frequency =
+                ((frequency * fractal.lacunarity.raw as i128) >> SHIFT).clamp(-maximum, maximum);
+            amplitude = amplitude * fractal.gain;
+        }
+
+        if total == Flint::ZERO {
+            return Flint::ZERO;
+        }
+
+        sum / total
+    }
+
+    fn sample_raw(&self, kind: NoiseKind, x: i64, y: i64) -> Flint {
+        match kind {
+            NoiseKind::Value => self.value_raw(x, y),
+            NoiseKind::Gradient => self.gradient_raw(x, y),
+            NoiseKind::Simplex => self.simplex_raw(x, y),
+        }
+    }
+
+    fn value_raw(&self, x: i64, y: i64) -> Flint {
+        let (i, fx) = split(x);
+        let (j, fy) = split(y);
+
+        // 0..=255 spread over [-1, 1]
+        let corner = |i, j| Flint::from_raw(self.hash(i, j) as i32 * 514 - ONE as i32);
+
+        let u = fade(fx);
+        let v = fade(fy);
+
+        lerp(
+            lerp(corner(i, j), corner(i + 1, j), u),
+            lerp(corner(i, j + 1), corner(i + 1, j + 1), u),
+            v,
+        )
+    }
+
+    fn gradient_raw(&self, x: i64, y: i64) -> Flint {
+        let (i, fx) = split(x);
+        let (j, fy) = split(y);
+
+        let corner = |i, j, dx: Flint, dy: Flint| dot(self.hash(i, j), dx, dy);
+
+        let u = fade(fx);
+        let v = fade(fy);
+
+        lerp(
+            lerp(
+                corner(i, j, fx, fy),
+                corner(i + 1, j, fx - Flint::ONE, fy),
+                u,
+            ),
+            lerp(
+                corner(i, j + 1, fx, fy - Flint::ONE),
+                corner(i + 1, j + 1, fx - Flint::ONE, fy - Flint::ONE),
+                u,
+            ),
+            v,
+        )
+    }
+
+    fn simplex_raw(&self, x: i64, y: i64) -> Flint {
+        // the cell of the skewed grid, floored by the arithmetic shifts
+        let s = ((x + y) * F2) >> SHIFT;
+        let i = (x + s) >> SHIFT;
+        let j = (y + s) >> SHIFT;
+
+        // distances to the first corner, back in the unskewed plane
+        let t = (i + j) * G2;
+        let x0 = x - ((i << SHIFT) - t);
+        let y0 = y - ((j << SHIFT) - t);
+
+        // which of the two triangles of the cell the point lies in
+        let (i1, j1) = if x0 > y0 { (1, 0) } else { (0, 1) };
+
+        let x1 = x0 - i1 * ONE + G2;
+        let y1 = y0 - j1 * ONE + G2;
+        let x2 = x0 - ONE + 2 * G2;
+        let y2 = y0 - ONE + 2 * G2;
+
+        let (i, j) = ((i & 0xff) as usize, (j & 0xff) as usize);
+
+        let sum = self.contribution(i, j, x0, y0)
+            + self.contribution(i + i1 as usize, j + j1 as usize, x1, y1)
+            + self.contribution(i + 1, j + 1, x2, y2);
+
+        sum * SIMPLEX_SCALE
+    }
+
+    // falls off to zero at a distance of sqrt(0.5) from the corner
+    fn contribution(&self, i: usize, j: usize, x: i64, y: i64) -> Flint {
+        let (x, y) = (Flint::from_raw(x as i32), Flint::from_raw(y as i32));
+        let t = Flint::ZERO_FIVE - x * x - y * y;
+
+        if t < Flint::ZERO {
+            return Flint::ZERO;
+        }
+
+        let t = t * t;
+
+        t * t * dot(self.hash(i, j), x, y)
+    }
+
+    fn hash(&self, i: usize, j: usize) -> u8 {
+        self.permutation[self.permutation[i & 0xff] as usize + (j & 0xff)]
+    }
+}
+
+// the lattice cell, wrapped to the period, and the position inside it
+fn split(raw: i64) -> (usize, Flint) {
+    (
+        ((raw >> SHIFT) & 0xff) as usize,
+        Flint::from_raw((raw & (ONE - 1)) as i32),
+    )
+}
+
+// 6t^5 - 15t^4 + 10t^3, flat at both ends so the cells join smoothly
+fn fade(t: Flint) -> Flint {
+    t * t * t * (t * (t * 6 - Flint::from_i16(15)) + Flint::from_i16(10))
+}
+
+fn lerp(a: Flint, b: Flint, t: Flint) -> Flint {
+    a + (b - a) * t
+}
+
+fn dot(hash: u8, x: Flint, y: Flint) -> Flint {
+    let (gx, gy) = GRADIENTS[(hash & 7) as usize];
+
+    x * gx as i16 + y * gy as i16
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Flint, Fractal, Noise, NoiseKind, Vec2};
+
+    const KINDS: [NoiseKind; 3] = [NoiseKind::Value, NoiseKind::Gradient, NoiseKind::Simplex];
+
+    // spread over several cells, off the lattice, in every quadrant
+    fn points() -> impl Iterator<Item = Vec2<Flint>> {
+        (-64..64).flat_map(|i| {
+            (-64..64).map(move |j| {
+                Vec2::new(
+                    Flint::from_raw(i * 4099 + 77),
+                    Flint::from_raw(j * 4111 + 13),
+                )
+            })
+        })
+    }
+
+    fn in_range(value: Flint) -> bool {
+        (Flint::NEG_ONE..=Flint::ONE).contains(&value)
+    }
+
+    #[test]
+    fn the_same_seed_gives_the_same_noise() {
+        let (noise, same, other) = (
+            Noise::from_seed(3),
+            Noise::from_seed(3),
+            Noise::from_seed(4),
+        );
+
+        for kind in KINDS {
+            let samples =
+                |noise: &Noise| points().map(|p| noise.sample(kind, p)).collect::<Vec<_>>();
+
+            assert_eq!(samples(&noise), samples(&same), "{kind:?}");
+            assert_ne!(samples(&noise), samples(&other), "{kind:?}");
+        }
+    }
+
+    #[test]
+    fn samples_stay_in_range() {
+        for seed in 0..4 {
+            let noise = Noise::from_seed(seed);
+
+            for kind in KINDS {
+                let (mut low, mut high) = (Flint::ZERO, Flint::ZERO);
+
+                for point in points() {
+                    let value = noise.sample(kind, point);
+
+                    assert!(in_range(value), "{kind:?} {value}");
+                    low = low.min(value);
+                    high = high.max(value);
+                }
+
+                // and spread over most of it
+                assert!(
+                    low < -Flint::ZERO_FIVE && high > Flint::ZERO_FIVE,
+                    "{kind:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn gradient_noise_is_zero_on_the_lattice() {
+        let noise = Noise::from_seed(3);
+
+        for i in -20..20 {
+            for j in -20..20 {
+                let point = Vec2::new(Flint::from_i16(i), Flint::from_i16(j));
+
+                assert_eq!(noise.gradient(point), Flint::ZERO);
+            }
+        }
+    }
+
+    #[test]
+    fn value_and_gradient_noise_repeat_every_256_units() {
+        let noise = Noise::from_seed(3);
+        let period = Flint::from_i16(256);
+
+        for kind in [NoiseKind::Value, NoiseKind::Gradient] {
+            for point in points() {
+                let shifted = Vec2::new(point.x + period, point.y - period);
+
+                assert_eq!(
+                    noise.sample(kind, point),
+                    noise.sample(kind, shifted),
+                    "{kind:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn fbm_is_normalized_to_a_single_octave() {
+        let noise = Noise::from_seed(3);
+        let single = Fractal {
+            octaves: 1,
+            ..Fractal::default()
+        };
+        let none = Fractal {
+            octaves: 0,
+            ..Fractal::default()
+        };
+
+        for kind in KINDS {
+            for point in points() {
+                assert_eq!(noise.fbm(kind, point, &single), noise.sample(kind, point));
+                assert_eq!(noise.fbm(kind, point, &none), Flint::ZERO);
+                assert!(in_range(noise.fbm(kind, point, &Fractal::default())));
+            }
+        }
+    }
+
+    #[test]
+    fn fbm_survives_many_octaves() {
+        let noise = Noise::from_seed(3);
+        let fractals = [
+            Fractal {
+                octaves: 64,
+                ..Fractal::default()
+            },
+            Fractal {
+                octaves: 64,
+                lacunarity: Flint::MAX,
+                gain: Flint::ONE,
+            },
+            Fractal {
+                octaves: 64,
+                lacunarity: Flint::MIN,
+                gain: Flint::ONE,
+            },
+        ];
+
+        for kind in KINDS {
+            for fractal in &fractals {
+                for point in [Flint::MIN, Flint::NEG_ONE, Flint::ONE, Flint::MAX] {
+                    let value = noise.fbm(kind, Vec2::new(point, point), fractal);
+
+                    assert!(in_range(value), "{kind:?} {value}");
+                }
+            }
+        }
+    }
+}
@@ -1,21 +1,390 @@
+use crate::{Flint, Vec2};
+
+/// xoshiro256**, seeded through splitmix64 so any seed, zero included,
+/// gives a well mixed state.
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Random {
-    state: u64,
+    state: [u64; 4],
 }
 
+// advances the state by 2^128 steps, as if next was called that many times
+const JUMP: [u64; 4] = [
+    0x180e_c6d3_3cfd_0aba,
+    0xd5a6_1266_f0c9_392c,
+    0xa958_2618_e03f_c9aa,
+    0x39ab_dc45_29b1_661c,
+];
+
 impl Random {
     pub fn new(seed: u64) -> Self {
-        Self { state: seed }
+        let mut seed = seed;
+        let mut state = [0; 4];
+
+        for word in state.iter_mut() {
+            *word = splitmix(&mut seed);
+        }
+
+        Self { state }
     }
 
-    pub fn next(&mut self) -> u64 {
-        self.state ^= self.state << 13;
-        self.state ^= self.state >> 7;
-        self.state ^= self.state << 17;
+    /// Restores a generator from its state, e.g. from a snapshot.
+    pub fn from_state(state: [u64; 4]) -> Self {
+        // the only state xoshiro cannot leave
+        if state == [0; 4] {
+            return Self::new(0);
+        }
+
+        Self { state }
+    }
+
+    pub fn state(&self) -> [u64; 4] {
         self.state
     }
 
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> u64 {
+        let result = self.state[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = self.state[1] << 17;
+
+        self.state[2] ^= self.state[0];
+        self.state[3] ^= self.state[1];
+        self.state[1] ^= self.state[2];
+        self.state[0] ^= self.state[3];
+        self.state[2] ^= t;
+        self.state[3] = self.state[3].rotate_left(45);
+
+        result
+    }
+
+    /// An independent generator, 2^128 draws away from this one.
+    pub fn fork(&mut self) -> Self {
+        let fork = self.clone();
+        let mut state = [0; 4];
+
+        for jump in JUMP {
+            for bit in 0..64 {
+                if jump & (1 << bit) != 0 {
+                    for (word, current) in state.iter_mut().zip(self.state) {
+                        *word ^= current;
+                    }
+                }
+
+                self.next();
+            }
+        }
+
+        self.state = state;
+        fork
+    }
+
+    /// In [min, max), without bias, min when the range is empty.
     pub fn range(&mut self, min: u64, max: u64) -> u64 {
-        let range = max - min;
-        min + (self.next() % range)
+        if max <= min {
+            return min;
+        }
+
+        min + self.below(max - min)
+    }
+
+    /// In [min, max], without bias, min when the range is empty.
+    pub fn range_inclusive(&mut self, min: u64, max: u64) -> u64 {
+        if max <= min {
+            return min;
+        }
+
+        match max - min {
+            u64::MAX => self.next(),
+            span => min + self.below(span + 1),
+        }
+    }
+
+    /// In [min, max), to the precision of a flint, min when the range is empty.
+    pub fn flint(&mut self, min: Flint, max: Flint) -> Flint {
+        if max <= min {
+            return min;
+        }
+
+        let span = (max.raw as i64 - min.raw as i64) as u64;
+
+        Flint::from_raw((min.raw as i64 + self.below(span) as i64) as i32)
+    }
+
+    /// Uniformly distributed inside the unit circle.
+    pub fn in_unit_circle(&mut self) -> Vec2<Flint> {
+        loop {
+            let point = Vec2::new(
+                self.flint(Flint::NEG_ONE, Flint::ONE),
+                self.flint(Flint::NEG_ONE, Flint::ONE),
+            );
+
+            if point.len_sqr() <= Flint::ONE {
+                return point;
+            }
+        }
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i as u64 + 1) as usize;
+
+            items.swap(i, j);
+        }
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        if items.is_empty() {
+            return None;
+        }
+
+        items.get(self.below(items.len() as u64) as usize)
+    }
+
+    /// Index picked with a probability proportional to its weight, `None`
+    /// when every weight is zero.
+    pub fn weighted(&mut self, weights: &[u64]) -> Option<usize> {
+        let total = weights.iter().map(|weight| *weight as u128).sum::<u128>();
+
+        if total == 0 {
+            return None;
+        }
+
+        let mut pick = match u64::try_from(total) {
+            Ok(total) => self.below(total) as u128,
+            // too heavy to be bias free, close enough for weights that large
+            Err(_) => ((self.next() as u128) << 64 | self.next() as u128) % total,
+        };
+
+        weights.iter().position(|weight| {
+            if pick < *weight as u128 {
+                return true;
+            }
+
+            pick -= *weight as u128;
+            false
+        })
+    }
+
+    // in [0, bound) for a positive bound, rejecting the draws that would bias it
+    fn below(&mut self, bound: u64) -> u64 {
+        let mut product = self.next() as u128 * bound as u128;
+
+        if (product as u64) < bound {
+            let threshold = bound.wrapping_neg() % bound;
+
+            while (product as u64) < threshold {
+                product = self.next() as u128 * bound as u128;
+            }
+        }
+
+        (product >> 64) as u64
+    }
+}
+
+fn splitmix(seed: &mut u64) -> u64 {
+    *seed = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+    let mut z = *seed;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::{Flint, Random};
+
+    const DRAWS: usize = 10_000;
+
+    #[test]
+    fn the_stream_matches_the_reference() {
+        // the first draws of the reference implementation from this state
+        let mut random = Random::from_state([1, 2, 3, 4]);
+        let expected = [
+            11520,
+            0,
+            1509978240,
+            1215971899390074240,
+            1216172134540287360,
+            607988272756665600,
+            16172922978634559625,
+            8476171486693032832,
+            10595114339597558777,
+            2904607092377533576,
+        ];
+
+        assert_eq!(expected.map(|_| random.next()), expected);
+    }
+
+    #[test]
+    fn a_zero_seed_is_mixed_by_splitmix() {
+        let mut random = Random::new(0);
+        let state = [
+            0xe220_a839_7b1d_cdaf,
+            0x6e78_9e6a_a1b9_65f4,
+            0x06c4_5d18_8009_454f,
+            0xf88b_b8a8_724c_81ec,
+        ];
+
+        assert_eq!(random.state(), state);
+        assert_eq!(Random::from_state([0; 4]), Random::new(0));
+
+        let draws = (0..DRAWS).map(|_| random.next()).collect::<HashSet<_>>();
+
+        assert_eq!(draws.len(), DRAWS);
+    }
+
+    #[test]
+    fn restoring_the_state_continues_the_stream() {
+        let mut random = Random::new(7);
+
+        for _ in 0..100 {
+            random.next();
+        }
+
+        let mut restored = Random::from_state(random.state());
+
+        assert_eq!(restored, random);
+        assert!((0..100).all(|_| restored.next() == random.next()));
+    }
+
+    #[test]
+    fn forks_do_not_overlap() {
+        let mut random = Random::new(7);
+        let original = random.clone();
+        let mut fork = random.fork();
+
+        // the fork carries on the stream, the generator itself jumps ahead
+        assert_eq!(fork, original);
+        assert_ne!(random, original);
+
+        let mut second = random.fork();
+        let mut streams = [&mut random, &mut fork, &mut second];
+        let mut draws = HashSet::new();
+
+        for stream in streams.iter_mut() {
+            for _ in 0..DRAWS {
+                assert!(draws.insert(stream.next()));
+            }
+        }
+    }
+
+    #[test]
+    fn empty_ranges_give_the_minimum() {
+        let mut random = Random::new(7);
+
+        assert_eq!(random.range(5, 5), 5);
+        assert_eq!(random.range(7, 3), 7);
+        assert_eq!(random.range_inclusive(5, 5), 5);
+        assert_eq!(random.range_inclusive(7, 3), 7);
+        assert_eq!(random.flint(Flint::ONE, Flint::ONE), Flint::ONE);
+        assert_eq!(random.flint(Flint::ONE, Flint::ZERO), Flint::ONE);
+    }
+
+    #[test]
+    fn ranges_cover_their_bounds() {
+        let mut random = Random::new(7);
+        let (mut range, mut inclusive) = (HashSet::new(), HashSet::new());
+
+        for _ in 0..DRAWS {
+            range.insert(random.range(10, 13));
+            inclusive.insert(random.range_inclusive(10, 13));
+        }
+
+        assert_eq!(range, HashSet::from([10, 11, 12]));
+        assert_eq!(inclusive, HashSet::from([10, 11, 12, 13]));
+    }
+
+    #[test]
+    fn ranges_over_the_full_span() {
+        // the inclusive one is the raw stream itself
+        let mut random = Random::from_state([1, 2, 3, 4]);
+        let mut reference = random.clone();
+
+        for _ in 0..100 {
+            assert_eq!(random.range_inclusive(0, u64::MAX), reference.next());
+        }
+
+        let mut random = Random::new(7);
+        let (mut low, mut high) = (false, false);
+
+        for _ in 0..DRAWS {
+            let value = random.range(1, u64::MAX);
+
+            assert!((1..u64::MAX).contains(&value));
+            low |= value < u64::MAX / 4;
+            high |= value > u64::MAX / 4 * 3;
+        }
+
+        assert!(low && high);
+    }
+
+    #[test]
+    fn flints_stay_below_the_maximum() {
+        let mut random = Random::new(7);
+        let small = (0..DRAWS)
+            .map(|_| random.flint(Flint::ZERO, Flint::from_raw(3)).raw)
+            .collect::<HashSet<_>>();
+
+        assert_eq!(small, HashSet::from([0, 1, 2]));
+
+        for _ in 0..DRAWS {
+            let value = random.flint(Flint::MIN, Flint::MAX);
+
+            assert!(value < Flint::MAX);
+        }
+    }
+
+    #[test]
+    fn points_stay_inside_the_unit_circle() {
+        let mut random = Random::new(7);
+        let mut quadrants = HashSet::new();
+
+        for _ in 0..DRAWS {
+            let point = random.in_unit_circle();
+
+            assert!(point.len_sqr() <= Flint::ONE);
+            quadrants.insert((point.x < Flint::ZERO, point.y < Flint::ZERO));
+        }
+
+        assert_eq!(quadrants.len(), 4);
+    }
+
+    #[test]
+    fn shuffles_are_permutations() {
+        let mut random = Random::new(7);
+        let sorted = (0..50).collect::<Vec<_>>();
+        let mut items = sorted.clone();
+
+        random.shuffle(&mut items);
+        assert_ne!(items, sorted);
+
+        items.sort();
+        assert_eq!(items, sorted);
+
+        random.shuffle(&mut [0; 0]);
+        random.shuffle(&mut [0]);
+    }
+
+    #[test]
+    fn weighted_skips_zero_weights() {
+        let mut random = Random::new(7);
+        let mut counts = [0; 4];
+
+        for _ in 0..DRAWS {
+            counts[random.weighted(&[0, 3, 0, 1]).unwrap()] += 1;
+        }
+
+        assert_eq!((counts[0], counts[2]), (0, 0));
+        // three to one, within a few percent
+        assert!((7000..8000).contains(&counts[1]), "{counts:?}");
+
+        for _ in 0..100 {
+            assert_ne!(random.weighted(&[u64::MAX, 0, u64::MAX]), Some(1));
+        }
+
+        assert_eq!(random.weighted(&[0, 0, 0]), None);
+        assert_eq!(random.weighted(&[]), None);
     }
 }
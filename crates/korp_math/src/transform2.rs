@@ -0,0 +1,14 @@
+mod f32;
+mod fixed;
+
+use crate::Vec2;
+
+/// Affine 2D transform, a linear part given by the images of the x and y
+/// axes followed by a translation. Composing with `*` applies the right
+/// hand side first, like matrices.
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug)]
+pub struct Transform2<T> {
+    pub x: Vec2<T>,
+    pub y: Vec2<T>,
+    pub translation: Vec2<T>,
+}
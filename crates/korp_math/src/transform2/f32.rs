@@ -0,0 +1,198 @@
+use std::ops::Mul;
+
+use crate::{Transform2, Vec2};
+
+impl Transform2<f32> {
+    pub const IDENTITY: Transform2<f32> = Transform2 {
+        x: Vec2::new(1.0, 0.0),
+        y: Vec2::new(0.0, 1.0),
+        translation: Vec2::new(0.0, 0.0),
+    };
+
+    /// Rotates by the unit vector, then translates.
+    pub fn new(translation: Vec2<f32>, rotation: Vec2<f32>) -> Self {
+        Self {
+            x: rotation,
+            y: rotation.perp(),
+            translation,
+        }
+    }
+
+    pub fn translation(translation: Vec2<f32>) -> Self {
+        Self {
+            translation,
+            ..Self::IDENTITY
+        }
+    }
+
+    /// The rotation must be a unit vector.
+    pub fn rotation(rotation: Vec2<f32>) -> Self {
+        Self::new(Vec2::<f32>::ZERO, rotation)
+    }
+
+    pub fn scale(scale: Vec2<f32>) -> Self {
+        Self {
+            x: Vec2::new(scale.x, 0.0),
+            y: Vec2::new(0.0, scale.y),
+            translation: Vec2::<f32>::ZERO,
+        }
+    }
+
+    pub fn apply(&self, point: Vec2<f32>) -> Vec2<f32> {
+        self.apply_vector(point) + self.translation
+    }
+
+    /// Ignores the translation, for directions and offsets.
+    pub fn apply_vector(&self, vector: Vec2<f32>) -> Vec2<f32> {
+        Vec2::new(
+            self.x.x * vector.x + self.y.x * vector.y,
+            self.x.y * vector.x + self.y.y * vector.y,
+        )
+    }
+
+    pub fn determinant(&self) -> f32 {
+        self.x.cross(&self.y)
+    }
+
+    /// `None` when the transform collapses onto a line or a point.
+    pub fn inverse(&self) -> Option<Self> {
+        let determinant = self.determinant();
+
+        if determinant.abs() <= f32::EPSILON {
+            return None;
+        }
+
+        let x = Vec2::new(self.y.y, -self.x.y) / determinant;
+        let y = Vec2::new(-self.y.x, self.x.x) / determinant;
+        let linear = Self {
+            x,
+            y,
+            translation: Vec2::<f32>::ZERO,
+        };
+
+        Some(Self {
+            translation: -linear.apply_vector(self.translation),
+            ..linear
+        })
+    }
+
+    /// Column major, as the renderer expects it, with z left untouched.
+    pub fn to_mat4(&self) -> [[f32; 4]; 4] {
+        [
+            [self.x.x, self.x.y, 0.0, 0.0],
+            [self.y.x, self.y.y, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [self.translation.x, self.translation.y, 0.0, 1.0],
+        ]
+    }
+}
+
+impl Mul for Transform2<f32> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self {
+            x: self.apply_vector(rhs.x),
+            y: self.apply_vector(rhs.y),
+            translation: self.apply(rhs.translation),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Random, Vec2};
+
+    type Transform2 = crate::Transform2<f32>;
+
+    fn error(actual: Transform2, expected: Transform2) -> f32 {
+        [
+            actual.x - expected.x,
+            actual.y - expected.y,
+            actual.translation - expected.translation,
+        ]
+        .into_iter()
+        .map(|v| v.x.abs().max(v.y.abs()))
+        .fold(0.0, f32::max)
+    }
+
+    // rotated, scaled by up to 4 either way and moved by up to 100
+    fn transform(random: &mut Random) -> Transform2 {
+        let mut float = |min: f32, max: f32| {
+            min + (max - min) * (random.next() >> 40) as f32 / (1 << 24) as f32
+        };
+        let rotation = Vec2::<f32>::from_angle(float(-4.0, 4.0));
+        let scale = Vec2::new(float(0.25, 4.0), float(0.25, 4.0));
+        let translation = Vec2::new(float(-100.0, 100.0), float(-100.0, 100.0));
+
+        Transform2::translation(translation)
+            * Transform2::rotation(rotation)
+            * Transform2::scale(scale)
+    }
+
+    #[test]
+    fn composition_applies_the_right_hand_side_first() {
+        let translation = Transform2::translation(Vec2::new(3.0, 0.0));
+        let rotation = Transform2::rotation(Vec2::new(0.0, 1.0));
+        let point = Vec2::new(1.0, 0.0);
+
+        assert_eq!((translation * rotation).apply(point), Vec2::new(3.0, 1.0));
+        assert_eq!((rotation * translation).apply(point), Vec2::new(0.0, 4.0));
+    }
+
+    #[test]
+    fn inverse_undoes_the_transform() {
+        let mut random = Random::new(23);
+        let mut largest = 0.0_f32;
+
+        for _ in 0..1000 {
+            let transform = transform(&mut random);
+            let inverse = transform.inverse().unwrap();
+
+            largest = largest.max(error(transform * inverse, Transform2::IDENTITY));
+            largest = largest.max(error(inverse * transform, Transform2::IDENTITY));
+        }
+
+        assert!(largest <= 1e-4, "{largest}");
+    }
+
+    #[test]
+    fn singular_transforms_have_no_inverse() {
+        let flattened = Transform2::scale(Vec2::new(2.0, 0.0));
+        let parallel = Transform2 {
+            x: Vec2::new(1.0, 2.0),
+            y: Vec2::new(-2.0, -4.0),
+            translation: Vec2::new(5.0, 5.0),
+        };
+
+        assert_eq!(flattened.inverse(), None);
+        assert_eq!(parallel.inverse(), None);
+        assert_eq!(Transform2::default().inverse(), None);
+    }
+
+    #[test]
+    fn vectors_ignore_the_translation() {
+        let transform = Transform2::new(Vec2::new(5.0, -3.0), Vec2::new(0.0, 1.0));
+
+        assert_eq!(transform.apply(Vec2::new(2.0, 1.0)), Vec2::new(4.0, -1.0));
+        assert_eq!(
+            transform.apply_vector(Vec2::new(2.0, 1.0)),
+            Vec2::new(-1.0, 2.0)
+        );
+        assert_eq!(transform.apply(Vec2::<f32>::ZERO), Vec2::new(5.0, -3.0));
+        assert_eq!(transform.apply_vector(Vec2::<f32>::ZERO), Vec2::<f32>::ZERO);
+    }
+
+    #[test]
+    fn mirroring_flips_the_determinant() {
+        let rotation = Transform2::rotation(Vec2::<f32>::from_angle(0.5));
+        let mirror = Transform2::scale(Vec2::new(-1.0, 1.0));
+        let scale = Transform2::scale(Vec2::new(2.0, 3.0));
+
+        assert!((rotation.determinant() - 1.0).abs() <= f32::EPSILON);
+        assert_eq!(scale.determinant(), 6.0);
+        assert_eq!(mirror.determinant(), -1.0);
+        assert!((mirror * rotation * scale).determinant() < 0.0);
+        assert!((mirror * rotation * mirror).determinant() > 0.0);
+    }
+}
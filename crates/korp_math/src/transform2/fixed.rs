@@ -0,0 +1,222 @@
+use std::ops::Mul;
+
+use crate::{Flint, Flint64, Transform2, Vec2};
+
+macro_rules! transform2_fixed {
+    ($fixed:ident) => {
+        impl Transform2<$fixed> {
+            pub const IDENTITY: Transform2<$fixed> = Transform2 {
+                x: Vec2::new($fixed::ONE, $fixed::ZERO),
+                y: Vec2::new($fixed::ZERO, $fixed::ONE),
+                translation: Vec2::new($fixed::ZERO, $fixed::ZERO),
+            };
+
+            /// Rotates by the unit vector, then translates.
+            pub fn new(translation: Vec2<$fixed>, rotation: Vec2<$fixed>) -> Self {
+                Self {
+                    x: rotation,
+                    y: rotation.perp(),
+                    translation,
+                }
+            }
+
+            pub fn translation(translation: Vec2<$fixed>) -> Self {
+                Self {
+                    translation,
+                    ..Self::IDENTITY
+                }
+            }
+
+            /// The rotation must be a unit vector.
+            pub fn rotation(rotation: Vec2<$fixed>) -> Self {
+                Self::new(Vec2::<$fixed>::ZERO, rotation)
+            }
+
+            pub fn scale(scale: Vec2<$fixed>) -> Self {
+                Self {
+                    x: Vec2::new(scale.x, $fixed::ZERO),
+                    y: Vec2::new($fixed::ZERO, scale.y),
+                    translation: Vec2::<$fixed>::ZERO,
+                }
+            }
+
+            pub fn apply(&self, point: Vec2<$fixed>) -> Vec2<$fixed> {
+                self.apply_vector(point) + self.translation
+            }
+
+            /// Ignores the translation, for directions and offsets.
+            pub fn apply_vector(&self, vector: Vec2<$fixed>) -> Vec2<$fixed> {
+                Vec2::new(
+                    self.x.x * vector.x + self.y.x * vector.y,
+                    self.x.y * vector.x + self.y.y * vector.y,
+                )
+            }
+
+            pub fn determinant(&self) -> $fixed {
+                self.x.cross(&self.y)
+            }
+
+            /// `None` when the transform collapses onto a line or a point.
+            pub fn inverse(&self) -> Option<Self> {
+                let determinant = self.determinant();
+
+                if determinant == $fixed::ZERO {
+                    return None;
+                }
+
+                let x = Vec2::new(self.y.y, -self.x.y) / determinant;
+                let y = Vec2::new(-self.y.x, self.x.x) / determinant;
+                let linear = Self {
+                    x,
+                    y,
+                    translation: Vec2::<$fixed>::ZERO,
+                };
+
+                Some(Self {
+                    translation: -linear.apply_vector(self.translation),
+                    ..linear
+                })
+            }
+        }
+
+        impl Mul for Transform2<$fixed> {
+            type Output = Self;
+
+            fn mul(self, rhs: Self) -> Self::Output {
+                Self {
+                    x: self.apply_vector(rhs.x),
+                    y: self.apply_vector(rhs.y),
+                    translation: self.apply(rhs.translation),
+                }
+            }
+        }
+    };
+}
+
+transform2_fixed!(Flint);
+transform2_fixed!(Flint64);
+
+#[cfg(test)]
+mod tests {
+    macro_rules! transforms {
+        ($module:ident, $name:ident) => {
+            mod $module {
+                use crate::{Random, Vec2, $name};
+
+                type Transform2 = crate::Transform2<$name>;
+
+                // the smallest difference between two values
+                const STEP: f64 = $name::from_raw(1).to_f64();
+
+                fn vec2(x: i16, y: i16) -> Vec2<$name> {
+                    Vec2::new($name::from_f64(x as f64), $name::from_f64(y as f64))
+                }
+
+                fn error(actual: Transform2, expected: Transform2) -> f64 {
+                    [
+                        (actual.x.x, expected.x.x),
+                        (actual.x.y, expected.x.y),
+                        (actual.y.x, expected.y.x),
+                        (actual.y.y, expected.y.y),
+                        (actual.translation.x, expected.translation.x),
+                        (actual.translation.y, expected.translation.y),
+                    ]
+                    .into_iter()
+                    .map(|(a, e)| (a.to_f64() - e.to_f64()).abs())
+                    .fold(0.0, f64::max)
+                }
+
+                // rotated, scaled up by up to 4 and moved by up to 100
+                fn transform(random: &mut Random) -> Transform2 {
+                    let mut flint = |min: i16, max: i16| -> $name {
+                        let flint =
+                            random.flint(crate::Flint::from_i16(min), crate::Flint::from_i16(max));
+
+                        $name::from_f64(flint.to_f64())
+                    };
+                    let rotation = Vec2::<$name>::from_angle(flint(-4, 4));
+                    let scale = Vec2::new(flint(1, 4), flint(1, 4));
+                    let translation = Vec2::new(flint(-100, 100), flint(-100, 100));
+
+                    Transform2::translation(translation)
+                        * Transform2::rotation(rotation)
+                        * Transform2::scale(scale)
+                }
+
+                #[test]
+                fn composition_applies_the_right_hand_side_first() {
+                    let translation = Transform2::translation(vec2(3, 0));
+                    let rotation = Transform2::rotation(vec2(0, 1));
+
+                    assert_eq!((translation * rotation).apply(vec2(1, 0)), vec2(3, 1));
+                    assert_eq!((rotation * translation).apply(vec2(1, 0)), vec2(0, 4));
+                    assert_eq!(
+                        Transform2::new(vec2(3, 0), vec2(0, 1)),
+                        translation * rotation
+                    );
+                }
+
+                #[test]
+                fn inverse_undoes_the_transform() {
+                    let mut random = Random::new(23);
+                    let identity = Transform2::IDENTITY;
+                    let mut largest = 0.0_f64;
+
+                    for _ in 0..1000 {
+                        let transform = transform(&mut random);
+                        let inverse = transform.inverse().unwrap();
+
+                        largest = largest.max(error(transform * inverse, identity));
+                        largest = largest.max(error(inverse * transform, identity));
+                    }
+
+                    // steps of the inverse, scaled by translations up to 141 and the scale
+                    assert!(largest <= 2.0 * 141.0 * 4.0 * STEP, "{largest}");
+                }
+
+                #[test]
+                fn singular_transforms_have_no_inverse() {
+                    let flattened = Transform2::scale(vec2(2, 0));
+                    let parallel = Transform2 {
+                        x: vec2(1, 2),
+                        y: vec2(-2, -4),
+                        translation: vec2(5, 5),
+                    };
+
+                    assert_eq!(flattened.inverse(), None);
+                    assert_eq!(parallel.inverse(), None);
+                    assert_eq!(Transform2::default().inverse(), None);
+                }
+
+                #[test]
+                fn vectors_ignore_the_translation() {
+                    let transform = Transform2::new(vec2(5, -3), vec2(0, 1));
+
+                    assert_eq!(transform.apply(vec2(2, 1)), vec2(4, -1));
+                    assert_eq!(transform.apply_vector(vec2(2, 1)), vec2(-1, 2));
+                    assert_eq!(transform.apply(Vec2::<$name>::ZERO), vec2(5, -3));
+                    assert_eq!(
+                        transform.apply_vector(Vec2::<$name>::ZERO),
+                        Vec2::<$name>::ZERO
+                    );
+                }
+
+                #[test]
+                fn mirroring_flips_the_determinant() {
+                    let rotation = Transform2::rotation(vec2(0, 1));
+                    let mirror = Transform2::scale(vec2(-1, 1));
+                    let scale = Transform2::scale(vec2(2, 3));
+
+                    assert_eq!(rotation.determinant(), $name::ONE);
+                    assert_eq!(scale.determinant(), $name::from_f64(6.0));
+                    assert_eq!(mirror.determinant(), $name::NEG_ONE);
+                    assert!((mirror * rotation * scale).determinant() < $name::ZERO);
+                    assert!((mirror * rotation * mirror).determinant() > $name::ZERO);
+                }
+            }
+        };
+    }
+
+    transforms!(flint, Flint);
+    transforms!(flint64, Flint64);
+}
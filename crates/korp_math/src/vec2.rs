@@ -1,7 +1,7 @@
 mod f32;
-mod flint;
+mod fixed;
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Default, PartialEq, Eq, Hash, Debug)]
 pub struct Vec2<T> {
     pub x: T,
     pub y: T,
@@ -13,6 +13,24 @@ impl<T> From<Vec2<T>> for [T; 2] {
     }
 }
 
+impl<T> From<[T; 2]> for Vec2<T> {
+    fn from([x, y]: [T; 2]) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> From<(T, T)> for Vec2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> From<Vec2<T>> for (T, T) {
+    fn from(value: Vec2<T>) -> Self {
+        (value.x, value.y)
+    }
+}
+
 impl<T> Vec2<T> {
     pub const fn new(x: T, y: T) -> Self {
         Self { x, y }
@@ -1,8 +1,10 @@
-use std::ops::{Add, AddAssign, Mul, Sub};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 
-use crate::{Flint, Vec2};
+use crate::Vec2;
 
 impl Vec2<f32> {
+    pub const ZERO: Vec2<f32> = Vec2::new(0.0, 0.0);
+
     pub fn perp(&self) -> Self {
         Self {
             x: -self.y,
@@ -10,27 +12,45 @@ impl Vec2<f32> {
         }
     }
 
+    /// Zero when the vector is too short, or not finite, to have a direction.
     pub fn normalized(&self) -> Self {
+        self.try_normalized().unwrap_or(Self::ZERO)
+    }
+
+    pub fn try_normalized(&self) -> Option<Self> {
         let len = self.len();
 
-        if len == 0.0 {
-            return Self { x: 0.0, y: 0.0 };
+        if len <= f32::EPSILON || !len.is_finite() {
+            return None;
         }
 
-        Self {
+        Some(Self {
             x: self.x / len,
             y: self.y / len,
-        }
+        })
     }
 
     pub fn len(&self) -> f32 {
-        self.dot(self).sqrt()
+        self.x.hypot(self.y)
+    }
+
+    pub fn len_sqr(&self) -> f32 {
+        self.dot(self)
+    }
+
+    pub fn distance(&self, v: &Vec2<f32>) -> f32 {
+        (*v - *self).len()
     }
 
     pub fn dot(&self, v: &Vec2<f32>) -> f32 {
         self.x * v.x + self.y * v.y
     }
 
+    /// z of the 3D cross product, positive when v is counterclockwise of self.
+    pub fn cross(&self, v: &Vec2<f32>) -> f32 {
+        self.x * v.y - self.y * v.x
+    }
+
     pub fn angle(&self) -> f32 {
         self.y.atan2(self.x)
     }
@@ -41,11 +61,34 @@ impl Vec2<f32> {
             y: radians.sin(),
         }
     }
-}
 
-impl From<Vec2<Flint>> for Vec2<f32> {
-    fn from(value: Vec2<Flint>) -> Self {
-        Vec2::new(value.x.into(), value.y.into())
+    /// Signed radians from self to v, in the range [-PI, PI].
+    pub fn angle_between(&self, v: &Vec2<f32>) -> f32 {
+        self.cross(v).atan2(self.dot(v))
+    }
+
+    /// Zero when projecting onto a vector without length.
+    pub fn project_onto(&self, v: &Vec2<f32>) -> Self {
+        let v = v.normalized();
+
+        v * self.dot(&v)
+    }
+
+    /// The normal must be a unit vector.
+    pub fn reflect(&self, normal: &Vec2<f32>) -> Self {
+        *self - *normal * (self.dot(normal) * 2.0)
+    }
+
+    pub fn lerp(&self, v: &Vec2<f32>, t: f32) -> Self {
+        *self + (*v - *self) * t
+    }
+
+    pub fn clamp_len(&self, maximum: f32) -> Self {
+        if self.len() > maximum {
+            return self.normalized() * maximum;
+        }
+
+        *self
     }
 }
 
@@ -72,6 +115,13 @@ impl Sub for Vec2<f32> {
     }
 }
 
+impl SubAssign for Vec2<f32> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
 impl Mul<f32> for Vec2<f32> {
     type Output = Self;
 
@@ -79,3 +129,41 @@ impl Mul<f32> for Vec2<f32> {
         Vec2::new(self.x * rhs, self.y * rhs)
     }
 }
+
+impl Mul for Vec2<f32> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Vec2::new(self.x * rhs.x, self.y * rhs.y)
+    }
+}
+
+impl Div<f32> for Vec2<f32> {
+    type Output = Self;
+
+    fn div(self, rhs: f32) -> Self::Output {
+        Vec2::new(self.x / rhs, self.y / rhs)
+    }
+}
+
+impl Neg for Vec2<f32> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Vec2::new(-self.x, -self.y)
+    }
+}
+
+impl MulAssign<f32> for Vec2<f32> {
+    fn mul_assign(&mut self, rhs: f32) {
+        self.x *= rhs;
+        self.y *= rhs;
+    }
+}
+
+impl DivAssign<f32> for Vec2<f32> {
+    fn div_assign(&mut self, rhs: f32) {
+        self.x /= rhs;
+        self.y /= rhs;
+    }
+}
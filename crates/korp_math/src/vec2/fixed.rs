@@ -0,0 +1,375 @@
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::{Flint, Flint64, Vec2};
+
+macro_rules! vec2_fixed {
+    ($fixed:ident, $int:ty) => {
+        impl Vec2<$fixed> {
+            pub const ZERO: Vec2<$fixed> = Vec2::new($fixed::ZERO, $fixed::ZERO);
+
+            pub fn perp(&self) -> Self {
+                Self {
+                    x: -self.y,
+                    y: self.x,
+                }
+            }
+
+            /// Zero when the vector has no length.
+            pub fn normalized(&self) -> Self {
+                self.try_normalized().unwrap_or(Self::ZERO)
+            }
+
+            pub fn try_normalized(&self) -> Option<Self> {
+                let len = self.len();
+
+                if len == $fixed::ZERO {
+                    return None;
+                }
+
+                Some(Self {
+                    x: self.x / len,
+                    y: self.y / len,
+                })
+            }
+
+            /// Safe over the whole range, saturates when the length does not fit.
+            pub fn len(&self) -> $fixed {
+                $fixed::hypot(self.x, self.y)
+            }
+
+            // overflows past the square root of MAX, prefer len for anything but small vectors
+            pub fn len_sqr(&self) -> $fixed {
+                self.dot(self)
+            }
+
+            pub fn distance(&self, v: &Vec2<$fixed>) -> $fixed {
+                (*v - *self).len()
+            }
+
+            pub fn dot(&self, v: &Vec2<$fixed>) -> $fixed {
+                self.x * v.x + self.y * v.y
+            }
+
+            /// z of the 3D cross product, positive when v is counterclockwise of self.
+            pub fn cross(&self, v: &Vec2<$fixed>) -> $fixed {
+                self.x * v.y - self.y * v.x
+            }
+
+            /// Radians from the x axis, in the range [-PI, PI].
+            pub fn angle(&self) -> $fixed {
+                $fixed::atan2(self.y, self.x)
+            }
+
+            pub fn from_angle(radians: $fixed) -> Self {
+                let (sin, cos) = radians.sin_cos();
+
+                Self { x: cos, y: sin }
+            }
+
+            /// Signed radians from self to v, in the range [-PI, PI].
+            pub fn angle_between(&self, v: &Vec2<$fixed>) -> $fixed {
+                // normalized first so the products cannot overflow
+                let a = self.normalized();
+                let b = v.normalized();
+
+                $fixed::atan2(a.cross(&b), a.dot(&b))
+            }
+
+            /// Zero when projecting onto a vector without length.
+            pub fn project_onto(&self, v: &Vec2<$fixed>) -> Self {
+                let v = v.normalized();
+
+                v * self.dot(&v)
+            }
+
+            /// The normal must be a unit vector.
+            pub fn reflect(&self, normal: &Vec2<$fixed>) -> Self {
+                *self - *normal * (self.dot(normal) * 2)
+            }
+
+            pub fn lerp(&self, v: &Vec2<$fixed>, t: $fixed) -> Self {
+                *self + (*v - *self) * t
+            }
+
+            pub fn clamp_len(&self, maximum: $fixed) -> Self {
+                if self.len() > maximum {
+                    return self.normalized() * maximum;
+                }
+
+                *self
+            }
+
+            /// Rounds every component to the nearest.
+            pub fn from_f32(value: Vec2<f32>) -> Self {
+                Self {
+                    x: $fixed::from_f32(value.x),
+                    y: $fixed::from_f32(value.y),
+                }
+            }
+
+            pub fn rotated(&self, degrees: $fixed) -> Self {
+                let radians = degrees.to_radians();
+                let (sin, cos) = radians.sin_cos();
+
+                let x = self.x * cos - self.y * sin;
+                let y = self.x * sin + self.y * cos;
+
+                Self { x, y }
+            }
+
+            pub fn rotated_v(&self, theta: Vec2<$fixed>) -> Self {
+                Self {
+                    x: self.x * theta.x - self.y * theta.y,
+                    y: self.x * theta.y + self.y * theta.x,
+                }
+            }
+        }
+
+        impl Add for Vec2<$fixed> {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self::Output {
+                Self {
+                    x: self.x + rhs.x,
+                    y: self.y + rhs.y,
+                }
+            }
+        }
+
+        impl Sub for Vec2<$fixed> {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self::Output {
+                Self {
+                    x: self.x - rhs.x,
+                    y: self.y - rhs.y,
+                }
+            }
+        }
+
+        impl Mul<$fixed> for Vec2<$fixed> {
+            type Output = Self;
+
+            fn mul(self, rhs: $fixed) -> Self::Output {
+                Self {
+                    x: self.x * rhs,
+                    y: self.y * rhs,
+                }
+            }
+        }
+
+        impl Mul<$int> for Vec2<$fixed> {
+            type Output = Self;
+
+            fn mul(self, rhs: $int) -> Self::Output {
+                Self {
+                    x: self.x * rhs,
+                    y: self.y * rhs,
+                }
+            }
+        }
+
+        impl Mul for Vec2<$fixed> {
+            type Output = Self;
+
+            fn mul(self, rhs: Self) -> Self::Output {
+                Self {
+                    x: self.x * rhs.x,
+                    y: self.y * rhs.y,
+                }
+            }
+        }
+
+        impl Div<$fixed> for Vec2<$fixed> {
+            type Output = Self;
+
+            fn div(self, rhs: $fixed) -> Self::Output {
+                Self {
+                    x: self.x / rhs,
+                    y: self.y / rhs,
+                }
+            }
+        }
+
+        impl Neg for Vec2<$fixed> {
+            type Output = Self;
+
+            fn neg(self) -> Self::Output {
+                Self {
+                    x: -self.x,
+                    y: -self.y,
+                }
+            }
+        }
+
+        impl MulAssign<$fixed> for Vec2<$fixed> {
+            fn mul_assign(&mut self, rhs: $fixed) {
+                self.x = self.x * rhs;
+                self.y = self.y * rhs;
+            }
+        }
+
+        impl DivAssign<$fixed> for Vec2<$fixed> {
+            fn div_assign(&mut self, rhs: $fixed) {
+                self.x /= rhs;
+                self.y /= rhs;
+            }
+        }
+
+        impl AddAssign for Vec2<$fixed> {
+            fn add_assign(&mut self, rhs: Self) {
+                self.x += rhs.x;
+                self.y += rhs.y;
+            }
+        }
+
+        impl SubAssign for Vec2<$fixed> {
+            fn sub_assign(&mut self, rhs: Self) {
+                self.x -= rhs.x;
+                self.y -= rhs.y;
+            }
+        }
+
+        impl From<Vec2<$fixed>> for Vec2<f32> {
+            fn from(value: Vec2<$fixed>) -> Self {
+                Vec2::new(value.x.into(), value.y.into())
+            }
+        }
+    };
+}
+
+vec2_fixed!(Flint, i16);
+vec2_fixed!(Flint64, i32);
+
+impl From<Vec2<Flint>> for Vec2<Flint64> {
+    fn from(value: Vec2<Flint>) -> Self {
+        Vec2::new(value.x.into(), value.y.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Flint, Random, Vec2};
+
+    const CASES: usize = 2000;
+    // components stay small enough for dot and cross products to fit
+    const RANGE: Flint = Flint::new(100, 0);
+    // the smallest difference between two flints
+    const STEP: f32 = Flint::from_raw(1).to_f32();
+
+    fn error(actual: Flint, expected: f32) -> f32 {
+        (actual.to_f32() - expected).abs()
+    }
+
+    fn error_v(actual: Vec2<Flint>, expected: Vec2<f32>) -> f32 {
+        error(actual.x, expected.x).max(error(actual.y, expected.y))
+    }
+
+    fn vector(random: &mut Random) -> Vec2<Flint> {
+        Vec2::new(random.flint(-RANGE, RANGE), random.flint(-RANGE, RANGE))
+    }
+
+    // the largest error of a property over random pairs of vectors, given
+    // both as fixed and as floats
+    fn largest(property: impl Fn(Vec2<Flint>, Vec2<Flint>, Vec2<f32>, Vec2<f32>) -> f32) -> f32 {
+        let mut random = Random::new(19);
+        let mut largest = 0.0_f32;
+
+        for _ in 0..CASES {
+            let (a, b) = (vector(&mut random), vector(&mut random));
+
+            largest = largest.max(property(a, b, a.into(), b.into()));
+        }
+
+        largest
+    }
+
+    #[test]
+    fn exact_operations_match_f32() {
+        assert_eq!(largest(|a, _, fa, _| error_v(a.perp(), fa.perp())), 0.0);
+        assert_eq!(largest(|a, b, fa, fb| error_v(a + b, fa + fb)), 0.0);
+        assert_eq!(largest(|a, b, fa, fb| error_v(a - b, fa - fb)), 0.0);
+    }
+
+    #[test]
+    fn scaling_is_within_a_step() {
+        let mul = largest(|a, b, fa, _| {
+            let t = b.x / RANGE;
+
+            error_v(a * t, fa * t.to_f32())
+        });
+        let div = largest(|a, b, fa, _| {
+            // kept away from zero
+            let divisor = b.x.abs() + Flint::ONE;
+
+            error_v(a / divisor, fa / divisor.to_f32())
+        });
+        let lerp = largest(|a, b, fa, fb| {
+            let t = Flint::from_raw(b.x.raw.rem_euclid(Flint::ONE.raw));
+
+            error_v(a.lerp(&b, t), fa.lerp(&fb, t.to_f32()))
+        });
+
+        assert!(mul <= STEP, "mul {mul}");
+        assert!(div <= STEP, "div {div}");
+        assert!(lerp <= STEP, "lerp {lerp}");
+    }
+
+    #[test]
+    fn lengths_are_within_a_step() {
+        let len = largest(|a, _, fa, _| error(a.len(), fa.len()));
+        let distance = largest(|a, b, fa, fb| error(a.distance(&b), fa.distance(&fb)));
+        let normalized = largest(|a, _, fa, _| error_v(a.normalized(), fa.normalized()));
+
+        assert!(len <= STEP, "len {len}");
+        assert!(distance <= STEP, "distance {distance}");
+        assert!(normalized <= STEP, "normalized {normalized}");
+    }
+
+    #[test]
+    fn products_are_within_f32_precision() {
+        // f32 itself only keeps 2^-9 at the size of the largest products
+        let dot = largest(|a, b, fa, fb| error(a.dot(&b), fa.dot(&fb)));
+        let cross = largest(|a, b, fa, fb| error(a.cross(&b), fa.cross(&fb)));
+
+        assert!(dot <= 1.0 / 512.0, "dot {dot}");
+        assert!(cross <= 1.0 / 512.0, "cross {cross}");
+    }
+
+    #[test]
+    fn angles_are_within_16_steps() {
+        let angle = largest(|a, _, fa, _| error(a.angle(), fa.angle()));
+        let between = largest(|a, b, fa, fb| error(a.angle_between(&b), fa.angle_between(&fb)));
+        let from_angle = largest(|a, _, _, _| {
+            // over a full turn either way
+            let radians = a.x / Flint::from_i16(16);
+            let expected = Vec2::<f32>::from_angle(radians.to_f32());
+
+            error_v(Vec2::<Flint>::from_angle(radians), expected)
+        });
+
+        assert!(angle <= 16.0 * STEP, "angle {angle}");
+        assert!(between <= 16.0 * STEP, "angle_between {between}");
+        assert!(from_angle <= 16.0 * STEP, "from_angle {from_angle}");
+    }
+
+    #[test]
+    fn projections_are_within_the_error_of_the_normal() {
+        // a step of the normalized vector, scaled by lengths up to 141
+        let project = largest(|a, b, fa, fb| error_v(a.project_onto(&b), fa.project_onto(&fb)));
+        let clamp_len = largest(|a, b, fa, _| {
+            let maximum = b.x.abs();
+
+            error_v(a.clamp_len(maximum), fa.clamp_len(maximum.to_f32()))
+        });
+        let reflect = largest(|a, b, fa, _| {
+            let normal = b.normalized();
+
+            error_v(a.reflect(&normal), fa.reflect(&normal.into()))
+        });
+
+        assert!(reflect <= 16.0 * STEP, "reflect {reflect}");
+        assert!(project <= 2.0 * 141.0 * STEP, "project_onto {project}");
+        assert!(clamp_len <= 141.0 * STEP, "clamp_len {clamp_len}");
+    }
+}
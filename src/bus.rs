@@ -21,9 +21,8 @@ impl Bus {
             network.event(&event);
 
             // // let's not print these...
-            match event {
-                Event::Cosmos(IntentEvent::Event(CosmosEvent::TrackedMovement { .. })) => continue,
-                _ => (),
+            if let Event::Cosmos(IntentEvent::Event(CosmosEvent::TrackedMovement { .. })) = event {
+                continue;
             }
 
             // println!("{:?}", event);
@@ -33,4 +32,14 @@ impl Bus {
     pub fn send<T: Into<Event>>(&mut self, event: T) {
         self.events.push(event.into());
     }
+
+    #[cfg(test)]
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+
+    #[cfg(test)]
+    pub fn drain(&mut self) -> Vec<Event> {
+        std::mem::take(&mut self.events)
+    }
 }
@@ -1,14 +1,20 @@
 #![allow(dead_code)] // not all events are actually used per se, but they might be logged
 
-use std::net::IpAddr;
+use std::{io, net::IpAddr};
 
 use korp_engine::CoreEvent;
 use korp_math::{Flint, Vec2};
 
 use crate::{
-    ecs::{commands::Command, cosmos::Configure, entities::Entity, tracker::Track},
+    ecs::{
+        checksum::{Checksum, Dump},
+        commands::Command,
+        cosmos::Configure,
+        entities::Entity,
+        tracker::Track,
+    },
     network,
-    nexus::{self, game, lobby, menu},
+    nexus::{self, game, lobby, menu, replay},
 };
 
 #[derive(Debug)]
@@ -20,6 +26,7 @@ pub enum Event {
     Menu(MenuEvent),
     Lobby(LobbyEvent),
     Game(GameEvent),
+    Replay(ReplayEvent),
 }
 
 #[derive(Debug)]
@@ -42,15 +49,32 @@ pub enum CosmosEvent {
         entity: Entity,
     },
     Died(Entity),
+    Damaged {
+        entity: Entity,
+        source: Entity,
+        amount: Flint,
+    },
+    Hitscan {
+        source: Entity,
+        target: Entity,
+        point: Vec2<Flint>,
+        damage: Flint,
+    },
     TrackedDeath(Entity),
     TrackedMovement {
         entity: Entity,
         centroid: Vec2<Flint>,
+        rotation: Vec2<Flint>,
+        velocity: Vec2<Flint>,
     },
     Collided {
         alpha: Entity,
         beta: Entity,
-        mtv: Flint,
+        // unit axis pointing from alpha to beta, beta moved by depth along it separates them
+        axis: Vec2<Flint>,
+        depth: Flint,
+        // fraction of the tick at which they first touched
+        toi: Flint,
     },
 }
 
@@ -60,7 +84,15 @@ pub enum NetworkIntent {
     Connect(IpAddr),
     Disconnect,
     Launch,
-    Commands { tick: usize, commands: Vec<Command> },
+    Commands {
+        tick: usize,
+        commands: Vec<Command>,
+    },
+    Checksum {
+        tick: usize,
+        checksum: Checksum,
+        dump: Option<Dump>,
+    },
     Pause,
     Resume,
 }
@@ -77,6 +109,7 @@ pub enum NetworkEvent {
     Launched {
         seed: u64,
         delay: usize,
+        ids: Vec<usize>,
     },
     Disconnected {
         id: usize,
@@ -86,8 +119,18 @@ pub enum NetworkEvent {
         tick: usize,
         commands: Vec<Command>,
     },
+    Desynced {
+        tick: usize,
+        id: usize,
+        ours: Checksum,
+        theirs: Checksum,
+        // our own state, if the cosmos was asked to keep it
+        dump: Option<Dump>,
+    },
     Paused,
     Resumed,
+    // the transport failed, whatever it was doing is dropped
+    Error(io::Error),
 }
 
 #[derive(Debug)]
@@ -118,6 +161,14 @@ pub enum GameEvent {
     Action(game::Action),
     Transitioned(game::State),
     Toggled(bool),
+    Error(io::Error),
+}
+
+#[derive(Debug)]
+pub enum ReplayEvent {
+    Action(replay::Action),
+    Transitioned(replay::State),
+    Error(io::Error),
 }
 
 impl From<CosmosEvent> for Event {
@@ -173,3 +224,9 @@ impl From<GameEvent> for Event {
         Event::Game(value)
     }
 }
+
+impl From<ReplayEvent> for Event {
+    fn from(value: ReplayEvent) -> Self {
+        Event::Replay(value)
+    }
+}
@@ -1,8 +1,11 @@
+pub mod checksum;
 pub mod commands;
 pub mod components;
 pub mod cosmos;
 pub mod entities;
 pub mod forge;
+pub mod query;
+pub mod ray;
 pub mod sparse_set;
 pub mod systems;
 pub mod tracker;
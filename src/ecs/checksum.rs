@@ -0,0 +1,279 @@
+use std::{
+    fmt::{Debug, Write},
+    hash::{Hash, Hasher},
+};
+
+use korp_math::Random;
+
+use crate::ecs::{components::Components, entities::EntityFactory, sparse_set::SparseSet};
+
+// amount of ticks between two checksums
+pub const CHECKSUM_INTERVAL: usize = 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Set {
+    Bodies,
+    Motions,
+    Hitboxes,
+    Healths,
+    Weapons,
+    Lifetimes,
+    Particles,
+    Entities,
+    Random,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Checksum {
+    pub hashes: [u64; Set::ALL.len()],
+}
+
+// std's hashers are randomly seeded or differ between versions
+#[derive(Clone, Copy)]
+struct Fnv1a(u64);
+
+/// Textual state of every set at the tick a checksum was taken,
+/// kept around to explain what went wrong once peers disagree.
+#[derive(Debug, Clone)]
+pub struct Dump {
+    // boxed, it travels over the bus next to much smaller events
+    sets: Box<[String; Set::ALL.len()]>,
+}
+
+impl Set {
+    pub const ALL: [Set; 9] = [
+        Set::Bodies,
+        Set::Motions,
+        Set::Hitboxes,
+        Set::Healths,
+        Set::Weapons,
+        Set::Lifetimes,
+        Set::Particles,
+        Set::Entities,
+        Set::Random,
+    ];
+}
+
+impl Checksum {
+    /// Hashes the components themselves, they only hold integers and fixed
+    /// point values so the hash is as deterministic as the simulation.
+    pub fn take(components: &Components, entities: &EntityFactory, random: &Random) -> Self {
+        let logic = &components.logic;
+        let mut hashers = [Fnv1a::new(); Set::ALL.len()];
+
+        hash_sparse(&mut hashers[Set::Bodies as usize], &logic.bodies);
+        hash_sparse(&mut hashers[Set::Motions as usize], &logic.motions);
+        hash_sparse(&mut hashers[Set::Hitboxes as usize], &logic.hitboxes);
+        hash_sparse(&mut hashers[Set::Healths as usize], &logic.healths);
+        hash_sparse(&mut hashers[Set::Weapons as usize], &logic.weapons);
+        hash_sparse(&mut hashers[Set::Lifetimes as usize], &logic.lifetimes);
+        logic.particles.hash(&mut hashers[Set::Particles as usize]);
+
+        // the order of the free list decides which index is handed out next
+        entities
+            .generations()
+            .hash(&mut hashers[Set::Entities as usize]);
+        entities.free().hash(&mut hashers[Set::Entities as usize]);
+        random.state().hash(&mut hashers[Set::Random as usize]);
+
+        Checksum {
+            hashes: hashers.map(|hasher| hasher.finish()),
+        }
+    }
+
+    pub fn differing(&self, other: &Checksum) -> Vec<Set> {
+        Set::ALL
+            .into_iter()
+            .filter(|set| self.hashes[*set as usize] != other.hashes[*set as usize])
+            .collect()
+    }
+}
+
+impl Dump {
+    /// Only taken when asked for, formatting every set is far slower than
+    /// hashing it.
+    pub fn take(components: &Components, entities: &EntityFactory, random: &Random) -> Self {
+        let logic = &components.logic;
+        let mut sets: [String; Set::ALL.len()] = Default::default();
+
+        dump_sparse(&mut sets[Set::Bodies as usize], &logic.bodies);
+        dump_sparse(&mut sets[Set::Motions as usize], &logic.motions);
+        dump_sparse(&mut sets[Set::Hitboxes as usize], &logic.hitboxes);
+        dump_sparse(&mut sets[Set::Healths as usize], &logic.healths);
+        dump_sparse(&mut sets[Set::Weapons as usize], &logic.weapons);
+        dump_sparse(&mut sets[Set::Lifetimes as usize], &logic.lifetimes);
+
+        for particle in logic.particles.iter() {
+            let _ = writeln!(sets[Set::Particles as usize], "{:?}", particle);
+        }
+
+        for (index, generation) in entities.generations().iter().enumerate() {
+            let _ = writeln!(sets[Set::Entities as usize], "{} {}", index, generation);
+        }
+
+        let _ = writeln!(sets[Set::Entities as usize], "free {:?}", entities.free());
+        let _ = writeln!(sets[Set::Random as usize], "{:?}", random.state());
+
+        Dump {
+            sets: Box::new(sets),
+        }
+    }
+
+    pub fn get(&self, set: Set) -> &str {
+        &self.sets[set as usize]
+    }
+}
+
+fn dump_sparse<T: Debug>(dump: &mut String, set: &SparseSet<T>) {
+    for (entity, component) in set.iter() {
+        let _ = writeln!(
+            dump,
+            "{}:{} {:?}",
+            entity.index, entity.generation, component
+        );
+    }
+}
+
+fn hash_sparse<T: Hash>(hasher: &mut Fnv1a, set: &SparseSet<T>) {
+    for (entity, component) in set.iter() {
+        entity.hash(hasher);
+        component.hash(hasher);
+    }
+}
+
+impl Fnv1a {
+    fn new() -> Self {
+        Self(0xcbf29ce484222325)
+    }
+}
+
+impl Hasher for Fnv1a {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x100000001b3);
+        }
+    }
+
+    // integers are little endian and sizes 64 bits wide on every platform,
+    // peers have to agree on the bytes and not only on the values
+
+    fn write_u16(&mut self, value: u16) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_u32(&mut self, value: u32) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, value: usize) {
+        self.write_u64(value as u64);
+    }
+
+    fn write_i16(&mut self, value: i16) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_i32(&mut self, value: i32) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_i64(&mut self, value: i64) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_isize(&mut self, value: isize) {
+        self.write_i64(value as i64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use korp_engine::shapes::Rectangle;
+    use korp_math::{Flint, Vec2};
+
+    use super::*;
+    use crate::ecs::forge::Forge;
+
+    fn cosmos() -> (Components, Forge) {
+        let bounds = Rectangle {
+            x: Flint::ZERO,
+            y: Flint::ZERO,
+            width: Flint::from_i16(1000),
+            height: Flint::from_i16(1000),
+        };
+        let mut components = Components::new(bounds);
+        let mut forge = Forge::new();
+
+        forge.triangle(
+            Vec2::new(Flint::from_i16(100), Flint::from_i16(100)),
+            &mut components,
+        );
+        forge.rectangle(
+            Vec2::new(Flint::from_i16(300), Flint::from_i16(200)),
+            &mut components,
+        );
+
+        (components, forge)
+    }
+
+    #[test]
+    fn same_state_same_checksum() {
+        let random = Random::new(7);
+        let (alpha, forge_alpha) = cosmos();
+        let (beta, forge_beta) = cosmos();
+
+        assert_eq!(
+            Checksum::take(&alpha, forge_alpha.entities(), &random),
+            Checksum::take(&beta, forge_beta.entities(), &random),
+        );
+    }
+
+    #[test]
+    fn only_changed_sets_differ() {
+        let random = Random::new(7);
+        let (alpha, forge_alpha) = cosmos();
+        let (mut beta, forge_beta) = cosmos();
+
+        let (_, motion) = beta.logic.motions.iter_mut().next().unwrap();
+        motion.velocity.x += Flint::from_raw(1);
+
+        let checksum = Checksum::take(&alpha, forge_alpha.entities(), &random);
+        let other = Checksum::take(&beta, forge_beta.entities(), &random);
+        assert_eq!(checksum.differing(&other), [Set::Motions]);
+
+        let other = Checksum::take(&alpha, forge_alpha.entities(), &Random::new(8));
+        assert_eq!(checksum.differing(&other), [Set::Random]);
+    }
+
+    #[test]
+    fn hashes_are_platform_independent() {
+        let mut hasher = Fnv1a::new();
+        assert_eq!(hasher.finish(), 0xcbf29ce484222325);
+
+        // a usize hashes like a u64 and both little endian
+        7usize.hash(&mut hasher);
+        let mut other = Fnv1a::new();
+        other.write(&[7, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(hasher.finish(), other.finish());
+    }
+
+    #[test]
+    fn dump_describes_every_set() {
+        let random = Random::new(7);
+        let (components, forge) = cosmos();
+        let dump = Dump::take(&components, forge.entities(), &random);
+
+        assert_eq!(dump.get(Set::Bodies).lines().count(), 2);
+        assert!(dump.get(Set::Entities).contains("free"));
+        assert!(!dump.get(Set::Random).is_empty());
+    }
+}
@@ -1,14 +1,29 @@
-use korp_math::{Flint, Vec2};
+use korp_math::{Flint, Ray, Transform2, Vec2};
 
 use crate::{
     bus::events::CosmosEvent,
     ecs::{
-        components::{Components, Shape},
+        components::{Arsenal, CollisionFilter, Components, WeaponKind},
         entities::Entity,
         forge::Forge,
+        query::{Optional, Query},
+        ray::Castable,
     },
 };
 
+// distance between and ticks lived by the particles drawing a beam
+const BEAM_SPACING: Flint = Flint::from_i16(6);
+const BEAM_LIFETIME: u32 = 2;
+// outline of the spawned polygons, a rough rock
+const ROCK: [Vec2<Flint>; 6] = [
+    Vec2::new(Flint::new(32, 0), Flint::new(4, 0)),
+    Vec2::new(Flint::new(12, 0), Flint::new(26, 0)),
+    Vec2::new(Flint::new(-18, 0), Flint::new(24, 0)),
+    Vec2::new(Flint::new(-30, 0), Flint::new(-2, 0)),
+    Vec2::new(Flint::new(-14, 0), Flint::new(-28, 0)),
+    Vec2::new(Flint::new(20, 0), Flint::new(-22, 0)),
+];
+
 #[derive(Debug, Clone)]
 pub enum Command {
     Accelerate(Entity),
@@ -17,6 +32,7 @@ pub enum Command {
     TurnRight(Entity),
     Shoot(Entity),
     Kill(Entity),
+    CycleWeapon(Entity),
     Spawn { id: Option<usize>, kind: SpawnKind },
 }
 
@@ -28,9 +44,18 @@ pub enum SpawnKind {
     Rectangle {
         centroid: Vec2<Flint>,
     },
+    Circle {
+        centroid: Vec2<Flint>,
+    },
+    Polygon {
+        centroid: Vec2<Flint>,
+    },
     Projectile {
         owner: Entity,
         relative_speed: Flint,
+        speed: Flint,
+        damage: Flint,
+        lifetime: u32,
         centroid: Vec2<Flint>,
         rotation: Vec2<Flint>,
     },
@@ -56,6 +81,7 @@ impl Command {
             Command::TurnRight(entity) => turn_right(entity, components),
             Command::Shoot(entity) => shoot(entity, components, forge, events),
             Command::Kill(entity) => kill(entity, components, forge, events),
+            Command::CycleWeapon(entity) => cycle_weapon(entity, components),
             Command::Spawn { id, kind } => spawn(id, kind, components, forge, events),
         }
     }
@@ -67,21 +93,30 @@ fn kill(
     forge: &mut Forge,
     events: &mut Vec<CosmosEvent>,
 ) {
+    // several things can kill the same entity within a tick
+    if !forge.entities().is_alive(*entity) {
+        return;
+    }
+
     forge.destroy(*entity, components);
     events.push(CosmosEvent::Died(*entity));
 }
 
 fn accelerate(entity: &Entity, components: &mut Components) {
-    let (Some(motion), Some(body)) = (
-        components.logic.motions.get_mut(entity),
-        components.logic.bodies.get(entity),
-    ) else {
+    let logic = &mut components.logic;
+    let mut query = Query::new((
+        &mut logic.motions,
+        &logic.bodies,
+        Optional(&mut logic.exhaust_emitters),
+    ));
+
+    let Some((motion, body, emitter)) = query.get(entity) else {
         return;
     };
 
     motion.velocity += body.new.rotation * motion.acceleration;
 
-    if let Some(emitter) = components.logic.exhaust_emitters.get_mut(entity) {
+    if let Some(emitter) = emitter {
         emitter.lifetime += 1;
 
         if emitter.lifetime > emitter.lifetime_maximum {
@@ -91,10 +126,10 @@ fn accelerate(entity: &Entity, components: &mut Components) {
 }
 
 fn decelerate(entity: &Entity, components: &mut Components) {
-    let (Some(motion), Some(body)) = (
-        components.logic.motions.get_mut(entity),
-        components.logic.bodies.get(entity),
-    ) else {
+    let logic = &mut components.logic;
+    let mut query = Query::new((&mut logic.motions, &logic.bodies));
+
+    let Some((motion, body)) = query.get(entity) else {
         return;
     };
 
@@ -123,38 +158,134 @@ fn shoot(
     forge: &mut Forge,
     events: &mut Vec<CosmosEvent>,
 ) {
-    let Some(body) = components.logic.bodies.get(entity) else {
+    let Some(arsenal) = components.logic.weapons.get_mut(entity) else {
+        return;
+    };
+
+    // the rest of a burst is shot on its own, the trigger only starts one
+    let weapon = arsenal.current_mut();
+    if weapon.volley() || weapon.trigger() {
+        fire(entity, components, forge, events);
+    }
+}
+
+fn fire(
+    entity: &Entity,
+    components: &mut Components,
+    forge: &mut Forge,
+    events: &mut Vec<CosmosEvent>,
+) {
+    let (Some(body), Some(weapon)) = (
+        components.logic.bodies.get(entity),
+        components.logic.weapons.get(entity).map(Arsenal::current),
+    ) else {
         return;
     };
 
     // calculate the spawn point
     let rotation = body.new.rotation;
-    let centroid = body.new.centroid
-        + match body.new.shape {
-            Shape::Triangle(triangle) => triangle.top,
-            Shape::Rectangle(rectangle) => {
-                Vec2::new(rectangle.width * Flint::ZERO_FIVE, rectangle.height)
-            }
-        }
-        .rotated_v(rotation);
+    let centroid =
+        Transform2::<Flint>::new(body.new.centroid, rotation).apply(body.new.shape.muzzle());
+
+    let kind = weapon.kind;
+    let speed = weapon.projectile_speed;
+    let damage = weapon.damage;
+    let lifetime = weapon.projectile_lifetime;
+    let directions = weapon.directions(rotation);
+
+    if let WeaponKind::Beam { range } = kind {
+        let ray = Ray {
+            origin: centroid,
+            direction: rotation,
+            range,
+        };
+
+        beam(entity, &ray, damage, components, forge, events);
+        return;
+    }
 
     let relative_speed = match components.logic.motions.get(entity) {
         Some(motion) => motion.velocity.len(),
         None => Flint::ZERO,
     };
 
-    spawn(
-        &None,
-        &SpawnKind::Projectile {
-            owner: *entity,
-            relative_speed,
+    for direction in directions {
+        spawn(
+            &None,
+            &SpawnKind::Projectile {
+                owner: *entity,
+                relative_speed,
+                speed,
+                damage,
+                lifetime,
+                centroid,
+                rotation: direction,
+            },
+            components,
+            forge,
+            events,
+        );
+    }
+}
+
+fn beam(
+    entity: &Entity,
+    ray: &Ray,
+    damage: Flint,
+    components: &mut Components,
+    forge: &mut Forge,
+    events: &mut Vec<CosmosEvent>,
+) {
+    let hit = ray.cast(components, |target| {
+        let filters = (
+            components.logic.collision_filters.get(entity),
+            components.logic.collision_filters.get(target),
+        );
+
+        match filters {
+            (_, None) => false,
+            // beams hit whatever projectiles would
+            (_, Some(filter)) => target != entity && filter.mask & CollisionFilter::PROJECTILE != 0,
+        }
+    });
+
+    let length = match hit {
+        Some(hit) => {
+            events.push(CosmosEvent::Hitscan {
+                source: *entity,
+                target: hit.entity,
+                point: ray.origin + ray.direction * hit.distance,
+                damage,
+            });
+
+            hit.distance
+        }
+        None => ray.range,
+    };
+
+    // the beam itself is drawn with short lived particles
+    let mut distance = Flint::ZERO;
+
+    while distance < length {
+        let centroid = ray.origin + ray.direction * distance;
+        forge.particle(
             centroid,
-            rotation,
-        },
-        components,
-        forge,
-        events,
-    );
+            ray.direction,
+            Flint::ZERO,
+            BEAM_LIFETIME,
+            components,
+        );
+
+        distance += BEAM_SPACING;
+    }
+}
+
+fn cycle_weapon(entity: &Entity, components: &mut Components) {
+    let Some(arsenal) = components.logic.weapons.get_mut(entity) else {
+        return;
+    };
+
+    arsenal.cycle();
 }
 
 fn spawn(
@@ -167,12 +298,31 @@ fn spawn(
     let entity = match kind {
         SpawnKind::Triangle { centroid } => forge.triangle(*centroid, components),
         SpawnKind::Rectangle { centroid } => forge.rectangle(*centroid, components),
+        SpawnKind::Circle { centroid } => forge.circle(*centroid, components),
+        SpawnKind::Polygon { centroid } => {
+            let Some(entity) = forge.polygon(*centroid, &ROCK, components) else {
+                return;
+            };
+
+            entity
+        }
         SpawnKind::Projectile {
             owner,
             relative_speed,
+            speed,
+            damage,
+            lifetime,
             centroid,
             rotation,
-        } => forge.projectile(*owner, *relative_speed, *centroid, *rotation, components),
+        } => forge.projectile(
+            *owner,
+            *relative_speed + *speed,
+            *damage,
+            *lifetime,
+            *centroid,
+            *rotation,
+            components,
+        ),
         SpawnKind::Particle {
             centroid,
             direction,
@@ -4,7 +4,9 @@ use korp_math::{Flint, Vec2};
 use crate::ecs::{entities::Entity, sparse_set::SparseSet};
 
 pub mod collision_filter;
+pub mod shape;
 pub mod traits;
+pub mod weapon;
 
 pub struct Logic {
     pub bodies: SparseSet<Morph<Body<Flint>>>,
@@ -16,6 +18,10 @@ pub struct Logic {
     pub owners: SparseSet<Owner>,
     pub spawn_protections: SparseSet<SpawnProtection>,
     pub exhaust_emitters: SparseSet<ExhaustEmitter>,
+    pub healths: SparseSet<Health>,
+    pub damages: SparseSet<Damage>,
+    pub weapons: SparseSet<Arsenal>,
+    pub lifetimes: SparseSet<Lifetime>,
     pub particles: Vec<Particle>,
 }
 
@@ -47,6 +53,10 @@ impl Components {
                 owners: SparseSet::new(capacity),
                 spawn_protections: SparseSet::new(capacity),
                 exhaust_emitters: SparseSet::new(capacity),
+                healths: SparseSet::new(capacity),
+                damages: SparseSet::new(capacity),
+                weapons: SparseSet::new(capacity),
+                lifetimes: SparseSet::new(capacity),
                 particles: Vec::new(),
             },
             render: Render {
@@ -69,24 +79,32 @@ impl Components {
         self.logic.owners.remove(entity);
         self.logic.spawn_protections.remove(entity);
         self.logic.exhaust_emitters.remove(entity);
+        self.logic.healths.remove(entity);
+        self.logic.damages.remove(entity);
+        self.logic.weapons.remove(entity);
+        self.logic.lifetimes.remove(entity);
 
         self.render.bodies.remove(entity);
         self.render.hitboxes.remove(entity);
     }
 }
 
+#[derive(Hash, Debug)]
 pub struct Motion {
     pub velocity: Vec2<Flint>,
     pub speed_maximum: Flint,
+    #[allow(dead_code)] // nothing limits reversing yet
     pub speed_minimum: Flint,
     pub acceleration: Flint,
     pub rotation_speed: Flint,
     pub rotation_speed_maximum: Flint,
     pub rotation_speed_minimum: Flint,
     pub rotation_acceleration: Flint,
+    // fraction of the closing speed kept after bouncing, between 0 and 1
+    pub restitution: Flint,
 }
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Hash, Debug)]
 pub struct Body<T> {
     pub centroid: Vec2<T>,
     pub rotation: Vec2<T>,
@@ -94,30 +112,48 @@ pub struct Body<T> {
     pub color: Color,
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Hash, Debug)]
 pub enum Shape<T> {
     Triangle(Triangle<T>),
     Rectangle(Rectangle<T>),
+    Circle(Circle<T>),
+    Polygon(Polygon<T>),
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Hash, Debug)]
 pub struct Triangle<T> {
     pub top: Vec2<T>,
     pub left: Vec2<T>,
     pub right: Vec2<T>,
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Hash, Debug)]
 pub struct Rectangle<T> {
     pub width: T,
     pub height: T,
 }
 
+#[derive(Copy, Clone, Hash, Debug)]
+pub struct Circle<T> {
+    pub radius: T,
+}
+
+// convex, counterclockwise around the centroid, only the first count are used
+#[derive(Copy, Clone, Hash, Debug)]
+pub struct Polygon<T> {
+    pub vertices: [Vec2<T>; POLYGON_VERTICES_MAXIMUM],
+    pub count: usize,
+}
+
+pub const POLYGON_VERTICES_MAXIMUM: usize = 8;
+
 pub struct ConstantAccelerator;
 
 pub struct CollisionFilter {
     pub category: u32,
     pub mask: u32,
+    // categories bounced off instead of passed through
+    pub solid: u32,
 }
 
 pub struct Owner {
@@ -126,6 +162,79 @@ pub struct Owner {
 
 pub struct SpawnProtection;
 
+#[derive(Hash, Debug)]
+pub struct Health {
+    pub current: Flint,
+    #[allow(dead_code)] // nothing heals yet
+    pub maximum: Flint,
+}
+
+pub struct Damage {
+    pub amount: Flint,
+    // destroyed after hitting something, like projectiles
+    pub expendable: bool,
+}
+
+// ticks left before the entity dies of old age
+#[derive(Hash, Debug)]
+pub struct Lifetime {
+    pub ticks: u32,
+}
+
+#[derive(Hash, Debug)]
+pub struct Weapon {
+    pub kind: WeaponKind,
+    // ticks between two pulls of the trigger
+    pub fire_rate: u32,
+    pub cooldown: u32,
+    pub ammunition: Ammunition,
+    // degrees fanned out by a spread or swept by a burst
+    pub spread: Flint,
+    pub projectile_speed: Flint,
+    // ticks projectiles live for
+    pub projectile_lifetime: u32,
+    pub damage: Flint,
+    // volleys left in the current burst and ticks until the next one
+    pub volleys: u32,
+    pub interval: u32,
+    // a volley of the burst is due and fired by the next shot
+    pub due: bool,
+}
+
+// every weapon carried, each keeps its ammunition and cooldown while holstered
+#[derive(Hash, Debug)]
+pub struct Arsenal {
+    pub weapons: [Weapon; 4],
+    pub current: usize,
+}
+
+#[derive(Debug, Clone, Copy, Hash)]
+pub enum WeaponKind {
+    Single,
+    Spread { count: u32 },
+    Burst { count: u32, interval: u32 },
+    // hits the first thing along the ray instantly
+    Beam { range: Flint },
+}
+
+#[derive(Hash, Debug)]
+pub enum Ammunition {
+    Magazine {
+        capacity: u32,
+        rounds: u32,
+        // ticks to refill an empty magazine
+        reload: u32,
+        reloading: u32,
+    },
+    Energy {
+        capacity: Flint,
+        current: Flint,
+        cost: Flint,
+        // regained every tick
+        regeneration: Flint,
+    },
+}
+
 pub struct ExhaustEmitter {
     pub lifetime_maximum: u32,
     pub lifetime: u32,
@@ -134,6 +243,7 @@ pub struct ExhaustEmitter {
     pub relative_direction: Vec2<Flint>,
 }
 
+#[derive(Hash, Debug)]
 pub struct Particle {
     pub lifetime: u32,
     pub velocity: Vec2<Flint>,
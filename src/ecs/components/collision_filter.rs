@@ -4,8 +4,16 @@ impl CollisionFilter {
     pub const TRIANGLE: u32 = 1 << 0;
     pub const RECTANGLE: u32 = 1 << 1;
     pub const PROJECTILE: u32 = 1 << 2;
+    pub const CIRCLE: u32 = 1 << 3;
+    pub const POLYGON: u32 = 1 << 4;
+    // everything that bumps into each other
+    pub const BODIES: u32 = Self::TRIANGLE | Self::RECTANGLE | Self::CIRCLE | Self::POLYGON;
 
     pub fn is_collidable(&self, other: &CollisionFilter) -> bool {
         (self.mask & other.category) != 0 && (other.mask & self.category) != 0
     }
+
+    pub fn is_solid(&self, other: &CollisionFilter) -> bool {
+        (self.solid & other.category) != 0 && (other.solid & self.category) != 0
+    }
 }
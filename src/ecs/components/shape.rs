@@ -0,0 +1,128 @@
+use korp_math::{ConvexPolygon, Flint, Vec2};
+
+use crate::ecs::components::{POLYGON_VERTICES_MAXIMUM, Polygon, Shape};
+
+impl Shape<Flint> {
+    /// Where projectiles leave the shape, relative to the centroid and facing
+    /// along the x axis.
+    pub fn muzzle(&self) -> Vec2<Flint> {
+        match self {
+            Shape::Triangle(triangle) => triangle.top,
+            Shape::Rectangle(rectangle) => {
+                Vec2::new(rectangle.width * Flint::ZERO_FIVE, rectangle.height)
+            }
+            Shape::Circle(circle) => Vec2::new(circle.radius, Flint::ZERO),
+            // the vertex furthest forward
+            Shape::Polygon(polygon) => polygon
+                .vertices()
+                .iter()
+                .copied()
+                .max_by_key(|vertex| vertex.x)
+                .unwrap_or(Vec2::<Flint>::ZERO),
+        }
+    }
+}
+
+impl<T> Polygon<T> {
+    pub fn vertices(&self) -> &[Vec2<T>] {
+        &self.vertices[..self.count]
+    }
+}
+
+impl Polygon<Flint> {
+    /// Convex hull of the points moved so its centroid is at the origin, none
+    /// when they are all on one line or the hull has too many vertices.
+    pub fn new(points: &[Vec2<Flint>]) -> Option<Self> {
+        let hull = ConvexPolygon::from_points(points)?;
+
+        if hull.vertices().len() > POLYGON_VERTICES_MAXIMUM {
+            return None;
+        }
+
+        let centroid = hull.centroid();
+        let mut vertices = [Vec2::<Flint>::ZERO; POLYGON_VERTICES_MAXIMUM];
+
+        for (vertex, hull) in vertices.iter_mut().zip(hull.vertices()) {
+            *vertex = *hull - centroid;
+        }
+
+        Some(Self {
+            vertices,
+            count: hull.vertices().len(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use korp_math::{ConvexPolygon, Flint, Vec2};
+
+    use crate::ecs::components::{POLYGON_VERTICES_MAXIMUM, Polygon};
+
+    fn point(x: i16, y: i16) -> Vec2<Flint> {
+        Vec2::new(Flint::from_i16(x), Flint::from_i16(y))
+    }
+
+    #[test]
+    fn polygons_are_the_hull_of_their_points() {
+        // a square with a point inside, one on an edge and a repeated corner
+        let points = [
+            point(0, 0),
+            point(4, 4),
+            point(2, 1),
+            point(4, 0),
+            point(2, 0),
+            point(0, 4),
+            point(4, 4),
+        ];
+        let polygon = Polygon::new(&points).unwrap();
+
+        // counterclockwise from the lowest x, like the hull
+        let square = [point(-2, -2), point(2, -2), point(2, 2), point(-2, 2)];
+
+        assert_eq!(polygon.count, 4);
+        assert_eq!(polygon.vertices(), square);
+        assert!(
+            polygon.vertices[4..]
+                .iter()
+                .all(|vertex| *vertex == Vec2::<Flint>::ZERO)
+        );
+    }
+
+    #[test]
+    fn polygons_are_centred_on_their_centroid() {
+        let points = [point(10, 10), point(16, 10), point(10, 13)];
+        let polygon = Polygon::new(&points).unwrap();
+        let centroid = ConvexPolygon::new(polygon.vertices()).unwrap().centroid();
+
+        assert_eq!(
+            polygon.vertices(),
+            [point(-2, -1), point(4, -1), point(-2, 2)]
+        );
+        assert_eq!(centroid, Vec2::<Flint>::ZERO);
+    }
+
+    #[test]
+    fn polygons_need_an_area_and_few_enough_vertices() {
+        let circle = |count: usize| {
+            (0..count)
+                .map(|i| {
+                    let angle =
+                        Flint::TAU * Flint::from_i16(i as i16) / Flint::from_i16(count as i16);
+
+                    Vec2::<Flint>::from_angle(angle) * Flint::from_i16(10)
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            Polygon::new(&circle(POLYGON_VERTICES_MAXIMUM))
+                .unwrap()
+                .count,
+            POLYGON_VERTICES_MAXIMUM
+        );
+        assert!(Polygon::new(&circle(POLYGON_VERTICES_MAXIMUM + 1)).is_none());
+        assert!(Polygon::new(&[point(0, 0), point(1, 1), point(3, 3)]).is_none());
+        assert!(Polygon::new(&[point(0, 0), point(1, 1)]).is_none());
+    }
+}
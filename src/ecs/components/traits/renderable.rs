@@ -1,11 +1,11 @@
 use korp_engine::{
     misc::Morph,
     renderer::Renderer,
-    shapes::{Rectangle, Triangle},
+    shapes::{Circle, Rectangle, Triangle},
 };
 use korp_math::{Vec2, lerp, lerp_angle};
 
-use crate::ecs::components::{Body, Shape};
+use crate::ecs::components::{self, Body, Polygon, Shape};
 
 pub trait Renderable {
     fn render(&self, renderer: &mut Renderer, toggle: bool, alpha: f32);
@@ -13,38 +13,20 @@ pub trait Renderable {
 
 impl Renderable for Morph<Body<f32>> {
     fn render(&self, renderer: &mut Renderer, draw_filled: bool, alpha: f32) {
-        let rotation = Vec2::from_angle(lerp_angle(
+        let rotation = Vec2::<f32>::from_angle(lerp_angle(
             self.old.rotation.angle(),
             self.new.rotation.angle(),
             alpha,
         ));
 
-        let centroid = Vec2::new(
-            lerp(self.old.centroid.x, self.new.centroid.x, alpha),
-            lerp(self.old.centroid.y, self.new.centroid.y, alpha),
-        );
+        let centroid = self.old.centroid.lerp(&self.new.centroid, alpha);
 
         // TODO: lerp color?
         let color = self.new.color;
 
-        match (self.old.shape, self.new.shape) {
-            (Shape::Triangle(old), Shape::Triangle(new)) => {
-                let top = Vec2::new(
-                    lerp(old.top.x, new.top.x, alpha),
-                    lerp(old.top.y, new.top.y, alpha),
-                );
-
-                let left = Vec2::new(
-                    lerp(old.left.x, new.left.x, alpha),
-                    lerp(old.left.y, new.left.y, alpha),
-                );
-
-                let right = Vec2::new(
-                    lerp(old.right.x, new.right.x, alpha),
-                    lerp(old.right.y, new.right.y, alpha),
-                );
-
-                let shape = Triangle::from(top, left, right, centroid);
+        match morph_shape(self.old.shape, self.new.shape, alpha) {
+            Shape::Triangle(triangle) => {
+                let shape = Triangle::from(triangle.top, triangle.left, triangle.right, centroid);
 
                 if draw_filled {
                     renderer.draw_triangle_filled(shape, rotation, centroid, color);
@@ -52,10 +34,8 @@ impl Renderable for Morph<Body<f32>> {
                     renderer.draw_triangle_lines(shape, rotation, centroid, color);
                 }
             }
-            (Shape::Rectangle(old), Shape::Rectangle(new)) => {
-                let width = lerp(old.width, new.width, alpha);
-                let height = lerp(old.height, new.height, alpha);
-                let shape = Rectangle::from(width, height, centroid);
+            Shape::Rectangle(rectangle) => {
+                let shape = Rectangle::from(rectangle.width, rectangle.height, centroid);
 
                 if draw_filled {
                     renderer.draw_rectangle_filled(shape, rotation, centroid, color);
@@ -63,8 +43,151 @@ impl Renderable for Morph<Body<f32>> {
                     renderer.draw_rectangle_lines(shape, rotation, centroid, color);
                 }
             }
-            // TODO: can't currently morph between different shapes, draw old or new?
-            _ => panic!("wtf drawable shapes"),
+            Shape::Circle(circle) => {
+                let shape = Circle::from(circle.radius, centroid);
+
+                if draw_filled {
+                    renderer.draw_circle_filled(shape, rotation, centroid, color);
+                } else {
+                    renderer.draw_circle_lines(shape, rotation, centroid, color);
+                }
+            }
+            Shape::Polygon(polygon) => {
+                let corners = polygon
+                    .vertices()
+                    .iter()
+                    .map(|vertex| centroid + *vertex)
+                    .collect::<Vec<_>>();
+
+                if draw_filled {
+                    renderer.draw_polygon_filled(&corners, rotation, centroid, color);
+                } else {
+                    renderer.draw_polygon_lines(&corners, rotation, centroid, color);
+                }
+            }
+        }
+    }
+}
+
+// shapes of different kinds can't be blended, the new one is drawn right away
+fn morph_shape(old: Shape<f32>, new: Shape<f32>, alpha: f32) -> Shape<f32> {
+    match (old, new) {
+        (Shape::Triangle(old), Shape::Triangle(new)) => Shape::Triangle(components::Triangle {
+            top: old.top.lerp(&new.top, alpha),
+            left: old.left.lerp(&new.left, alpha),
+            right: old.right.lerp(&new.right, alpha),
+        }),
+        (Shape::Rectangle(old), Shape::Rectangle(new)) => Shape::Rectangle(components::Rectangle {
+            width: lerp(old.width, new.width, alpha),
+            height: lerp(old.height, new.height, alpha),
+        }),
+        (Shape::Circle(old), Shape::Circle(new)) => Shape::Circle(components::Circle {
+            radius: lerp(old.radius, new.radius, alpha),
+        }),
+        (Shape::Polygon(old), Shape::Polygon(new)) if old.count == new.count => {
+            let mut vertices = new.vertices;
+
+            for (vertex, old) in vertices.iter_mut().zip(old.vertices) {
+                *vertex = old.lerp(vertex, alpha);
+            }
+
+            Shape::Polygon(Polygon { vertices, ..new })
+        }
+        (_, new) => new,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use korp_engine::{
+        color::Color,
+        renderer::{Recorder, RenderBatch},
+    };
+
+    use super::*;
+
+    fn body(centroid: Vec2<f32>, rotation: Vec2<f32>, shape: Shape<f32>) -> Body<f32> {
+        Body {
+            centroid,
+            rotation,
+            shape,
+            color: Color::RED,
+        }
+    }
+
+    fn triangle() -> Shape<f32> {
+        Shape::Triangle(components::Triangle {
+            top: Vec2::new(0.0, -2.0),
+            left: Vec2::new(-1.0, 1.0),
+            right: Vec2::new(1.0, 1.0),
+        })
+    }
+
+    #[test]
+    fn bodies_are_drawn_between_ticks() {
+        let right = Vec2::new(1.0, 0.0);
+        let down = Vec2::new(0.0, 1.0);
+        let morph = Morph::new(
+            body(Vec2::new(0.0, 0.0), right, triangle()),
+            body(Vec2::new(10.0, 20.0), down, triangle()),
+        );
+        let mut recorder = Recorder::new(800, 600);
+
+        morph.render(&mut recorder.begin(), true, 0.5);
+
+        let frame = recorder.frame().unwrap();
+        let vertices = frame.vertices();
+        let positions = vertices
+            .iter()
+            .map(|vertex| vertex.position)
+            .collect::<Vec<_>>();
+
+        // halfway in place, the rotation is applied around the origin later
+        assert_eq!(positions, [[5.0, 8.0], [4.0, 11.0], [6.0, 11.0]]);
+
+        let half = std::f32::consts::FRAC_1_SQRT_2;
+
+        for vertex in vertices {
+            assert_eq!(vertex.origin, [5.0, 10.0]);
+            assert_eq!(vertex.color, u32::from(Color::RED));
+            assert!((vertex.rotation[0] - half).abs() < 1e-6, "{:?}", vertex);
+            assert!((vertex.rotation[1] - half).abs() < 1e-6, "{:?}", vertex);
+        }
+
+        assert_eq!(
+            frame.batches(),
+            [RenderBatch {
+                start: 0,
+                end: 3,
+                view_projection_idx: 0,
+            }]
+        );
+    }
+
+    #[test]
+    fn bodies_changing_shape_are_drawn_with_the_new_one() {
+        let right = Vec2::new(1.0, 0.0);
+        let circle = Shape::Circle(components::Circle { radius: 4.0 });
+        let morph = Morph::new(
+            body(Vec2::new(0.0, 0.0), right, triangle()),
+            body(Vec2::new(0.0, 0.0), right, circle),
+        );
+        let mut recorder = Recorder::new(800, 600);
+
+        morph.render(&mut recorder.begin(), true, 0.25);
+
+        let vertices = recorder.frame().unwrap().vertices();
+
+        // a fan of 30 triangles, every outer vertex on the circle
+        assert_eq!(vertices.len(), 90);
+
+        for vertex in vertices
+            .iter()
+            .filter(|vertex| vertex.position != [0.0, 0.0])
+        {
+            let [x, y] = vertex.position;
+
+            assert!((Vec2::new(x, y).len() - 4.0).abs() < 1e-5, "{:?}", vertex);
         }
     }
 }
@@ -1,7 +1,7 @@
 use korp_engine::misc::Morph;
 use korp_math::Flint;
 
-use crate::ecs::components::{Body, Rectangle, Shape, Triangle};
+use crate::ecs::components::{Body, Circle, Polygon, Rectangle, Shape, Triangle};
 
 pub trait Transformable<T> {
     fn transform(&self) -> T;
@@ -38,6 +38,13 @@ impl Transformable<Shape<f32>> for Shape<Flint> {
                 width: rectangle.width.into(),
                 height: rectangle.height.into(),
             }),
+            Shape::Circle(circle) => Shape::Circle(Circle {
+                radius: circle.radius.into(),
+            }),
+            Shape::Polygon(polygon) => Shape::Polygon(Polygon {
+                vertices: polygon.vertices.map(Into::into),
+                count: polygon.count,
+            }),
         }
     }
 }
@@ -1,18 +1,23 @@
-use korp_math::{Flint, Vec2};
+use korp_math::{Flint, Transform2, Vec2};
 
 use crate::ecs::components::{Body, Shape};
 
+// edges of the polygon standing in for a circle
+const CIRCLE_SEGMENTS: i16 = 16;
+
 pub trait Vertexable {
     fn vertices(&self) -> Vec<Vec2<Flint>>;
 }
 
 impl Vertexable for Body<Flint> {
     fn vertices(&self) -> Vec<Vec2<Flint>> {
+        let transform = Transform2::<Flint>::new(self.centroid, self.rotation);
+
         match self.shape {
             Shape::Triangle(triangle) => {
-                let t = self.centroid + triangle.top.rotated_v(self.rotation);
-                let l = self.centroid + triangle.left.rotated_v(self.rotation);
-                let r = self.centroid + triangle.right.rotated_v(self.rotation);
+                let t = transform.apply(triangle.top);
+                let l = transform.apply(triangle.left);
+                let r = transform.apply(triangle.right);
 
                 vec![t, l, r]
             }
@@ -20,13 +25,32 @@ impl Vertexable for Body<Flint> {
                 let w = rectangle.width * Flint::ZERO_FIVE;
                 let h = rectangle.height * Flint::ZERO_FIVE;
 
-                let tl = self.centroid + Vec2::new(-w, -h).rotated_v(self.rotation);
-                let tr = self.centroid + Vec2::new(w, -h).rotated_v(self.rotation);
-                let bl = self.centroid + Vec2::new(-w, h).rotated_v(self.rotation);
-                let br = self.centroid + Vec2::new(w, h).rotated_v(self.rotation);
+                let tl = transform.apply(Vec2::new(-w, -h));
+                let tr = transform.apply(Vec2::new(w, -h));
+                let bl = transform.apply(Vec2::new(-w, h));
+                let br = transform.apply(Vec2::new(w, h));
+
+                // in order around the edges
+                vec![tl, tr, br, bl]
+            }
+            // circumscribed so the hitbox covers the whole circle
+            Shape::Circle(circle) => {
+                let step = Flint::PI / Flint::from_i16(CIRCLE_SEGMENTS / 2);
+                let (_, cos) = (step * Flint::ZERO_FIVE).sin_cos();
+                let radius = circle.radius / cos;
 
-                vec![tl, tr, bl, br]
+                (0..CIRCLE_SEGMENTS)
+                    .map(|i| {
+                        let direction = Vec2::<Flint>::from_angle(step * Flint::from_i16(i));
+                        transform.apply(direction * radius)
+                    })
+                    .collect()
             }
+            Shape::Polygon(polygon) => polygon
+                .vertices()
+                .iter()
+                .map(|vertex| transform.apply(*vertex))
+                .collect(),
         }
     }
 }
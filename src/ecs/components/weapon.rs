@@ -0,0 +1,416 @@
+use korp_math::{Flint, Vec2};
+
+use crate::ecs::components::{Ammunition, Arsenal, Weapon, WeaponKind};
+
+// ticks before a weapon can fire after switching to it
+const SWITCH_DELAY: u32 = 30;
+
+impl Weapon {
+    pub fn cannon() -> Self {
+        Self::new(
+            WeaponKind::Single,
+            8,
+            Ammunition::magazine(12, 90),
+            Flint::ZERO,
+            Flint::from_i16(16),
+            45,
+            Flint::from_i16(25),
+        )
+    }
+
+    pub fn shotgun() -> Self {
+        Self::new(
+            WeaponKind::Spread { count: 5 },
+            30,
+            Ammunition::magazine(4, 120),
+            Flint::from_i16(30),
+            Flint::from_i16(14),
+            20,
+            Flint::from_i16(12),
+        )
+    }
+
+    pub fn burst() -> Self {
+        Self::new(
+            WeaponKind::Burst {
+                count: 3,
+                interval: 4,
+            },
+            24,
+            Ammunition::magazine(8, 120),
+            Flint::from_i16(6),
+            Flint::from_i16(18),
+            40,
+            Flint::from_i16(15),
+        )
+    }
+
+    pub fn beam() -> Self {
+        Self::new(
+            WeaponKind::Beam {
+                range: Flint::from_i16(500),
+            },
+            1,
+            Ammunition::Energy {
+                capacity: Flint::from_i16(100),
+                current: Flint::from_i16(100),
+                cost: Flint::from_i16(2),
+                regeneration: Flint::ZERO_FIVE,
+            },
+            Flint::ZERO,
+            Flint::ZERO,
+            0,
+            Flint::from_i16(2),
+        )
+    }
+
+    fn new(
+        kind: WeaponKind,
+        fire_rate: u32,
+        ammunition: Ammunition,
+        spread: Flint,
+        projectile_speed: Flint,
+        projectile_lifetime: u32,
+        damage: Flint,
+    ) -> Self {
+        Self {
+            kind,
+            fire_rate,
+            cooldown: 0,
+            ammunition,
+            spread,
+            projectile_speed,
+            projectile_lifetime,
+            damage,
+            volleys: 0,
+            interval: 0,
+            due: false,
+        }
+    }
+
+    /// Pulls the trigger, spending ammunition and starting a burst when it fires.
+    pub fn trigger(&mut self) -> bool {
+        if self.cooldown > 0 || self.volleys > 0 {
+            return false;
+        }
+
+        if !self.ammunition.spend() {
+            return false;
+        }
+
+        self.cooldown = self.fire_rate;
+
+        // the first volley is fired right away
+        if let WeaponKind::Burst { count, interval } = self.kind {
+            self.volleys = count.saturating_sub(1);
+            self.interval = interval;
+        }
+
+        true
+    }
+
+    /// Takes the volley of a burst that is due, if any.
+    pub fn volley(&mut self) -> bool {
+        std::mem::take(&mut self.due)
+    }
+
+    /// Advances cooldowns and ammunition, tells whether the next volley of a burst is due.
+    pub fn update(&mut self) -> bool {
+        self.cooldown = self.cooldown.saturating_sub(1);
+        self.ammunition.update();
+
+        if self.volleys == 0 {
+            return false;
+        }
+
+        self.interval = self.interval.saturating_sub(1);
+
+        if self.interval > 0 {
+            return false;
+        }
+
+        self.volleys -= 1;
+        self.due = true;
+
+        if let WeaponKind::Burst { interval, .. } = self.kind {
+            self.interval = interval;
+        }
+
+        true
+    }
+
+    // a burst in progress is abandoned with the weapon
+    fn holster(&mut self) {
+        self.volleys = 0;
+        self.due = false;
+    }
+
+    /// Directions of the projectiles of the current volley.
+    pub fn directions(&self, rotation: Vec2<Flint>) -> Vec<Vec2<Flint>> {
+        match self.kind {
+            WeaponKind::Single | WeaponKind::Beam { .. } => vec![rotation],
+            WeaponKind::Spread { count } => (0..count)
+                .map(|i| rotation.rotated(self.fan(i, count)))
+                .collect(),
+            WeaponKind::Burst { count, .. } => {
+                // the volley being fired, bursts sweep across the spread
+                let i = count.saturating_sub(1) - self.volleys.min(count.saturating_sub(1));
+
+                vec![rotation.rotated(self.fan(i, count))]
+            }
+        }
+    }
+
+    // evenly spaced degrees over the spread, centered on zero
+    fn fan(&self, i: u32, count: u32) -> Flint {
+        if count < 2 {
+            return Flint::ZERO;
+        }
+
+        let half = self.spread * Flint::ZERO_FIVE;
+
+        self.spread * Flint::from_i16(i as i16) / Flint::from_i16(count as i16 - 1) - half
+    }
+}
+
+impl Arsenal {
+    pub const CANNON: usize = 0;
+    pub const SHOTGUN: usize = 1;
+
+    /// Every weapon, with the given one drawn and ready to fire.
+    pub fn new(current: usize) -> Self {
+        Self {
+            weapons: [
+                Weapon::cannon(),
+                Weapon::shotgun(),
+                Weapon::burst(),
+                Weapon::beam(),
+            ],
+            current,
+        }
+    }
+
+    pub fn current(&self) -> &Weapon {
+        &self.weapons[self.current]
+    }
+
+    pub fn current_mut(&mut self) -> &mut Weapon {
+        &mut self.weapons[self.current]
+    }
+
+    /// Draws the next weapon, not ready to fire right away.
+    pub fn cycle(&mut self) {
+        self.current_mut().holster();
+        self.current = (self.current + 1) % self.weapons.len();
+
+        let weapon = self.current_mut();
+        weapon.cooldown = weapon.cooldown.max(SWITCH_DELAY);
+    }
+
+    /// Advances every weapon, holstered ones keep reloading, tells whether
+    /// the next volley of the drawn one is due.
+    pub fn update(&mut self) -> bool {
+        let mut due = false;
+
+        for (idx, weapon) in self.weapons.iter_mut().enumerate() {
+            let volley = weapon.update();
+            due |= volley && idx == self.current;
+        }
+
+        due
+    }
+}
+
+impl Ammunition {
+    pub fn magazine(capacity: u32, reload: u32) -> Self {
+        Ammunition::Magazine {
+            capacity,
+            rounds: capacity,
+            reload,
+            reloading: 0,
+        }
+    }
+
+    fn spend(&mut self) -> bool {
+        match self {
+            Ammunition::Magazine {
+                rounds,
+                reload,
+                reloading,
+                ..
+            } => {
+                if *reloading > 0 || *rounds == 0 {
+                    return false;
+                }
+
+                *rounds -= 1;
+
+                if *rounds == 0 {
+                    *reloading = *reload;
+                }
+
+                true
+            }
+            Ammunition::Energy { current, cost, .. } => {
+                if *current < *cost {
+                    return false;
+                }
+
+                *current -= *cost;
+
+                true
+            }
+        }
+    }
+
+    fn update(&mut self) {
+        match self {
+            Ammunition::Magazine {
+                capacity,
+                rounds,
+                reloading,
+                ..
+            } => {
+                if *reloading == 0 {
+                    return;
+                }
+
+                *reloading -= 1;
+
+                if *reloading == 0 {
+                    *rounds = *capacity;
+                }
+            }
+            Ammunition::Energy {
+                capacity,
+                current,
+                regeneration,
+                ..
+            } => {
+                *current = (*current + *regeneration).min(*capacity);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // slot of the burst weapon in every arsenal
+    const BURST: usize = 2;
+
+    fn rounds(weapon: &Weapon) -> (u32, u32) {
+        match weapon.ammunition {
+            Ammunition::Magazine {
+                rounds, reloading, ..
+            } => (rounds, reloading),
+            Ammunition::Energy { .. } => panic!("not a magazine"),
+        }
+    }
+
+    // pulls the trigger whenever the weapon is ready until the magazine is empty
+    fn empty(arsenal: &mut Arsenal) {
+        while rounds(arsenal.current()).0 > 0 {
+            arsenal.current_mut().trigger();
+            arsenal.update();
+        }
+    }
+
+    #[test]
+    fn cycling_does_not_reload() {
+        let mut arsenal = Arsenal::new(Arsenal::CANNON);
+        empty(&mut arsenal);
+
+        let (_, reloading) = rounds(arsenal.current());
+        assert!(reloading > 0);
+
+        for _ in 0..arsenal.weapons.len() {
+            arsenal.cycle();
+            arsenal.update();
+        }
+
+        assert_eq!(arsenal.current, Arsenal::CANNON);
+        assert_eq!(rounds(arsenal.current()), (0, reloading - 4));
+        assert!(!arsenal.current_mut().trigger());
+    }
+
+    #[test]
+    fn holstered_weapons_keep_reloading() {
+        let mut arsenal = Arsenal::new(Arsenal::SHOTGUN);
+        empty(&mut arsenal);
+        arsenal.cycle();
+
+        while rounds(&arsenal.weapons[Arsenal::SHOTGUN]).1 > 0 {
+            arsenal.update();
+        }
+
+        assert_eq!(rounds(&arsenal.weapons[Arsenal::SHOTGUN]), (4, 0));
+    }
+
+    #[test]
+    fn drawn_weapons_wait_before_firing() {
+        let mut arsenal = Arsenal::new(Arsenal::CANNON);
+        arsenal.cycle();
+
+        assert_eq!(arsenal.current, Arsenal::SHOTGUN);
+        assert!(!arsenal.current_mut().trigger());
+
+        for _ in 0..SWITCH_DELAY {
+            arsenal.update();
+        }
+        assert!(arsenal.current_mut().trigger());
+    }
+
+    #[test]
+    fn cycling_does_not_skip_the_cooldown() {
+        let mut arsenal = Arsenal::new(Arsenal::CANNON);
+        let fire_rate = arsenal.current().fire_rate;
+
+        assert!(arsenal.current_mut().trigger());
+
+        // drawing it again never shortens what is left of its cooldown
+        for _ in 0..arsenal.weapons.len() {
+            arsenal.cycle();
+        }
+        assert_eq!(arsenal.current().cooldown, fire_rate.max(SWITCH_DELAY));
+    }
+
+    #[test]
+    fn bursts_fire_their_volleys_once_each() {
+        let mut arsenal = Arsenal::new(BURST);
+        let WeaponKind::Burst { count, interval } = arsenal.current().kind else {
+            panic!("not a burst");
+        };
+
+        assert!(arsenal.current_mut().trigger());
+
+        let mut volleys = 0;
+
+        for _ in 0..count * interval {
+            if arsenal.update() {
+                // the trigger stays locked while the volley is taken once
+                assert!(!arsenal.current_mut().trigger());
+                assert!(arsenal.current_mut().volley());
+                assert!(!arsenal.current_mut().volley());
+                volleys += 1;
+            }
+        }
+
+        assert_eq!(volleys, count - 1);
+    }
+
+    #[test]
+    fn holstering_abandons_a_burst() {
+        let mut arsenal = Arsenal::new(BURST);
+        assert!(arsenal.current_mut().trigger());
+        arsenal.cycle();
+
+        for _ in 0..SWITCH_DELAY {
+            assert!(!arsenal.update());
+        }
+
+        assert_eq!(arsenal.weapons[BURST].volleys, 0);
+        assert!(!arsenal.weapons[BURST].volley());
+    }
+}
@@ -4,9 +4,10 @@ use korp_math::{Flint, Random};
 use crate::{
     bus::{
         Bus,
-        events::{CosmosEvent, CosmosIntent, Event, IntentEvent},
+        events::{CosmosEvent, CosmosIntent, Event, IntentEvent, NetworkIntent},
     },
     ecs::{
+        checksum::{CHECKSUM_INTERVAL, Checksum, Dump},
         commands::Command,
         components::Components,
         forge::Forge,
@@ -19,9 +20,12 @@ use crate::{
 #[derive(Debug, Clone)]
 pub enum Configure {
     Toggle(Toggle),
+    // keep a textual dump of the state next to every checksum
+    Dump(bool),
 }
 
 #[derive(Debug, Clone)]
+#[allow(clippy::enum_variant_names)]
 pub enum Toggle {
     DrawFilled,
     DrawQuadtree,
@@ -46,6 +50,7 @@ pub struct Configuration {
     pub draw_filled: bool,
     pub draw_quadtree: bool,
     pub draw_hitbox: bool,
+    pub dump: bool,
 }
 
 impl Cosmos {
@@ -65,11 +70,18 @@ impl Cosmos {
                 draw_filled: false,
                 draw_quadtree: false,
                 draw_hitbox: false,
+                dump: false,
             },
         }
     }
 
-    pub fn update(&mut self, bus: &mut Bus, random: &mut Random, commands: &[Vec<Command>]) {
+    pub fn update(
+        &mut self,
+        bus: &mut Bus,
+        random: &mut Random,
+        tick: usize,
+        commands: &[Vec<Command>],
+    ) {
         self.execute_commands(commands);
 
         self.executor.execute(
@@ -90,6 +102,21 @@ impl Cosmos {
         );
 
         self.tracker.update(&self.components, bus);
+
+        if tick.is_multiple_of(CHECKSUM_INTERVAL) {
+            let entities = self.forge.entities();
+            let checksum = Checksum::take(&self.components, entities, random);
+            let dump = self
+                .configuration
+                .dump
+                .then(|| Dump::take(&self.components, entities, random));
+
+            bus.send(NetworkIntent::Checksum {
+                tick,
+                checksum,
+                dump,
+            });
+        }
     }
 
     pub fn render(&self, renderer: &mut Renderer, alpha: f32) {
@@ -115,6 +142,10 @@ impl Cosmos {
         }
     }
 
+    pub fn bounds(&self) -> Rectangle<Flint> {
+        self.bounds
+    }
+
     pub fn components(&self) -> &Components {
         &self.components
     }
@@ -138,6 +169,7 @@ impl Configuration {
                 Toggle::DrawQuadtree => self.draw_quadtree = !self.draw_quadtree,
                 Toggle::DrawHitbox => self.draw_hitbox = !self.draw_hitbox,
             },
+            Configure::Dump(dump) => self.dump = *dump,
         }
     }
 }
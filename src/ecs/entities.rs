@@ -1,4 +1,4 @@
-#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub struct Entity {
     pub index: u32,
     pub generation: u32,
@@ -33,10 +33,25 @@ impl EntityFactory {
         }
     }
 
+    pub fn generations(&self) -> &[u32] {
+        &self.generations
+    }
+
+    pub fn free(&self) -> &[u32] {
+        &self.free
+    }
+
+    pub fn is_alive(&self, entity: Entity) -> bool {
+        self.generations.get(entity.index as usize) == Some(&entity.generation)
+    }
+
     pub fn destroy(&mut self, entity: Entity) {
-        if let Some(generation) = self.generations.get_mut(entity.index as usize) {
-            *generation += 1;
-            self.free.push(entity.index);
+        // destroying twice would hand out the same index twice
+        if !self.is_alive(entity) {
+            return;
         }
+
+        self.generations[entity.index as usize] += 1;
+        self.free.push(entity.index);
     }
 }
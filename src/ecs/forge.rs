@@ -3,8 +3,9 @@ use korp_math::{Flint, Vec2};
 
 use crate::ecs::{
     components::{
-        Body, CollisionFilter, Components, ConstantAccelerator, ExhaustEmitter, Motion, Owner,
-        Particle, Rectangle, Shape, SpawnProtection, Triangle,
+        Arsenal, Body, Circle, CollisionFilter, Components, ConstantAccelerator, Damage,
+        ExhaustEmitter, Health, Lifetime, Motion, Owner, Particle, Polygon, Rectangle, Shape,
+        SpawnProtection, Triangle,
     },
     entities::{Entity, EntityFactory},
     systems::COSMIC_DRAG,
@@ -21,6 +22,10 @@ impl Forge {
         }
     }
 
+    pub fn entities(&self) -> &EntityFactory {
+        &self.factory
+    }
+
     pub fn destroy(&mut self, entity: Entity, components: &mut Components) {
         self.factory.destroy(entity);
         components.destroy(entity);
@@ -52,14 +57,15 @@ impl Forge {
         components.logic.motions.insert(
             entity,
             Motion {
-                velocity: Vec2::ZERO,
+                velocity: Vec2::<Flint>::ZERO,
                 speed_maximum: Flint::new(15, 0),
                 speed_minimum: -Flint::new(10, 0),
-                acceleration: Flint::new(1, Flint::POINT_ONE * 3),
+                acceleration: Flint::from_f64(1.3),
                 rotation_speed: Flint::ZERO,
                 rotation_speed_maximum: Flint::new(16, 0),
                 rotation_speed_minimum: -Flint::new(16, 0),
                 rotation_acceleration: Flint::new(1, 0),
+                restitution: Flint::ZERO_FIVE,
             },
         );
 
@@ -67,12 +73,24 @@ impl Forge {
             entity,
             CollisionFilter {
                 category: CollisionFilter::TRIANGLE,
-                mask: CollisionFilter::PROJECTILE
-                    | CollisionFilter::TRIANGLE
-                    | CollisionFilter::RECTANGLE,
+                mask: CollisionFilter::PROJECTILE | CollisionFilter::BODIES,
+                solid: CollisionFilter::BODIES,
+            },
+        );
+
+        components.logic.healths.insert(
+            entity,
+            Health {
+                current: Flint::from_i16(100),
+                maximum: Flint::from_i16(100),
             },
         );
 
+        components
+            .logic
+            .weapons
+            .insert(entity, Arsenal::new(Arsenal::CANNON));
+
         components.logic.exhaust_emitters.insert(
             entity,
             ExhaustEmitter {
@@ -113,14 +131,15 @@ impl Forge {
         components.logic.motions.insert(
             entity,
             Motion {
-                velocity: Vec2::ZERO,
+                velocity: Vec2::<Flint>::ZERO,
                 speed_maximum: Flint::new(15, 0),
                 speed_minimum: -Flint::new(10, 0),
-                acceleration: Flint::new(1, Flint::POINT_ONE * 3),
+                acceleration: Flint::from_f64(1.3),
                 rotation_speed: Flint::ZERO,
                 rotation_speed_maximum: Flint::new(16, 0),
                 rotation_speed_minimum: -Flint::new(16, 0),
                 rotation_acceleration: Flint::new(1, 0),
+                restitution: Flint::from_f64(0.3),
             },
         );
 
@@ -128,19 +147,74 @@ impl Forge {
             entity,
             CollisionFilter {
                 category: CollisionFilter::RECTANGLE,
-                mask: CollisionFilter::PROJECTILE
-                    | CollisionFilter::TRIANGLE
-                    | CollisionFilter::RECTANGLE,
+                mask: CollisionFilter::PROJECTILE | CollisionFilter::BODIES,
+                solid: CollisionFilter::BODIES,
             },
         );
 
+        components.logic.healths.insert(
+            entity,
+            Health {
+                current: Flint::from_i16(150),
+                maximum: Flint::from_i16(150),
+            },
+        );
+
+        components
+            .logic
+            .weapons
+            .insert(entity, Arsenal::new(Arsenal::SHOTGUN));
+
         entity
     }
 
+    pub fn circle(&mut self, centroid: Vec2<Flint>, components: &mut Components) -> Entity {
+        let entity = self.factory.create();
+
+        let body = Body {
+            centroid,
+            rotation: Vec2::new(Flint::ZERO, Flint::NEG_ONE),
+            shape: Shape::Circle(Circle {
+                radius: Flint::new(25, 0),
+            }),
+            color: Color::GREEN,
+        };
+
+        self.obstacle(entity, body, CollisionFilter::CIRCLE, components);
+
+        entity
+    }
+
+    /// None when the outline has no area or its hull has more than
+    /// `POLYGON_VERTICES_MAXIMUM` vertices.
+    pub fn polygon(
+        &mut self,
+        centroid: Vec2<Flint>,
+        outline: &[Vec2<Flint>],
+        components: &mut Components,
+    ) -> Option<Entity> {
+        let polygon = Polygon::new(outline)?;
+        let entity = self.factory.create();
+
+        let body = Body {
+            centroid,
+            rotation: Vec2::new(Flint::ONE, Flint::ZERO),
+            shape: Shape::Polygon(polygon),
+            color: Color::GREEN,
+        };
+
+        self.obstacle(entity, body, CollisionFilter::POLYGON, components);
+
+        Some(entity)
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn projectile(
         &mut self,
         owner: Entity,
-        relative_speed: Flint,
+        speed: Flint,
+        damage: Flint,
+        lifetime: u32,
         centroid: Vec2<Flint>,
         rotation: Vec2<Flint>,
         components: &mut Components,
@@ -159,7 +233,7 @@ impl Forge {
 
         components.logic.bodies.insert(entity, Morph::one(body));
 
-        let velocity = rotation * Flint::new(16, 0) + rotation * relative_speed;
+        let velocity = rotation * speed;
 
         components.logic.motions.insert(
             entity,
@@ -172,6 +246,7 @@ impl Forge {
                 rotation_speed_maximum: Flint::ZERO,
                 rotation_speed_minimum: Flint::ZERO,
                 rotation_acceleration: Flint::ZERO,
+                restitution: Flint::ZERO,
             },
         );
 
@@ -184,10 +259,32 @@ impl Forge {
             entity,
             CollisionFilter {
                 category: CollisionFilter::PROJECTILE,
-                mask: CollisionFilter::TRIANGLE | CollisionFilter::RECTANGLE,
+                mask: CollisionFilter::BODIES,
+                solid: 0,
+            },
+        );
+
+        components.logic.healths.insert(
+            entity,
+            Health {
+                current: Flint::ONE,
+                maximum: Flint::ONE,
+            },
+        );
+
+        components.logic.damages.insert(
+            entity,
+            Damage {
+                amount: damage,
+                expendable: true,
             },
         );
 
+        components
+            .logic
+            .lifetimes
+            .insert(entity, Lifetime { ticks: lifetime });
+
         components
             .logic
             .owners
@@ -223,4 +320,47 @@ impl Forge {
             }),
         });
     }
+
+    // heavy, slow and unarmed, only pushed around by what hits it
+    fn obstacle(
+        &mut self,
+        entity: Entity,
+        body: Body<Flint>,
+        category: u32,
+        components: &mut Components,
+    ) {
+        components.logic.bodies.insert(entity, Morph::one(body));
+
+        components.logic.motions.insert(
+            entity,
+            Motion {
+                velocity: Vec2::<Flint>::ZERO,
+                speed_maximum: Flint::new(5, 0),
+                speed_minimum: Flint::ZERO,
+                acceleration: Flint::ZERO,
+                rotation_speed: Flint::ZERO,
+                rotation_speed_maximum: Flint::ZERO,
+                rotation_speed_minimum: Flint::ZERO,
+                rotation_acceleration: Flint::ZERO,
+                restitution: Flint::from_f64(0.8),
+            },
+        );
+
+        components.logic.collision_filters.insert(
+            entity,
+            CollisionFilter {
+                category,
+                mask: CollisionFilter::PROJECTILE | CollisionFilter::BODIES,
+                solid: CollisionFilter::BODIES,
+            },
+        );
+
+        components.logic.healths.insert(
+            entity,
+            Health {
+                current: Flint::from_i16(300),
+                maximum: Flint::from_i16(300),
+            },
+        );
+    }
 }
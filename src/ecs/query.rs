@@ -0,0 +1,313 @@
+use crate::ecs::{entities::Entity, sparse_set::SparseSet};
+
+/// A set a query joins on, `&SparseSet` yields shared and `&mut SparseSet`
+/// mutable components. Tuples of them join several sets, the borrow checker
+/// keeps the mutable ones disjoint.
+pub trait Fetch {
+    type Item<'a>
+    where
+        Self: 'a;
+
+    /// Entities a query driven by this set visits, none when it can't drive.
+    fn entities(&self) -> Option<&[Entity]>;
+
+    fn matches(&self, entity: &Entity) -> bool;
+
+    fn fetch(&mut self, entity: &Entity) -> Option<Self::Item<'_>>;
+}
+
+/// Joins on a set without requiring it, yielding none when it's missing.
+pub struct Optional<F>(pub F);
+
+/// Anything an entity can be excluded by.
+pub trait Contains {
+    fn contains(&self, entity: &Entity) -> bool;
+}
+
+/// Entities having every component of the fetched sets and none of the
+/// excluded ones, visited in the order of the smallest required set.
+pub struct Query<'e, F> {
+    fetch: F,
+    excluded: Vec<&'e dyn Contains>,
+    // copied out on the first step since the driving set is borrowed again
+    // for every entity
+    entities: Option<Vec<Entity>>,
+    cursor: usize,
+}
+
+impl<'e, F: Fetch> Query<'e, F> {
+    pub fn new(fetch: F) -> Self {
+        Self {
+            fetch,
+            excluded: Vec::new(),
+            entities: None,
+            cursor: 0,
+        }
+    }
+
+    // no system excludes components yet
+    #[cfg(test)]
+    pub fn without(mut self, set: &'e dyn Contains) -> Self {
+        self.excluded.push(set);
+        self
+    }
+
+    pub fn get(&mut self, entity: &Entity) -> Option<F::Item<'_>> {
+        if self.excluded.iter().any(|set| set.contains(entity)) {
+            return None;
+        }
+
+        self.fetch.fetch(entity)
+    }
+
+    /// The next matching entity, the components are borrowed until the
+    /// following call so it is driven by `while let` instead of `for`.
+    pub fn fetch_next(&mut self) -> Option<(Entity, F::Item<'_>)> {
+        let entities = self.entities.get_or_insert_with(|| {
+            self.fetch
+                .entities()
+                .map(<[Entity]>::to_vec)
+                .unwrap_or_default()
+        });
+
+        let entity = loop {
+            let entity = *entities.get(self.cursor)?;
+            self.cursor += 1;
+
+            let excluded = self.excluded.iter().any(|set| set.contains(&entity));
+
+            if !excluded && self.fetch.matches(&entity) {
+                break entity;
+            }
+        };
+
+        Some((entity, self.fetch.fetch(&entity)?))
+    }
+}
+
+impl<T> Contains for SparseSet<T> {
+    fn contains(&self, entity: &Entity) -> bool {
+        self.get(entity).is_some()
+    }
+}
+
+impl<T> Fetch for &SparseSet<T> {
+    type Item<'a>
+        = &'a T
+    where
+        Self: 'a;
+
+    fn entities(&self) -> Option<&[Entity]> {
+        Some(SparseSet::entities(self))
+    }
+
+    fn matches(&self, entity: &Entity) -> bool {
+        self.contains(entity)
+    }
+
+    fn fetch(&mut self, entity: &Entity) -> Option<Self::Item<'_>> {
+        self.get(entity)
+    }
+}
+
+impl<T> Fetch for &mut SparseSet<T> {
+    type Item<'a>
+        = &'a mut T
+    where
+        Self: 'a;
+
+    fn entities(&self) -> Option<&[Entity]> {
+        Some(SparseSet::entities(self))
+    }
+
+    fn matches(&self, entity: &Entity) -> bool {
+        self.contains(entity)
+    }
+
+    fn fetch(&mut self, entity: &Entity) -> Option<Self::Item<'_>> {
+        self.get_mut(entity)
+    }
+}
+
+impl<F: Fetch> Fetch for Optional<F> {
+    type Item<'a>
+        = Option<F::Item<'a>>
+    where
+        Self: 'a;
+
+    fn entities(&self) -> Option<&[Entity]> {
+        None
+    }
+
+    fn matches(&self, _: &Entity) -> bool {
+        true
+    }
+
+    fn fetch(&mut self, entity: &Entity) -> Option<Self::Item<'_>> {
+        Some(self.0.fetch(entity))
+    }
+}
+
+macro_rules! fetch_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: Fetch),+> Fetch for ($($name,)+) {
+            type Item<'a>
+                = ($($name::Item<'a>,)+)
+            where
+                Self: 'a;
+
+            // the smallest set, the first one on ties
+            fn entities(&self) -> Option<&[Entity]> {
+                #[allow(non_snake_case)]
+                let ($($name,)+) = self;
+                let mut smallest: Option<&[Entity]> = None;
+
+                $(
+                    if let Some(entities) = $name.entities()
+                        && smallest.is_none_or(|smallest| entities.len() < smallest.len())
+                    {
+                        smallest = Some(entities);
+                    }
+                )+
+
+                smallest
+            }
+
+            fn matches(&self, entity: &Entity) -> bool {
+                #[allow(non_snake_case)]
+                let ($($name,)+) = self;
+
+                $($name.matches(entity))&&+
+            }
+
+            fn fetch(&mut self, entity: &Entity) -> Option<Self::Item<'_>> {
+                #[allow(non_snake_case)]
+                let ($($name,)+) = self;
+
+                Some(($($name.fetch(entity)?,)+))
+            }
+        }
+    };
+}
+
+fetch_tuple!(A);
+fetch_tuple!(A, B);
+fetch_tuple!(A, B, C);
+fetch_tuple!(A, B, C, D);
+fetch_tuple!(A, B, C, D, E);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entity(index: u32) -> Entity {
+        Entity {
+            index,
+            generation: 0,
+        }
+    }
+
+    // the component of each entity is its index times ten
+    fn set(indices: &[u32]) -> SparseSet<u32> {
+        let mut set = SparseSet::new(16);
+
+        for &index in indices {
+            set.insert(entity(index), index * 10);
+        }
+
+        set
+    }
+
+    // indices of the entities visited, in order
+    fn collect<F: Fetch>(mut query: Query<'_, F>) -> Vec<u32> {
+        let mut visited = Vec::new();
+
+        while let Some((entity, _)) = query.fetch_next() {
+            visited.push(entity.index);
+        }
+
+        visited
+    }
+
+    #[test]
+    fn only_entities_in_every_set_are_joined() {
+        let (a, b, c) = (set(&[0, 1, 2, 3, 5]), set(&[1, 3, 4, 5]), set(&[5, 3, 6]));
+        let mut query = Query::new((&a, &b, &c));
+        let mut joined = Vec::new();
+
+        while let Some((entity, (a, b, c))) = query.fetch_next() {
+            joined.push((entity.index, *a, *b, *c));
+        }
+
+        assert_eq!(joined, [(5, 50, 50, 50), (3, 30, 30, 30)]);
+    }
+
+    #[test]
+    fn optional_sets_do_not_filter() {
+        let (a, b) = (set(&[0, 1, 2]), set(&[1, 7]));
+        let mut query = Query::new((&a, Optional(&b)));
+        let mut joined = Vec::new();
+
+        while let Some((entity, (_, b))) = query.fetch_next() {
+            joined.push((entity.index, b.copied()));
+        }
+
+        assert_eq!(joined, [(0, None), (1, Some(10)), (2, None)]);
+        // nothing to drive from
+        assert_eq!(collect(Query::new(Optional(&b))), []);
+    }
+
+    #[test]
+    fn excluded_entities_are_skipped() {
+        let (a, b, c) = (set(&[0, 1, 2, 3]), set(&[1]), set(&[3, 9]));
+        let query = Query::new(&a).without(&b).without(&c);
+
+        assert_eq!(collect(query), [0, 2]);
+    }
+
+    #[test]
+    fn the_smallest_set_drives_in_its_order() {
+        let (a, b) = (set(&[0, 1, 2, 3, 4]), set(&[4, 1, 2]));
+
+        assert_eq!(collect(Query::new((&a, &b))), [4, 1, 2]);
+        assert_eq!(collect(Query::new((&b, &a))), [4, 1, 2]);
+        // the first one on ties
+        let c = set(&[2, 1, 4]);
+
+        assert_eq!(collect(Query::new((&b, &c))), [4, 1, 2]);
+        assert_eq!(collect(Query::new((&c, &b))), [2, 1, 4]);
+    }
+
+    #[test]
+    fn mutable_sets_are_written_through() {
+        let (mut a, b) = (set(&[0, 1, 2]), set(&[1, 2]));
+        let mut query = Query::new((&mut a, &b));
+
+        while let Some((_, (a, b))) = query.fetch_next() {
+            *a += *b + 1;
+        }
+
+        assert_eq!(a.get(&entity(0)), Some(&0));
+        assert_eq!(a.get(&entity(1)), Some(&21));
+        assert_eq!(a.get(&entity(2)), Some(&41));
+    }
+
+    #[test]
+    fn get_misses_missing_and_excluded_entities() {
+        let (a, b, c) = (set(&[0, 1, 2]), set(&[1, 2]), set(&[2]));
+        let mut query = Query::new((&a, Optional(&b))).without(&c);
+
+        assert_eq!(query.get(&entity(0)), Some((&0, None)));
+        assert_eq!(query.get(&entity(1)), Some((&10, Some(&10))));
+        assert_eq!(query.get(&entity(2)), None);
+        assert_eq!(query.get(&entity(3)), None);
+        // a stale generation is another entity
+        assert_eq!(
+            query.get(&Entity {
+                index: 0,
+                generation: 1
+            }),
+            None
+        );
+    }
+}
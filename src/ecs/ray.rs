@@ -0,0 +1,64 @@
+use korp_engine::shapes::Rectangle;
+use korp_math::{Flint, Ray};
+
+use crate::ecs::{components::Components, entities::Entity};
+
+#[derive(Debug, Clone, Copy)]
+pub struct RayHit {
+    pub entity: Entity,
+    pub distance: Flint,
+}
+
+pub trait Castable {
+    /// Finds the closest entity along the ray among the ones accepted by the filter.
+    fn cast<F>(&self, components: &Components, filter: F) -> Option<RayHit>
+    where
+        F: Fn(&Entity) -> bool;
+}
+
+impl Castable for Ray {
+    fn cast<F>(&self, components: &Components, filter: F) -> Option<RayHit>
+    where
+        F: Fn(&Entity) -> bool,
+    {
+        let bounds = bounds(self);
+        let mut closest: Option<RayHit> = None;
+
+        for (entity, hitbox) in components.logic.hitboxes.iter() {
+            if !hitbox.overlaps(&bounds) || !filter(entity) {
+                continue;
+            }
+
+            let Some(vertices) = components.logic.vertices.get(entity) else {
+                continue;
+            };
+
+            let Some(distance) = self.polygon(&vertices.new) else {
+                continue;
+            };
+
+            if closest.is_none_or(|closest| distance < closest.distance) {
+                closest = Some(RayHit {
+                    entity: *entity,
+                    distance,
+                });
+            }
+        }
+
+        closest
+    }
+}
+
+fn bounds(ray: &Ray) -> Rectangle<Flint> {
+    let end = ray.end();
+    let x = ray.origin.x.min(end.x);
+    let y = ray.origin.y.min(end.y);
+
+    // padded so axis aligned rays still have an area to overlap with
+    Rectangle {
+        x: x - Flint::ONE,
+        y: y - Flint::ONE,
+        width: (ray.origin.x - end.x).abs() + Flint::from_i16(2),
+        height: (ray.origin.y - end.y).abs() + Flint::from_i16(2),
+    }
+}
@@ -72,6 +72,10 @@ impl<T> SparseSet<T> {
         self.entities.iter().zip(self.dense.iter_mut())
     }
 
+    pub fn entities(&self) -> &[Entity] {
+        &self.entities
+    }
+
     pub fn _len(&self) -> usize {
         self.dense.len()
     }
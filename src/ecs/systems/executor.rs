@@ -1,19 +1,25 @@
+use std::collections::HashSet;
+
 use korp_engine::{misc::Morph, shapes::Rectangle as EngineRectangle};
-use korp_math::{Flint, Random, Vec2};
+use korp_math::{
+    Flint, Mtv, Random, Sweep, Transform2, Vec2, circle_mtv, circle_polygon_mtv, polygon_mtv,
+    polygon_sweep,
+};
 
 use crate::{
     bus::events::CosmosEvent,
     ecs::{
         commands::{Command, SpawnKind},
         components::{
-            Body, Components, Rectangle, Shape, Triangle,
+            Body, Components, Shape,
             traits::{Transformable, Vertexable},
         },
+        query::{Optional, Query},
     },
     quadtree::Quadtree,
 };
 
-pub const COSMIC_DRAG: Flint = Flint::new(0, Flint::POINT_ONE * 2);
+pub const COSMIC_DRAG: Flint = Flint::from_f64(0.2);
 
 pub struct Executor {}
 
@@ -40,7 +46,9 @@ impl Executor {
         spawn_protections(components);
         collisions(components, quadtree, events);
         out_of_cosmos_bounds(cosmos_bounds, components, commands);
+        lifetimes(components, commands);
         constant_accelerators(components, commands);
+        weapons(components, commands);
         exhaust_emitters(components, random, commands);
         particles(components);
 
@@ -115,23 +123,18 @@ fn hitboxes(components: &mut Components) {
 }
 
 fn spawn_protections(components: &mut Components) {
+    let logic = &components.logic;
     let mut removed = Vec::new();
 
-    for (entity, _) in components.logic.spawn_protections.iter() {
-        let Some(owner) = components.logic.owners.get(entity) else {
-            continue;
-        };
+    let mut query = Query::new((&logic.spawn_protections, &logic.owners, &logic.hitboxes));
 
-        let Some(hitbox_entity) = components.logic.hitboxes.get(entity) else {
-            continue;
-        };
-
-        let Some(hitbox_owner) = components.logic.hitboxes.get(&owner.entity) else {
+    while let Some((entity, (_, owner, hitbox_entity))) = query.fetch_next() {
+        let Some(hitbox_owner) = logic.hitboxes.get(&owner.entity) else {
             continue;
         };
 
         if !hitbox_owner.overlaps(hitbox_entity) {
-            removed.push(*entity);
+            removed.push(entity);
         }
     }
 
@@ -141,50 +144,8 @@ fn spawn_protections(components: &mut Components) {
 }
 
 fn collisions(components: &mut Components, quadtree: &Quadtree, events: &mut Vec<CosmosEvent>) {
-    let project = |vertices: &[Vec2<Flint>], axis: Vec2<Flint>| {
-        let mut min = axis.dot(&vertices[0]);
-        let mut max = min;
-
-        for i in 1..vertices.len() {
-            let projection = axis.dot(&vertices[i]);
-            if projection < min {
-                min = projection;
-            } else if projection > max {
-                max = projection;
-            }
-        }
-
-        (min, max)
-    };
-
-    let separated = |v1: &[Vec2<Flint>], v2: &[Vec2<Flint>], axis: &[Vec2<Flint>]| {
-        for i in 0..axis.len() {
-            let a = axis[i];
-            let b = axis[(i + 1) % axis.len()];
-            let axis = (b - a).perp();
-
-            let (min1, max1) = project(v1, axis);
-            let (min2, max2) = project(v2, axis);
-
-            if max1 < min2 || max2 < min1 {
-                return true;
-            }
-        }
-
-        false
-    };
-
-    let intersecting = |v1, v2| {
-        if separated(v1, v2, v1) {
-            return false;
-        }
-
-        if separated(v1, v2, v2) {
-            return false;
-        }
-
-        true
-    };
+    // entities can live in several nodes, only report each pair once
+    let mut reported = HashSet::new();
 
     for node in quadtree.nodes() {
         let group = node
@@ -193,9 +154,10 @@ fn collisions(components: &mut Components, quadtree: &Quadtree, events: &mut Vec
             .filter_map(|(entity, hitbox)| {
                 let filter = components.logic.collision_filters.get(entity);
                 let vertices = components.logic.vertices.get(entity);
+                let body = components.logic.bodies.get(entity);
 
-                if let (Some(filter), Some(vertices)) = (filter, vertices) {
-                    return Some((entity, hitbox, filter, vertices));
+                if let (Some(filter), Some(vertices), Some(body)) = (filter, vertices, body) {
+                    return Some((entity, hitbox, filter, vertices, body));
                 }
 
                 None
@@ -204,11 +166,12 @@ fn collisions(components: &mut Components, quadtree: &Quadtree, events: &mut Vec
 
         let mut current = &group[..];
 
-        while let Some(((entity1, hitbox1, filter1, vertices1), remaining)) = current.split_first()
+        while let Some(((entity1, hitbox1, filter1, vertices1, body1), remaining)) =
+            current.split_first()
         {
             current = remaining;
 
-            for (entity2, hitbox2, filter2, vertices2) in remaining {
+            for (entity2, hitbox2, filter2, vertices2, body2) in remaining {
                 if !filter1.is_collidable(filter2) {
                     continue;
                 }
@@ -217,19 +180,100 @@ fn collisions(components: &mut Components, quadtree: &Quadtree, events: &mut Vec
                     continue;
                 }
 
-                // TODO: need to check values between old and new + rotation
-                if intersecting(&vertices1.new, &vertices2.new) {
-                    events.push(CosmosEvent::Collided {
-                        alpha: **entity1,
-                        beta: **entity2,
-                        mtv: Flint::ZERO,
-                    });
+                let displacement = (body1.new.centroid - body1.old.centroid)
+                    - (body2.new.centroid - body2.old.centroid);
+                let Some(Sweep { toi, axis, speed }) =
+                    polygon_sweep(&vertices1.new, &vertices2.new, displacement)
+                else {
+                    continue;
+                };
+
+                // when they passed through each other during the tick, moving them
+                // back along the axis they met on brings them into contact again
+                let (axis, depth) =
+                    match contact(&body1.new, &vertices1.new, &body2.new, &vertices2.new) {
+                        Contact::Overlapping(mtv) => (mtv.axis, mtv.depth),
+                        Contact::Apart => continue,
+                        Contact::Passed if speed < Flint::ZERO => {
+                            (-axis, (Flint::ONE - toi) * speed.abs())
+                        }
+                        Contact::Passed => (axis, (Flint::ONE - toi) * speed.abs()),
+                    };
+
+                let pair = if entity1.index < entity2.index {
+                    (**entity1, **entity2)
+                } else {
+                    (**entity2, **entity1)
+                };
+
+                if !reported.insert(pair) {
+                    continue;
                 }
+
+                events.push(CosmosEvent::Collided {
+                    alpha: **entity1,
+                    beta: **entity2,
+                    axis,
+                    depth,
+                    toi,
+                });
             }
         }
     }
 }
 
+// where two bodies whose sweeps met are at the end of the tick
+#[derive(Debug, PartialEq, Eq)]
+enum Contact {
+    Overlapping(Mtv),
+    // went through each other during the tick
+    Passed,
+    // only the vertices standing in for a circle met
+    Apart,
+}
+
+// circles are exact while their vertices only approximate them for the sweep
+fn contact(
+    body1: &Body<Flint>,
+    vertices1: &[Vec2<Flint>],
+    body2: &Body<Flint>,
+    vertices2: &[Vec2<Flint>],
+) -> Contact {
+    let mtv = match (body1.shape, body2.shape) {
+        (Shape::Circle(circle1), Shape::Circle(circle2)) => circle_mtv(
+            body1.centroid,
+            circle1.radius,
+            body2.centroid,
+            circle2.radius,
+        ),
+        (Shape::Circle(circle), Shape::Triangle(_) | Shape::Rectangle(_) | Shape::Polygon(_)) => {
+            circle_polygon_mtv(body1.centroid, circle.radius, vertices2)
+        }
+        (Shape::Triangle(_) | Shape::Rectangle(_) | Shape::Polygon(_), Shape::Circle(circle)) => {
+            circle_polygon_mtv(body2.centroid, circle.radius, vertices1).map(|mtv| Mtv {
+                axis: -mtv.axis,
+                depth: mtv.depth,
+            })
+        }
+        (
+            Shape::Triangle(_) | Shape::Rectangle(_) | Shape::Polygon(_),
+            Shape::Triangle(_) | Shape::Rectangle(_) | Shape::Polygon(_),
+        ) => {
+            return match polygon_mtv(vertices1, vertices2) {
+                Some(mtv) => Contact::Overlapping(mtv),
+                None => Contact::Passed,
+            };
+        }
+    };
+
+    match mtv {
+        Some(mtv) => Contact::Overlapping(mtv),
+        // the approximating vertices are larger than the circle
+        None if polygon_mtv(vertices1, vertices2).is_some() => Contact::Apart,
+        None => Contact::Passed,
+    }
+}
+
 fn morph_bodies_render(components: &mut Components) {
     for (_, body) in components.render.bodies.iter_mut() {
         body.old = body.new;
@@ -247,17 +291,7 @@ fn bodies_render(components: &mut Components) {
         let body = Body {
             centroid: lb.new.centroid.into(),
             rotation: lb.new.rotation.into(),
-            shape: match lb.new.shape {
-                Shape::Triangle(triangle) => Shape::Triangle(Triangle {
-                    top: triangle.top.into(),
-                    left: triangle.left.into(),
-                    right: triangle.right.into(),
-                }),
-                Shape::Rectangle(rectangle) => Shape::Rectangle(Rectangle {
-                    width: rectangle.width.into(),
-                    height: rectangle.height.into(),
-                }),
-            },
+            shape: lb.new.shape.transform(),
             color: lb.new.color,
         };
 
@@ -270,17 +304,7 @@ fn bodies_render(components: &mut Components) {
                     Body {
                         centroid: lb.old.centroid.into(),
                         rotation: lb.old.rotation.into(),
-                        shape: match lb.old.shape {
-                            Shape::Triangle(triangle) => Shape::Triangle(Triangle {
-                                top: triangle.top.into(),
-                                left: triangle.left.into(),
-                                right: triangle.right.into(),
-                            }),
-                            Shape::Rectangle(rectangle) => Shape::Rectangle(Rectangle {
-                                width: rectangle.width.into(),
-                                height: rectangle.height.into(),
-                            }),
-                        },
+                        shape: lb.old.shape.transform(),
                         color: lb.old.color,
                     },
                     body,
@@ -323,11 +347,10 @@ fn cosmos_bounds_render(components: &mut Components, cosmos_bounds: EngineRectan
 }
 
 fn motions(components: &mut Components) {
-    for (entity, motion) in components.logic.motions.iter_mut() {
-        let Some(body) = components.logic.bodies.get_mut(entity) else {
-            continue;
-        };
+    let logic = &mut components.logic;
+    let mut query = Query::new((&mut logic.motions, &mut logic.bodies));
 
+    while let Some((_, (motion, body))) = query.fetch_next() {
         // apply cosmic drag for rotation
         if motion.rotation_speed < Flint::ZERO {
             motion.rotation_speed += COSMIC_DRAG;
@@ -362,19 +385,11 @@ fn motions(components: &mut Components) {
 
         // make a full stop if entity has suddenly switched direction
         if direction.dot(&motion.velocity.normalized()) < Flint::ZERO {
-            motion.velocity = Vec2::ZERO;
+            motion.velocity = Vec2::<Flint>::ZERO;
         }
 
-        let speed = motion.velocity.len_sqr();
-        let speed_max = motion.speed_maximum * motion.speed_maximum;
-        let speed_min = -(motion.speed_minimum * motion.speed_minimum);
-
-        // ensure min/max velocity
-        if speed > speed_max {
-            motion.velocity = direction * motion.speed_maximum;
-        } else if speed < speed_min {
-            motion.velocity = direction * motion.speed_minimum;
-        }
+        // ensure max velocity
+        motion.velocity = motion.velocity.clamp_len(motion.speed_maximum);
 
         body.new.centroid += motion.velocity;
     }
@@ -392,12 +407,35 @@ fn out_of_cosmos_bounds(
     }
 }
 
+fn lifetimes(components: &mut Components, commands: &mut Vec<Command>) {
+    for (&entity, lifetime) in components.logic.lifetimes.iter_mut() {
+        // dying takes until the kill is executed, do not ask twice
+        if lifetime.ticks == 0 {
+            continue;
+        }
+
+        lifetime.ticks -= 1;
+
+        if lifetime.ticks == 0 {
+            commands.push(Command::Kill(entity));
+        }
+    }
+}
+
 fn constant_accelerators(components: &mut Components, commands: &mut Vec<Command>) {
     for (&entity, _) in components.logic.constant_accelerators.iter() {
         commands.push(Command::Accelerate(entity));
     }
 }
 
+fn weapons(components: &mut Components, commands: &mut Vec<Command>) {
+    for (&entity, arsenal) in components.logic.weapons.iter_mut() {
+        if arsenal.update() {
+            commands.push(Command::Shoot(entity));
+        }
+    }
+}
+
 fn rebuild_quadtree(components: &Components, quadtree: &mut Quadtree) {
     quadtree.clear();
 
@@ -407,43 +445,48 @@ fn rebuild_quadtree(components: &Components, quadtree: &mut Quadtree) {
 }
 
 fn exhaust_emitters(components: &mut Components, random: &mut Random, commands: &mut Vec<Command>) {
-    for (entity, emitter) in components
-        .logic
-        .exhaust_emitters
-        .iter_mut()
-        .filter(|(_, emitter)| emitter.lifetime > 0)
-    {
+    let logic = &mut components.logic;
+    let mut query = Query::new((
+        &mut logic.exhaust_emitters,
+        Optional(&logic.bodies),
+        Optional(&logic.motions),
+    ));
+
+    while let Some((_, (emitter, body, motion))) = query.fetch_next() {
+        if emitter.lifetime == 0 {
+            continue;
+        }
+
+        // burns down even when there is nothing to emit from
         emitter.lifetime -= 1;
 
-        if let (Some(body), Some(motion)) = (
-            components.logic.bodies.get(entity),
-            components.logic.motions.get(entity),
-        ) {
-            let direction = body.new.rotation.rotated_v(emitter.relative_direction);
-            let mut distance = motion.velocity.len();
-            let relative_speed = distance * Flint::ZERO_FIVE;
-            let half = emitter.width * Flint::ZERO_FIVE;
-
-            while distance > Flint::ZERO {
-                let x = -Flint::from_i16(random.range(0, distance.to_i16().max(1) as u64) as i16);
-                let y =
-                    Flint::from_i16(random.range(0, emitter.width.to_i16() as u64) as i16) - half;
-                let centroid = body.new.centroid
-                    + (emitter.relative_position + Vec2::new(x, y)).rotated_v(body.new.rotation);
-
-                commands.push(Command::Spawn {
-                    id: None,
-                    kind: SpawnKind::Particle {
-                        centroid,
-                        direction,
-                        speed: relative_speed
-                            + Flint::new(random.range(0, 4) as i16, random.range(0, 512) as u16),
-                        lifetime: random.range(4, 20) as u32,
-                    },
-                });
+        let (Some(body), Some(motion)) = (body, motion) else {
+            continue;
+        };
 
-                distance -= Flint::ONE;
-            }
+        let transform = Transform2::<Flint>::new(body.new.centroid, body.new.rotation);
+        let direction = transform.apply_vector(emitter.relative_direction);
+        let mut distance = motion.velocity.len();
+        let relative_speed = distance * Flint::ZERO_FIVE;
+        let half = emitter.width * Flint::ZERO_FIVE;
+
+        while distance > Flint::ZERO {
+            let x = -Flint::from_i16(random.range(0, distance.to_i16().max(1) as u64) as i16);
+            let y = Flint::from_i16(random.range(0, emitter.width.to_i16() as u64) as i16) - half;
+            let centroid = transform.apply(emitter.relative_position + Vec2::new(x, y));
+
+            commands.push(Command::Spawn {
+                id: None,
+                kind: SpawnKind::Particle {
+                    centroid,
+                    direction,
+                    speed: relative_speed
+                        + Flint::new(random.range(0, 4) as i16, random.range(0, 512) as u16),
+                    lifetime: random.range(4, 20) as u32,
+                },
+            });
+
+            distance -= Flint::ONE;
         }
     }
 }
@@ -482,3 +525,254 @@ fn particles(components: &mut Components) {
         true
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use korp_engine::color::Color;
+
+    use super::*;
+    use crate::ecs::{
+        components::{Circle, ExhaustEmitter, Lifetime, Motion, Rectangle},
+        entities::EntityFactory,
+        forge::Forge,
+    };
+
+    fn body(x: Flint, shape: Shape<Flint>) -> (Body<Flint>, Vec<Vec2<Flint>>) {
+        let body = Body {
+            centroid: Vec2::new(x, Flint::ZERO),
+            rotation: Vec2::new(Flint::ONE, Flint::ZERO),
+            shape,
+            color: Color::BLACK,
+        };
+        let vertices = body.vertices();
+
+        (body, vertices)
+    }
+
+    fn circle(x: Flint) -> (Body<Flint>, Vec<Vec2<Flint>>) {
+        body(
+            x,
+            Shape::Circle(Circle {
+                radius: Flint::from_i16(10),
+            }),
+        )
+    }
+
+    fn square(x: Flint) -> (Body<Flint>, Vec<Vec2<Flint>>) {
+        body(
+            x,
+            Shape::Rectangle(Rectangle {
+                width: Flint::from_i16(10),
+                height: Flint::from_i16(10),
+            }),
+        )
+    }
+
+    fn between(
+        (body1, vertices1): (Body<Flint>, Vec<Vec2<Flint>>),
+        (body2, vertices2): (Body<Flint>, Vec<Vec2<Flint>>),
+    ) -> Contact {
+        contact(&body1, &vertices1, &body2, &vertices2)
+    }
+
+    fn overlapping(x: i16, depth: i16) -> Contact {
+        Contact::Overlapping(Mtv {
+            axis: Vec2::new(Flint::from_i16(x), Flint::ZERO),
+            depth: Flint::from_i16(depth),
+        })
+    }
+
+    #[test]
+    fn circles_overlap_exactly() {
+        let (left, right) = (circle(Flint::ZERO), circle(Flint::from_i16(15)));
+
+        assert_eq!(between(left.clone(), right.clone()), overlapping(1, 5));
+        assert_eq!(between(right, left), overlapping(-1, 5));
+    }
+
+    #[test]
+    fn circles_and_polygons_overlap_exactly() {
+        let (circle, square) = (circle(Flint::ZERO), square(Flint::from_i16(12)));
+
+        assert_eq!(between(circle.clone(), square.clone()), overlapping(1, 3));
+        assert_eq!(between(square, circle), overlapping(-1, 3));
+    }
+
+    #[test]
+    fn polygons_overlap() {
+        let (left, right) = (square(Flint::ZERO), square(Flint::from_i16(8)));
+
+        assert_eq!(between(left, right), overlapping(1, 2));
+    }
+
+    #[test]
+    fn circles_are_apart_when_only_their_vertices_meet() {
+        // the vertices reach past the radius on the x axis
+        let x = Flint::new(20, Flint::POINT_ONE);
+
+        assert_eq!(between(circle(Flint::ZERO), circle(x)), Contact::Apart);
+
+        let x = Flint::new(15, Flint::POINT_ONE);
+
+        assert_eq!(between(circle(Flint::ZERO), square(x)), Contact::Apart);
+        assert_eq!(between(square(x), circle(Flint::ZERO)), Contact::Apart);
+    }
+
+    #[test]
+    fn shapes_apart_at_the_end_of_the_tick_passed_through() {
+        let far = Flint::from_i16(50);
+
+        assert_eq!(between(circle(Flint::ZERO), circle(far)), Contact::Passed);
+        assert_eq!(between(circle(Flint::ZERO), square(far)), Contact::Passed);
+        assert_eq!(between(square(Flint::ZERO), circle(far)), Contact::Passed);
+        assert_eq!(between(square(Flint::ZERO), square(far)), Contact::Passed);
+    }
+
+    fn components() -> Components {
+        Components::new(EngineRectangle {
+            x: Flint::ZERO,
+            y: Flint::ZERO,
+            width: Flint::from_i16(100),
+            height: Flint::from_i16(100),
+        })
+    }
+
+    fn emitter(lifetime: u32) -> ExhaustEmitter {
+        ExhaustEmitter {
+            lifetime_maximum: lifetime,
+            lifetime,
+            width: Flint::from_i16(4),
+            relative_position: Vec2::<Flint>::ZERO,
+            relative_direction: Vec2::new(Flint::NEG_ONE, Flint::ZERO),
+        }
+    }
+
+    #[test]
+    fn emitters_burn_down_with_or_without_a_body() {
+        let mut components = components();
+        let mut entities = EntityFactory::new();
+        let (detached, moving) = (entities.create(), entities.create());
+        let (body, _) = square(Flint::ZERO);
+        let logic = &mut components.logic;
+
+        logic.exhaust_emitters.insert(detached, emitter(2));
+        logic.exhaust_emitters.insert(moving, emitter(2));
+        logic.bodies.insert(moving, Morph::new(body, body));
+        logic.motions.insert(
+            moving,
+            Motion {
+                velocity: Vec2::new(Flint::from_i16(3), Flint::ZERO),
+                speed_maximum: Flint::from_i16(10),
+                speed_minimum: Flint::ZERO,
+                acceleration: Flint::ZERO,
+                rotation_speed: Flint::ZERO,
+                rotation_speed_maximum: Flint::ZERO,
+                rotation_speed_minimum: Flint::ZERO,
+                rotation_acceleration: Flint::ZERO,
+                restitution: Flint::ZERO,
+            },
+        );
+
+        let mut random = Random::new(0);
+        let remaining = |components: &Components| {
+            [detached, moving].map(|entity| {
+                components
+                    .logic
+                    .exhaust_emitters
+                    .get(&entity)
+                    .unwrap()
+                    .lifetime
+            })
+        };
+
+        // only the moving one has somewhere to emit from, a particle per unit travelled
+        let mut commands = Vec::new();
+        exhaust_emitters(&mut components, &mut random, &mut commands);
+        assert_eq!(remaining(&components), [1, 1]);
+        assert_eq!(commands.len(), 3);
+
+        exhaust_emitters(&mut components, &mut random, &mut commands);
+        exhaust_emitters(&mut components, &mut random, &mut commands);
+        assert_eq!(remaining(&components), [0, 0]);
+        assert_eq!(commands.len(), 6);
+    }
+
+    #[test]
+    fn lifetimes_kill_once_on_expiry() {
+        let mut components = components();
+        let mut forge = Forge::new();
+        let entity = forge.triangle(Vec2::<Flint>::ZERO, &mut components);
+        let mut commands = Vec::new();
+        let mut events = Vec::new();
+
+        components
+            .logic
+            .lifetimes
+            .insert(entity, Lifetime { ticks: 3 });
+
+        for ticks in [2, 1] {
+            lifetimes(&mut components, &mut commands);
+
+            assert!(commands.is_empty());
+            assert_eq!(
+                components.logic.lifetimes.get(&entity).unwrap().ticks,
+                ticks
+            );
+        }
+
+        lifetimes(&mut components, &mut commands);
+        assert!(matches!(commands[..], [Command::Kill(killed)] if killed == entity));
+
+        // still alive until the kill is executed, without asking again
+        lifetimes(&mut components, &mut commands);
+        assert_eq!(commands.len(), 1);
+
+        // executed twice, it only dies once
+        commands.push(Command::Kill(entity));
+
+        for command in commands.drain(..) {
+            command.execute(&mut components, &mut forge, &mut events);
+        }
+
+        assert!(matches!(events[..], [CosmosEvent::Died(died)] if died == entity));
+        assert!(components.logic.lifetimes.get(&entity).is_none());
+    }
+
+    #[test]
+    fn circles_and_polygons_are_spawned_as_solid_obstacles() {
+        let mut components = components();
+        let mut forge = Forge::new();
+        let mut events = Vec::new();
+        let centroid = Vec2::new(Flint::from_i16(50), Flint::from_i16(50));
+        let triangle = forge.triangle(Vec2::<Flint>::ZERO, &mut components);
+
+        for kind in [
+            SpawnKind::Circle { centroid },
+            SpawnKind::Polygon { centroid },
+        ] {
+            Command::Spawn { id: None, kind }.execute(&mut components, &mut forge, &mut events);
+        }
+
+        let spawned = events
+            .iter()
+            .map(|event| match event {
+                CosmosEvent::Spawned { id: None, entity } => *entity,
+                event => panic!("{event:?}"),
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(spawned.len(), 2);
+
+        let shape = |entity| components.logic.bodies.get(&entity).unwrap().new.shape;
+
+        assert!(matches!(shape(spawned[0]), Shape::Circle(_)));
+        assert!(matches!(shape(spawned[1]), Shape::Polygon(polygon) if polygon.count == 6));
+
+        let filters = &components.logic.collision_filters;
+        let triangle = filters.get(&triangle).unwrap();
+
+        for entity in spawned {
+            assert!(filters.get(&entity).unwrap().is_solid(triangle));
+        }
+    }
+}
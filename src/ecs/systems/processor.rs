@@ -1,23 +1,43 @@
+use std::collections::HashSet;
+
+use korp_math::{Flint, Vec2};
+
 use crate::{
     bus::{Bus, events::CosmosEvent},
-    ecs::{commands::Command, components::Components, entities::Entity, tracker::Tracker},
+    ecs::{
+        commands::Command, components::Components, entities::Entity, query::Query, tracker::Tracker,
+    },
 };
 
-pub struct Processor {}
+// closing speed, in units per tick, below which ramming does no harm
+const RAM_THRESHOLD: Flint = Flint::from_i16(3);
+const RAM_DAMAGE: Flint = Flint::from_i16(4);
+
+pub struct Processor {
+    // pairs that collided during the last tick, they only hurt each other
+    // once on entering contact instead of every tick they overlap
+    contacts: HashSet<(Entity, Entity)>,
+    touching: HashSet<(Entity, Entity)>,
+}
 
 impl Processor {
     pub fn new() -> Self {
-        Self {}
+        Self {
+            contacts: HashSet::new(),
+            touching: HashSet::new(),
+        }
     }
 
     pub fn process(
-        &self,
+        &mut self,
         components: &mut Components,
         tracker: &mut Tracker,
         events: &mut Vec<CosmosEvent>,
         commands: &mut Vec<Command>,
         bus: &mut Bus,
     ) {
+        let mut damaged = Vec::new();
+
         for event in events.drain(..) {
             match event {
                 CosmosEvent::Died(entity) => {
@@ -26,40 +46,452 @@ impl Processor {
                 CosmosEvent::Collided {
                     alpha,
                     beta,
-                    mtv: _,
+                    axis,
+                    depth,
+                    toi: _,
                 } => {
-                    // TODO: use the minimum translation vector to push entities apart?
                     if spawn_protected(alpha, beta, components) {
                         continue;
                     }
 
-                    commands.push(Command::Kill(alpha));
-                    commands.push(Command::Kill(beta));
+                    let pair = pair(alpha, beta);
+                    self.touching.insert(pair);
+                    let entering = !self.contacts.contains(&pair);
+
+                    // measured before bouncing changes the velocities
+                    let impact = impact(alpha, beta, axis, components);
+
+                    if solid(alpha, beta, components) {
+                        bounce(alpha, beta, axis, depth, components);
+                    }
+
+                    if entering {
+                        hit(alpha, beta, impact, components, commands, &mut damaged);
+                        hit(beta, alpha, impact, components, commands, &mut damaged);
+                    }
+                }
+                CosmosEvent::Hitscan {
+                    source,
+                    target,
+                    damage,
+                    ..
+                } => {
+                    wear(target, source, damage, components, commands, &mut damaged);
                 }
                 _ => (),
             }
 
             bus.send(event);
+
+            for event in damaged.drain(..) {
+                bus.send(event);
+            }
         }
+
+        std::mem::swap(&mut self.contacts, &mut self.touching);
+        self.touching.clear();
+    }
+}
+
+// the same key whichever way around the pair collided
+fn pair(a: Entity, b: Entity) -> (Entity, Entity) {
+    if (a.index, a.generation) <= (b.index, b.generation) {
+        (a, b)
+    } else {
+        (b, a)
     }
 }
 
 fn spawn_protected(a: Entity, b: Entity, components: &Components) -> bool {
+    let logic = &components.logic;
     let protected = |a, b| {
-        let Some(_) = components.logic.spawn_protections.get(&a) else {
-            return false;
-        };
+        let mut query = Query::new((&logic.spawn_protections, &logic.owners));
 
-        let Some(owner) = components.logic.owners.get(&a) else {
+        let Some((_, owner)) = query.get(&a) else {
             return false;
         };
 
-        if owner.entity != b {
-            return false;
+        owner.entity == b
+    };
+
+    protected(a, b) || protected(b, a)
+}
+
+fn solid(a: Entity, b: Entity, components: &Components) -> bool {
+    let filter_a = components.logic.collision_filters.get(&a);
+    let filter_b = components.logic.collision_filters.get(&b);
+
+    match (filter_a, filter_b) {
+        (Some(filter_a), Some(filter_b)) => filter_a.is_solid(filter_b),
+        _ => false,
+    }
+}
+
+// closing speed along the axis, zero when moving apart
+fn impact(a: Entity, b: Entity, axis: Vec2<Flint>, components: &Components) -> Flint {
+    let velocity = |entity| {
+        components
+            .logic
+            .motions
+            .get(&entity)
+            .map_or(Vec2::<Flint>::ZERO, |motion| motion.velocity)
+    };
+
+    let closing = (velocity(b) - velocity(a)).dot(&axis);
+
+    (-closing).max(Flint::ZERO)
+}
+
+// the source deals its damage to the target, or rams it when it has none
+fn hit(
+    target: Entity,
+    source: Entity,
+    impact: Flint,
+    components: &mut Components,
+    commands: &mut Vec<Command>,
+    damaged: &mut Vec<CosmosEvent>,
+) {
+    let amount = match components.logic.damages.get(&source) {
+        Some(damage) => {
+            if damage.expendable {
+                commands.push(Command::Kill(source));
+            }
+
+            damage.amount
         }
+        None => (impact - RAM_THRESHOLD).max(Flint::ZERO) * RAM_DAMAGE,
+    };
+
+    wear(target, source, amount, components, commands, damaged);
+}
 
-        true
+fn wear(
+    target: Entity,
+    source: Entity,
+    amount: Flint,
+    components: &mut Components,
+    commands: &mut Vec<Command>,
+    damaged: &mut Vec<CosmosEvent>,
+) {
+    if amount <= Flint::ZERO {
+        return;
+    }
+
+    // only what has health can be worn down
+    let Some(health) = components.logic.healths.get_mut(&target) else {
+        return;
     };
 
-    protected(a, b) || protected(b, a)
+    // already dying
+    if health.current <= Flint::ZERO {
+        return;
+    }
+
+    health.current -= amount;
+
+    damaged.push(CosmosEvent::Damaged {
+        entity: target,
+        source,
+        amount,
+    });
+
+    if health.current <= Flint::ZERO {
+        commands.push(Command::Kill(target));
+    }
+}
+
+// pushes the entities apart along the axis and reflects their closing velocity,
+// entities without motion are immovable and everything has the same mass
+fn bounce(a: Entity, b: Entity, axis: Vec2<Flint>, depth: Flint, components: &mut Components) {
+    let motion_a = components.logic.motions.get(&a);
+    let motion_b = components.logic.motions.get(&b);
+
+    let (share_a, share_b) = match (motion_a.is_some(), motion_b.is_some()) {
+        (true, true) => (Flint::ZERO_FIVE, Flint::ZERO_FIVE),
+        (true, false) => (Flint::ONE, Flint::ZERO),
+        (false, true) => (Flint::ZERO, Flint::ONE),
+        (false, false) => return,
+    };
+
+    let restitution = match (motion_a, motion_b) {
+        (Some(motion_a), Some(motion_b)) => motion_a.restitution.min(motion_b.restitution),
+        (Some(motion), None) | (None, Some(motion)) => motion.restitution,
+        (None, None) => Flint::ZERO,
+    };
+
+    let impact = impact(a, b, axis, components);
+
+    if let Some(body) = components.logic.bodies.get_mut(&a) {
+        body.new.centroid -= axis * (depth * share_a);
+    }
+
+    if let Some(body) = components.logic.bodies.get_mut(&b) {
+        body.new.centroid += axis * (depth * share_b);
+    }
+
+    let impulse = (Flint::ONE + restitution) * impact;
+
+    if let Some(motion) = components.logic.motions.get_mut(&a) {
+        motion.velocity -= axis * (impulse * share_a);
+    }
+
+    if let Some(motion) = components.logic.motions.get_mut(&b) {
+        motion.velocity += axis * (impulse * share_b);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use korp_engine::{color::Color, misc::Morph, shapes::Rectangle};
+
+    use super::*;
+    use crate::ecs::{
+        components::{self, Body, CollisionFilter, Damage, Health, Motion, Shape},
+        entities::EntityFactory,
+    };
+
+    struct Scene {
+        processor: Processor,
+        components: Components,
+        tracker: Tracker,
+        bus: Bus,
+        entities: EntityFactory,
+    }
+
+    impl Scene {
+        fn new() -> Self {
+            let bounds = Rectangle {
+                x: Flint::ZERO,
+                y: Flint::ZERO,
+                width: Flint::from_i16(100),
+                height: Flint::from_i16(100),
+            };
+
+            Self {
+                processor: Processor::new(),
+                components: Components::new(bounds),
+                tracker: Tracker::new(),
+                bus: Bus::new(),
+                entities: EntityFactory::new(),
+            }
+        }
+
+        fn spawn(&mut self, health: Option<i16>, damage: Option<i16>) -> Entity {
+            let entity = self.entities.create();
+
+            if let Some(health) = health {
+                self.components.logic.healths.insert(
+                    entity,
+                    Health {
+                        current: Flint::from_i16(health),
+                        maximum: Flint::from_i16(health),
+                    },
+                );
+            }
+
+            if let Some(damage) = damage {
+                self.components.logic.damages.insert(
+                    entity,
+                    Damage {
+                        amount: Flint::from_i16(damage),
+                        expendable: false,
+                    },
+                );
+            }
+
+            entity
+        }
+
+        // one tick in which the given pairs overlap
+        fn tick(&mut self, pairs: &[(Entity, Entity)]) -> Vec<Command> {
+            let mut events = pairs
+                .iter()
+                .map(|&(alpha, beta)| CosmosEvent::Collided {
+                    alpha,
+                    beta,
+                    axis: Vec2::new(Flint::ONE, Flint::ZERO),
+                    depth: Flint::ONE,
+                    toi: Flint::ZERO,
+                })
+                .collect();
+            let mut commands = Vec::new();
+
+            self.processor.process(
+                &mut self.components,
+                &mut self.tracker,
+                &mut events,
+                &mut commands,
+                &mut self.bus,
+            );
+
+            commands
+        }
+
+        // a square at x on the x axis, moving along it when given a motion,
+        // bouncing off other solid ones
+        fn place(&mut self, entity: Entity, x: i16, motion: Option<Motion>, solid: bool) {
+            let body = Body {
+                centroid: Vec2::new(Flint::from_i16(x), Flint::ZERO),
+                rotation: Vec2::new(Flint::ONE, Flint::ZERO),
+                shape: Shape::Rectangle(components::Rectangle {
+                    width: Flint::from_i16(2),
+                    height: Flint::from_i16(2),
+                }),
+                color: Color::WHITE,
+            };
+            let logic = &mut self.components.logic;
+
+            logic.bodies.insert(entity, Morph::new(body, body));
+            logic.collision_filters.insert(
+                entity,
+                CollisionFilter {
+                    category: CollisionFilter::RECTANGLE,
+                    mask: CollisionFilter::RECTANGLE,
+                    solid: if solid { CollisionFilter::RECTANGLE } else { 0 },
+                },
+            );
+
+            if let Some(motion) = motion {
+                logic.motions.insert(entity, motion);
+            }
+        }
+
+        fn position(&self, entity: Entity) -> Vec2<Flint> {
+            self.components
+                .logic
+                .bodies
+                .get(&entity)
+                .unwrap()
+                .new
+                .centroid
+        }
+
+        fn velocity(&self, entity: Entity) -> Vec2<Flint> {
+            self.components.logic.motions.get(&entity).unwrap().velocity
+        }
+
+        fn health(&self, entity: Entity) -> i16 {
+            self.components
+                .logic
+                .healths
+                .get(&entity)
+                .unwrap()
+                .current
+                .to_i16()
+        }
+    }
+
+    #[test]
+    fn damage_is_dealt_on_entering_contact() {
+        let mut scene = Scene::new();
+        let hazard = scene.spawn(None, Some(10));
+        let ship = scene.spawn(Some(100), None);
+
+        for _ in 0..5 {
+            scene.tick(&[(hazard, ship)]);
+        }
+        assert_eq!(scene.health(ship), 90);
+
+        // apart for a tick, then touching again the other way around
+        scene.tick(&[]);
+        scene.tick(&[(ship, hazard)]);
+        scene.tick(&[(hazard, ship)]);
+        assert_eq!(scene.health(ship), 80);
+    }
+
+    #[test]
+    fn pairs_are_tracked_separately() {
+        let mut scene = Scene::new();
+        let hazard = scene.spawn(None, Some(10));
+        let alpha = scene.spawn(Some(100), None);
+        let beta = scene.spawn(Some(100), None);
+
+        scene.tick(&[(hazard, alpha)]);
+        scene.tick(&[(hazard, alpha), (hazard, beta)]);
+        scene.tick(&[(hazard, alpha), (hazard, beta)]);
+
+        assert_eq!(scene.health(alpha), 90);
+        assert_eq!(scene.health(beta), 90);
+    }
+
+    #[test]
+    fn only_health_is_worn_down() {
+        let mut scene = Scene::new();
+        let hazard = scene.spawn(None, Some(10));
+        let wall = scene.spawn(None, None);
+        let ship = scene.spawn(Some(5), None);
+
+        assert!(scene.tick(&[(hazard, wall)]).is_empty());
+
+        let commands = scene.tick(&[(hazard, ship)]);
+        assert!(matches!(commands[..], [Command::Kill(entity)] if entity == ship));
+    }
+
+    fn motion(velocity: i16, restitution: Flint) -> Option<Motion> {
+        Some(Motion {
+            velocity: Vec2::new(Flint::from_i16(velocity), Flint::ZERO),
+            speed_maximum: Flint::from_i16(10),
+            speed_minimum: Flint::ZERO,
+            acceleration: Flint::ZERO,
+            rotation_speed: Flint::ZERO,
+            rotation_speed_maximum: Flint::ZERO,
+            rotation_speed_minimum: Flint::ZERO,
+            rotation_acceleration: Flint::ZERO,
+            restitution,
+        })
+    }
+
+    fn at(x: Flint) -> Vec2<Flint> {
+        Vec2::new(x, Flint::ZERO)
+    }
+
+    #[test]
+    fn moving_entities_share_the_bounce() {
+        let mut scene = Scene::new();
+        let (alpha, beta) = (scene.spawn(None, None), scene.spawn(None, None));
+        scene.place(alpha, 10, motion(4, Flint::ONE), true);
+        scene.place(beta, 11, motion(-4, Flint::ZERO_FIVE), true);
+
+        // overlapping by one along the x axis
+        scene.tick(&[(alpha, beta)]);
+
+        // each moves out by half the depth
+        assert_eq!(scene.position(alpha), at(Flint::new(9, Flint::POINT_FIVE)));
+        assert_eq!(scene.position(beta), at(Flint::new(11, Flint::POINT_FIVE)));
+
+        // the closing speed of 8 is reflected with the lower restitution
+        // of a half, split between both
+        assert_eq!(scene.velocity(alpha), at(Flint::from_i16(-2)));
+        assert_eq!(scene.velocity(beta), at(Flint::from_i16(2)));
+    }
+
+    #[test]
+    fn entities_without_motion_are_immovable() {
+        let mut scene = Scene::new();
+        let (ship, wall) = (scene.spawn(None, None), scene.spawn(None, None));
+        scene.place(ship, 10, motion(4, Flint::ONE), true);
+        scene.place(wall, 11, None, true);
+
+        scene.tick(&[(ship, wall)]);
+
+        // the ship takes the whole depth and the whole impulse
+        assert_eq!(scene.position(ship), at(Flint::from_i16(9)));
+        assert_eq!(scene.position(wall), at(Flint::from_i16(11)));
+        assert_eq!(scene.velocity(ship), at(Flint::from_i16(-4)));
+    }
+
+    #[test]
+    fn entities_that_are_not_solid_pass_through() {
+        let mut scene = Scene::new();
+        let (alpha, beta) = (scene.spawn(None, None), scene.spawn(None, None));
+        scene.place(alpha, 10, motion(4, Flint::ONE), true);
+        scene.place(beta, 11, motion(-4, Flint::ONE), false);
+
+        scene.tick(&[(alpha, beta)]);
+
+        assert_eq!(scene.position(alpha), at(Flint::from_i16(10)));
+        assert_eq!(scene.position(beta), at(Flint::from_i16(11)));
+        assert_eq!(scene.velocity(alpha), at(Flint::from_i16(4)));
+        assert_eq!(scene.velocity(beta), at(Flint::from_i16(-4)));
+    }
 }
@@ -1,3 +1,5 @@
+use korp_math::{Flint, Vec2};
+
 use crate::{
     bus::{Bus, events::CosmosEvent},
     ecs::{components::Components, entities::Entity},
@@ -24,10 +26,17 @@ impl Tracker {
 
     pub fn update(&mut self, components: &Components, bus: &mut Bus) {
         for entity in self.movement.iter() {
-            if let Some(body) = components.logic.bodies.get(&entity) {
+            if let Some(body) = components.logic.bodies.get(entity) {
+                let velocity = match components.logic.motions.get(entity) {
+                    Some(motion) => motion.velocity,
+                    None => Vec2::<Flint>::ZERO,
+                };
+
                 bus.send(CosmosEvent::TrackedMovement {
                     entity: *entity,
                     centroid: body.new.centroid,
+                    rotation: body.new.rotation,
+                    velocity,
                 });
             }
         }
@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use korp_engine::{Core, CoreEvent, input::Input, renderer::Renderer};
 
 use crate::{
@@ -12,16 +14,61 @@ pub struct Korp {
     network: Network,
 }
 
+/// Settings given on the command line.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    // directory a report is written to whenever we desync from a peer
+    pub desyncs: Option<PathBuf>,
+    // file matches are recorded to and played back from
+    pub replay: Option<PathBuf>,
+}
+
 impl Korp {
     pub fn new() -> Self {
+        Self::with_options(Options::default())
+    }
+
+    pub fn with_options(options: Options) -> Self {
+        Self::with_network(Network::new(), options)
+    }
+
+    pub(crate) fn with_network(network: Network, options: Options) -> Self {
         Self {
             bus: Bus::new(),
-            nexus: Nexus::new(),
-            network: Network::new(),
+            nexus: Nexus::new(options),
+            network,
         }
     }
 }
 
+impl Options {
+    pub fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut options = Self::default();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--desyncs" => {
+                    let directory = args.next().ok_or("--desyncs needs a directory")?;
+                    options.desyncs = Some(directory.into());
+                }
+                "--replay" => {
+                    let path = args.next().ok_or("--replay needs a path")?;
+                    options.replay = Some(path.into());
+                }
+                _ => return Err(format!("unknown argument {}", arg)),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+impl Default for Korp {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Core for Korp {
     fn update(&mut self) {
         self.bus.update(&mut self.nexus, &mut self.network);
@@ -41,3 +88,353 @@ impl Core for Korp {
         self.bus.send(Event::Core(*event));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use korp_engine::{Headless, input::KeyCode};
+    use korp_math::{Flint, Random, Vec2};
+
+    use super::*;
+    use crate::{
+        bus::events::{IntentEvent, NetworkEvent, NetworkIntent, NexusEvent},
+        ecs::{
+            checksum::Checksum,
+            commands::{Command, SpawnKind},
+        },
+        network::{
+            PORT,
+            transport::{Faults, Loopback},
+        },
+        nexus::State,
+    };
+
+    const PEERS: usize = 4;
+    // ticks of play
+    const TICKS: usize = 420;
+
+    const KEYS: [KeyCode; 8] = [
+        KeyCode::ArrowUp,
+        KeyCode::ArrowDown,
+        KeyCode::ArrowLeft,
+        KeyCode::ArrowRight,
+        KeyCode::Space,
+        KeyCode::KeyQ,
+        KeyCode::Digit1,
+        KeyCode::Digit2,
+    ];
+
+    #[derive(Default)]
+    struct Peer {
+        lobby: bool,
+        game: bool,
+        // back in the menu after the match
+        left: bool,
+        desynced: bool,
+        checksums: BTreeMap<usize, Checksum>,
+        // where our own spawns were sent to land
+        spawns: Vec<Vec2<Flint>>,
+    }
+
+    impl Peer {
+        fn observe(&mut self, korp: &Korp) {
+            for event in korp.bus.events() {
+                match event {
+                    Event::Nexus(IntentEvent::Event(NexusEvent::Transitioned(state))) => {
+                        match state {
+                            State::Lobby { .. } => self.lobby = true,
+                            State::Game { .. } => self.game = true,
+                            State::Menu if self.game => self.left = true,
+                            _ => (),
+                        }
+                    }
+                    Event::Network(IntentEvent::Intent(NetworkIntent::Checksum {
+                        tick,
+                        checksum,
+                        ..
+                    })) => {
+                        self.checksums.insert(*tick, *checksum);
+                    }
+                    Event::Network(IntentEvent::Event(NetworkEvent::Desynced { .. })) => {
+                        self.desynced = true;
+                    }
+                    Event::Network(IntentEvent::Intent(NetworkIntent::Commands {
+                        commands,
+                        ..
+                    })) => {
+                        for command in commands {
+                            if let Command::Spawn {
+                                kind:
+                                    SpawnKind::Triangle { centroid } | SpawnKind::Rectangle { centroid },
+                                ..
+                            } = command
+                            {
+                                self.spawns.push(*centroid);
+                            }
+                        }
+                    }
+                    _ => (),
+                }
+            }
+        }
+    }
+
+    // steps the chosen cores and delivers what they sent
+    fn step(
+        loopback: &Loopback,
+        cores: &mut [Headless<Korp>],
+        peers: &mut [Peer],
+        stepping: impl Fn(usize) -> bool,
+    ) {
+        for (idx, (core, peer)) in cores.iter_mut().zip(peers.iter_mut()).enumerate() {
+            if stepping(idx) {
+                core.step();
+                peer.observe(core.core());
+            }
+        }
+
+        loopback.advance();
+    }
+
+    fn press(core: &mut Headless<Korp>, key: KeyCode) {
+        core.input_mut().press(key);
+        core.step();
+        core.input_mut().release(key);
+    }
+
+    // the first peer hosts, the others connect and the match starts
+    fn launch(loopback: &Loopback, count: usize) -> (Vec<Headless<Korp>>, Vec<Peer>) {
+        let mut cores = (0..count)
+            .map(|_| {
+                let network = Network::with_transport(Box::new(loopback.transport()), PORT);
+                Headless::new(Korp::with_network(network, Options::default()))
+            })
+            .collect::<Vec<_>>();
+        let mut peers = (0..count).map(|_| Peer::default()).collect::<Vec<_>>();
+
+        press(&mut cores[0], KeyCode::KeyH);
+
+        for _ in 0..100 {
+            step(loopback, &mut cores, &mut peers, |_| true);
+        }
+        assert!(peers[0].lobby, "host never reached the lobby");
+
+        for core in cores.iter_mut().skip(1) {
+            press(core, KeyCode::KeyC);
+        }
+
+        for _ in 0..100 {
+            step(loopback, &mut cores, &mut peers, |_| true);
+        }
+        assert!(
+            peers.iter().all(|peer| peer.lobby),
+            "a peer never connected"
+        );
+
+        press(&mut cores[0], KeyCode::KeyS);
+
+        for _ in 0..100 {
+            step(loopback, &mut cores, &mut peers, |_| true);
+        }
+        assert!(peers.iter().all(|peer| peer.game), "a peer never launched");
+
+        (cores, peers)
+    }
+
+    fn lockstep(faults: Faults) {
+        let loopback = Loopback::with_faults(faults);
+        let mut random = Random::new(faults.seed);
+        let (mut cores, mut peers) = launch(&loopback, PEERS);
+
+        // everyone mashes keys, lockstep has to keep the cosmos identical
+        let mut ticks = 0;
+
+        while peers.iter().any(|peer| peer.checksums.len() < TICKS / 60) {
+            for core in cores.iter_mut() {
+                let key = KEYS[random.range(0, KEYS.len() as u64) as usize];
+
+                match random.range(0, 3) {
+                    0 => core.input_mut().press(key),
+                    1 => core.input_mut().release(key),
+                    _ => (),
+                }
+            }
+
+            step(&loopback, &mut cores, &mut peers, |_| true);
+
+            ticks += 1;
+            assert!(ticks < TICKS * 10, "lockstep stalled");
+        }
+
+        for (tick, checksum) in peers[0].checksums.iter().take(TICKS / 60) {
+            for (idx, peer) in peers.iter().enumerate().skip(1) {
+                assert_eq!(
+                    peer.checksums.get(tick),
+                    Some(checksum),
+                    "peer {} differs at tick {}",
+                    idx,
+                    tick
+                );
+            }
+        }
+
+        assert!(peers.iter().all(|peer| !peer.desynced));
+    }
+
+    #[test]
+    fn peers_stay_in_sync() {
+        lockstep(Faults::NONE);
+    }
+
+    #[test]
+    fn peers_stay_in_sync_over_a_faulty_network() {
+        lockstep(Faults {
+            seed: 7,
+            latency: 2,
+            jitter: 3,
+            loss: 15,
+            reorder: 15,
+        });
+    }
+
+    #[test]
+    fn a_peer_leaving_ahead_of_another_does_not_stand_in_for_it() {
+        let loopback = Loopback::with_faults(Faults::NONE);
+        let (mut cores, mut peers) = launch(&loopback, 3);
+        let (host, behind, ahead) = (0, 1, 2);
+
+        for _ in 0..60 {
+            step(&loopback, &mut cores, &mut peers, |_| true);
+        }
+
+        let others = |idx: usize| idx != behind;
+
+        // the slow one stops while steering, so running without its commands
+        // shows, the others run up to what it delivered and wait, the one
+        // ahead having sent its commands for the next ticks
+        cores[behind].input_mut().press(KeyCode::ArrowLeft);
+
+        for _ in 0..10 {
+            step(&loopback, &mut cores, &mut peers, others);
+        }
+
+        // it delivers one more tick, which the one ahead runs right away
+        step(&loopback, &mut cores, &mut peers, |_| true);
+        step(&loopback, &mut cores, &mut peers, others);
+
+        // and leaves before the slow one is heard from again
+        cores[ahead].input_mut().press(KeyCode::KeyE);
+
+        for _ in 0..10 {
+            step(&loopback, &mut cores, &mut peers, others);
+        }
+        assert!(peers[ahead].left, "never left");
+
+        let mut ticks = 0;
+
+        while [host, behind]
+            .iter()
+            .any(|&idx| peers[idx].checksums.len() < 5)
+        {
+            step(&loopback, &mut cores, &mut peers, |_| true);
+
+            ticks += 1;
+            assert!(ticks < 1000, "lockstep stalled");
+        }
+
+        for (tick, checksum) in peers[host].checksums.iter() {
+            if let Some(theirs) = peers[behind].checksums.get(tick) {
+                assert_eq!(theirs, checksum, "differs at tick {}", tick);
+            }
+        }
+
+        assert!(!peers[host].desynced && !peers[behind].desynced);
+    }
+
+    #[test]
+    fn options_are_parsed() {
+        let args = |args: &[&str]| Options::parse(args.iter().map(|arg| arg.to_string()));
+
+        assert!(args(&[]).unwrap().desyncs.is_none());
+        assert_eq!(
+            args(&["--desyncs", "reports"]).unwrap().desyncs,
+            Some(PathBuf::from("reports"))
+        );
+        assert_eq!(
+            args(&["--replay", "match.korp"]).unwrap().replay,
+            Some(PathBuf::from("match.korp"))
+        );
+        assert!(args(&["--desyncs"]).is_err());
+        assert!(args(&["--unknown"]).is_err());
+    }
+
+    #[test]
+    fn a_match_runs_headless_for_thousands_of_ticks() {
+        const MATCH: usize = 3000;
+
+        let loopback = Loopback::with_faults(Faults::NONE);
+        let network = Network::with_transport(Box::new(loopback.transport()), PORT);
+        let mut core = Headless::new(Korp::with_network(network, Options::default()));
+        let mut peer = Peer::default();
+        let mut random = Random::new(11);
+
+        let step = |core: &mut Headless<Korp>, peer: &mut Peer| {
+            core.step();
+            peer.observe(core.core());
+            loopback.advance();
+        };
+
+        for key in [KeyCode::KeyH, KeyCode::KeyS] {
+            core.input_mut().press(key);
+
+            for _ in 0..20 {
+                step(&mut core, &mut peer);
+                core.input_mut().release(key);
+            }
+        }
+        assert!(peer.game, "the match never started");
+
+        // the middle of the window is where the camera looks, which is the
+        // spawn point until anything moves, nothing is ever rendered and
+        // fractions of a pixel are kept
+        core.input_mut().move_mouse(Vec2::new(400.5, 300.25));
+        core.input_mut().press(KeyCode::Digit2);
+        step(&mut core, &mut peer);
+        core.input_mut().release(KeyCode::Digit2);
+
+        assert_eq!(
+            peer.spawns,
+            [Vec2::new(Flint::new(900, 0x8000), Flint::new(740, 0x4000))]
+        );
+
+        let mut ticks = 0;
+
+        while peer.checksums.len() < MATCH / 60 {
+            let key = KEYS[random.range(0, KEYS.len() as u64) as usize];
+
+            match random.range(0, 3) {
+                0 => core.input_mut().press(key),
+                1 => core.input_mut().release(key),
+                _ => (),
+            }
+
+            let mouse = Vec2::new(random.range(0, 800) as f32, random.range(0, 600) as f32);
+            core.input_mut().move_mouse(mouse);
+
+            step(&mut core, &mut peer);
+
+            ticks += 1;
+            assert!(ticks < MATCH * 2, "the match stalled");
+        }
+
+        assert!(peer.spawns.len() > 1);
+        assert!(!peer.desynced);
+
+        // the camera never leaves the cosmos, so neither does the cursor
+        for spawn in peer.spawns.iter() {
+            assert!((50..=1750).contains(&spawn.x.to_i16()), "{:?}", spawn);
+            assert!((40..=1440).contains(&spawn.y.to_i16()), "{:?}", spawn);
+        }
+    }
+}
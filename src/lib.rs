@@ -4,5 +4,6 @@ mod korp;
 mod network;
 mod nexus;
 mod quadtree;
+mod wire;
 
-pub use crate::korp::Korp;
+pub use crate::korp::{Korp, Options};
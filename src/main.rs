@@ -1,4 +1,4 @@
-use korp::Korp;
+use korp::{Korp, Options};
 use korp_engine::Engine;
 
 fn main() {
@@ -34,5 +34,13 @@ fn main() {
         println!("-------------------------------------------------------------------------------");
     }
 
-    Engine::new(12, Korp::new(), "korp").run();
+    let options = match Options::parse(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    };
+
+    Engine::new(12, Korp::with_options(options), "korp").run();
 }
@@ -1,17 +1,56 @@
-use std::net::IpAddr;
+use std::{
+    collections::BTreeMap,
+    io::{self, ErrorKind},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 use crate::{
     bus::{
         Bus,
         events::{Event, IntentEvent, NetworkEvent, NetworkIntent},
     },
-    ecs::commands::Command,
+    ecs::{
+        checksum::{Checksum, Dump},
+        commands::Command,
+    },
+    network::{
+        message::{Batch, Control, Message},
+        transport::{Transport, Udp},
+    },
+    wire,
 };
 
+pub mod message;
+pub mod transport;
+
+pub const PORT: u16 = 7331;
+
+// amount of updates without hearing from a peer before giving up on it
+const TIMEOUT: u32 = 120;
+// amount of unacknowledged ticks resent in a single packet
+const BATCHES_MAX: usize = 16;
+// amount of our own checksums kept around for peers to catch up
+const CHECKSUMS_MAX: usize = 8;
+// largest possible udp payload
+const PACKET_MAX: usize = 65507;
+
 pub struct Network {
     actions: Vec<Action>,
+    transport: Box<dyn Transport>,
+    open: bool,
+    role: Role,
+    peers: Vec<Peer>,
     id: usize,
+    id_next: usize,
     delay: usize,
+    port: u16,
+    launched: bool,
+    // whether the game consumes commands yet, peers may launch and send theirs first
+    started: bool,
+    checksums: BTreeMap<usize, (Checksum, Option<Dump>)>,
+    buffer: Vec<u8>,
+    packet: Vec<u8>,
 }
 
 #[derive(Debug, Clone)]
@@ -19,24 +58,78 @@ pub enum Action {
     Host,
     Connect(IpAddr),
     Disconnect,
-    Commands { tick: usize, commands: Vec<Command> },
+    Commands {
+        tick: usize,
+        commands: Vec<Command>,
+    },
+    Checksum {
+        tick: usize,
+        checksum: Checksum,
+        dump: Option<Dump>,
+    },
     Launch,
     Pause,
     Resume,
 }
 
-// TODO: this whole implementation is basically a stub
+enum Role {
+    Offline,
+    Host,
+    Connecting { host: SocketAddr, counter: u32 },
+    Client,
+}
+
+struct Peer {
+    id: usize,
+    address: SocketAddr,
+    silence: u32,
+    // commands sent to the peer which it has yet to acknowledge
+    outgoing: Vec<Batch>,
+    // the next tick we expect to receive from the peer
+    received: usize,
+    // commands received ahead of the expected tick
+    pending: BTreeMap<usize, Vec<Command>>,
+    // control messages sent to the peer which it has yet to acknowledge
+    controls: Vec<(u32, Control)>,
+    control_seq: u32,
+    control_next: u32,
+    // checksums received ahead of our own
+    checksums: BTreeMap<usize, Checksum>,
+    desynced: bool,
+}
+
 impl Network {
     pub fn new() -> Self {
+        Self::with_port(PORT)
+    }
+
+    pub fn with_port(port: u16) -> Self {
+        Self::with_transport(Box::new(Udp::new()), port)
+    }
+
+    pub fn with_transport(transport: Box<dyn Transport>, port: u16) -> Self {
         Self {
             actions: Vec::new(),
+            transport,
+            open: false,
+            role: Role::Offline,
+            peers: Vec::new(),
             id: 0,
+            id_next: 0,
             delay: 2,
+            port,
+            launched: false,
+            started: false,
+            checksums: BTreeMap::new(),
+            buffer: vec![0; PACKET_MAX],
+            packet: Vec::with_capacity(PACKET_MAX),
         }
     }
 
     pub fn update(&mut self, bus: &mut Bus) {
         self.action(bus);
+        self.receive(bus);
+        self.flush(bus);
     }
 
     pub fn event(&mut self, event: &Event) {
@@ -55,6 +148,17 @@ impl Network {
                     commands: (*commands).clone(),
                 });
             }
+            NetworkIntent::Checksum {
+                tick,
+                checksum,
+                dump,
+            } => {
+                self.actions.push(Action::Checksum {
+                    tick: *tick,
+                    checksum: *checksum,
+                    dump: dump.clone(),
+                });
+            }
             NetworkIntent::Disconnect => {
                 self.actions.push(Action::Disconnect);
             }
@@ -76,34 +180,753 @@ impl Network {
 
             match action {
                 Action::Host => {
-                    bus.send(NetworkEvent::Hosted { id: self.id });
+                    self.reset();
+
+                    let address = SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), self.port);
+
+                    if self.open(address, bus) {
+                        self.role = Role::Host;
+                        self.id = 0;
+                        self.id_next = 1;
+
+                        bus.send(NetworkEvent::Hosted { id: self.id });
+                    }
                 }
-                Action::Connect(_ip) => {
-                    bus.send(NetworkEvent::Connected { id: self.id });
+                Action::Connect(ip) => {
+                    self.reset();
+
+                    let unspecified = match ip {
+                        IpAddr::V4(_) => IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+                        IpAddr::V6(_) => IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+                    };
+
+                    // the hello is sent when flushing until we are welcomed
+                    if self.open(SocketAddr::new(unspecified, 0), bus) {
+                        self.role = Role::Connecting {
+                            host: SocketAddr::new(ip, self.port),
+                            counter: 0,
+                        };
+                    }
                 }
                 Action::Disconnect => {
+                    let addresses = self
+                        .peers
+                        .iter()
+                        .map(|peer| peer.address)
+                        .collect::<Vec<_>>();
+
+                    for address in addresses {
+                        self.send(address, &Message::Disconnect, bus);
+                    }
+
+                    self.reset();
+
                     bus.send(NetworkEvent::Disconnected { id: self.id });
                 }
                 Action::Commands { tick, commands } => {
+                    self.started = true;
+
+                    for peer in self.peers.iter_mut() {
+                        peer.outgoing.push(Batch {
+                            tick,
+                            commands: commands.clone(),
+                        });
+                    }
+
                     bus.send(NetworkEvent::Commands {
                         id: self.id,
                         tick,
                         commands,
                     });
                 }
+                Action::Checksum {
+                    tick,
+                    checksum,
+                    dump,
+                } => {
+                    for peer in self.peers.iter_mut() {
+                        peer.control(Control::Checksum { tick, checksum });
+                    }
+
+                    self.checksums.insert(tick, (checksum, dump));
+
+                    while self.checksums.len() > CHECKSUMS_MAX {
+                        self.checksums.pop_first();
+                    }
+
+                    // whatever is older than our oldest checksum can never be compared
+                    if let Some((&oldest, _)) = self.checksums.first_key_value() {
+                        for peer in self.peers.iter_mut() {
+                            peer.checksums.retain(|tick, _| *tick >= oldest);
+                        }
+                    }
+
+                    for idx in 0..self.peers.len() {
+                        self.compare(idx, tick, bus);
+                    }
+                }
                 Action::Launch => {
+                    if self.launched || !matches!(self.role, Role::Host | Role::Offline) {
+                        continue;
+                    }
+
+                    let seed = seed();
+                    let mut ids = vec![self.id];
+                    ids.extend(self.peers.iter().map(|peer| peer.id));
+
+                    // the host is sent without an address, every peer already knows it
+                    let mut peers = vec![(self.id, None)];
+                    peers.extend(self.peers.iter().map(|peer| (peer.id, Some(peer.address))));
+
+                    for peer in self.peers.iter_mut() {
+                        peer.received = self.delay;
+                        peer.control(Control::Launch {
+                            seed,
+                            delay: self.delay,
+                            peers: peers.clone(),
+                        });
+                    }
+
+                    self.launched = true;
+
                     bus.send(NetworkEvent::Launched {
-                        seed: 1337,
+                        seed,
                         delay: self.delay,
+                        ids,
                     });
                 }
                 Action::Pause => {
+                    for peer in self.peers.iter_mut() {
+                        peer.control(Control::Pause);
+                    }
+
                     bus.send(NetworkEvent::Paused);
                 }
                 Action::Resume => {
+                    for peer in self.peers.iter_mut() {
+                        peer.control(Control::Resume);
+                    }
+
                     bus.send(NetworkEvent::Resumed);
                 }
             }
         }
     }
+
+    fn receive(&mut self, bus: &mut Bus) {
+        if !self.open {
+            return;
+        }
+
+        let mut messages = Vec::new();
+
+        loop {
+            match self.transport.receive(&mut self.buffer) {
+                Ok(Some((len, address))) => {
+                    // silently drop anything we can't make sense of
+                    if let Ok(message) = wire::decode::<Message>(&self.buffer[..len]) {
+                        messages.push((address, message));
+                    }
+                }
+                Ok(None) => break,
+                Err(e) => {
+                    bus.send(NetworkEvent::Error(e));
+                    break;
+                }
+            }
+        }
+
+        for (address, message) in messages {
+            self.handle(address, message, bus);
+        }
+    }
+
+    fn handle(&mut self, address: SocketAddr, message: Message, bus: &mut Bus) {
+        match message {
+            Message::Hello => {
+                if !matches!(self.role, Role::Host) {
+                    return;
+                }
+
+                let id = match self.peers.iter().find(|peer| peer.address == address) {
+                    Some(peer) => peer.id,
+                    None if !self.launched => {
+                        let id = self.id_next;
+                        self.id_next += 1;
+                        self.peers.push(Peer::new(id, address, self.delay));
+                        id
+                    }
+                    None => return,
+                };
+
+                self.send(address, &Message::Welcome { id, host: self.id }, bus);
+            }
+            Message::Welcome { id, host } => {
+                let Role::Connecting { host: expected, .. } = self.role else {
+                    return;
+                };
+
+                if expected != address {
+                    return;
+                }
+
+                self.id = id;
+                self.role = Role::Client;
+                self.peers.push(Peer::new(host, address, self.delay));
+
+                bus.send(NetworkEvent::Connected { id });
+            }
+            message => {
+                let Some(idx) = self.peers.iter().position(|peer| peer.address == address) else {
+                    return;
+                };
+
+                self.peers[idx].silence = 0;
+
+                match message {
+                    Message::Control { seq, control } => {
+                        self.send(address, &Message::Acknowledge { seq }, bus);
+
+                        let peer = &mut self.peers[idx];
+
+                        // controls must be handled in order, anything else will be resent
+                        if seq != peer.control_next {
+                            return;
+                        }
+
+                        peer.control_next += 1;
+
+                        self.control(idx, control, bus);
+                    }
+                    Message::Acknowledge { seq } => {
+                        self.peers[idx].controls.retain(|(s, _)| *s != seq);
+                    }
+                    Message::Commands { ack, batches } => {
+                        if !self.launched {
+                            return;
+                        }
+
+                        let peer = &mut self.peers[idx];
+
+                        peer.outgoing.retain(|batch| batch.tick >= ack);
+
+                        for batch in batches {
+                            if batch.tick >= peer.received {
+                                peer.pending.insert(batch.tick, batch.commands);
+                            }
+                        }
+
+                        // held back commands aren't acknowledged, so they will be resent
+                        if !self.started {
+                            return;
+                        }
+
+                        while let Some(commands) = peer.pending.remove(&peer.received) {
+                            bus.send(NetworkEvent::Commands {
+                                id: peer.id,
+                                tick: peer.received,
+                                commands,
+                            });
+
+                            peer.received += 1;
+                        }
+                    }
+                    Message::Disconnect => {
+                        let peer = self.peers.remove(idx);
+
+                        bus.send(NetworkEvent::Disconnected { id: peer.id });
+                    }
+                    _ => (),
+                }
+            }
+        }
+    }
+
+    fn control(&mut self, idx: usize, control: Control, bus: &mut Bus) {
+        match control {
+            Control::Launch { seed, delay, peers } => {
+                if self.launched || !matches!(self.role, Role::Client) {
+                    return;
+                }
+
+                self.launched = true;
+                self.delay = delay;
+
+                for (id, address) in peers.iter() {
+                    // peers without an address are the host, which we already know
+                    if let Some(address) = address
+                        && *id != self.id
+                    {
+                        self.peers.push(Peer::new(*id, *address, delay));
+                    }
+                }
+
+                for peer in self.peers.iter_mut() {
+                    peer.received = delay;
+                }
+
+                bus.send(NetworkEvent::Launched {
+                    seed,
+                    delay,
+                    ids: peers.iter().map(|(id, _)| *id).collect(),
+                });
+            }
+            Control::Pause => bus.send(NetworkEvent::Paused),
+            Control::Resume => bus.send(NetworkEvent::Resumed),
+            Control::Checksum { tick, checksum } => {
+                self.peers[idx].checksums.insert(tick, checksum);
+                self.compare(idx, tick, bus);
+            }
+        }
+    }
+
+    fn compare(&mut self, idx: usize, tick: usize, bus: &mut Bus) {
+        let Some((checksum, dump)) = self.checksums.get(&tick) else {
+            return;
+        };
+
+        let peer = &mut self.peers[idx];
+
+        let Some(other) = peer.checksums.remove(&tick) else {
+            return;
+        };
+
+        // once desynced every following checksum differs as well, only report the first
+        if checksum.differing(&other).is_empty() || peer.desynced {
+            return;
+        }
+
+        peer.desynced = true;
+
+        bus.send(NetworkEvent::Desynced {
+            tick,
+            id: peer.id,
+            ours: *checksum,
+            theirs: other,
+            dump: dump.clone(),
+        });
+    }
+
+    fn flush(&mut self, bus: &mut Bus) {
+        if !self.open {
+            return;
+        }
+
+        if let Role::Connecting { host, counter } = &mut self.role {
+            *counter += 1;
+
+            if *counter > TIMEOUT {
+                self.reset();
+                bus.send(NetworkEvent::Disconnected { id: self.id });
+                return;
+            }
+
+            let host = *host;
+            self.send(host, &Message::Hello, bus);
+            return;
+        }
+
+        let mut messages = Vec::new();
+        let mut commands = Vec::new();
+
+        for (idx, peer) in self.peers.iter_mut().enumerate() {
+            peer.silence += 1;
+
+            for (seq, control) in peer.controls.iter() {
+                messages.push((
+                    peer.address,
+                    Message::Control {
+                        seq: *seq,
+                        control: control.clone(),
+                    },
+                ));
+            }
+
+            // the commands double as heartbeats once launched
+            if self.launched {
+                commands.push(idx);
+            } else {
+                messages.push((peer.address, Message::Heartbeat));
+            }
+        }
+
+        for (address, message) in messages {
+            self.send(address, &message, bus);
+        }
+
+        for idx in commands {
+            self.send_commands(idx, bus);
+        }
+
+        self.peers.retain(|peer| {
+            if peer.silence > TIMEOUT {
+                bus.send(NetworkEvent::Disconnected { id: peer.id });
+                return false;
+            }
+
+            true
+        });
+    }
+
+    fn send(&mut self, address: SocketAddr, message: &Message, bus: &mut Bus) {
+        wire::encode(message, &mut self.packet);
+        self.transmit(address, bus);
+    }
+
+    // as many of the oldest unacknowledged batches as fit in a packet, the
+    // rest go out once those are acknowledged
+    fn send_commands(&mut self, idx: usize, bus: &mut Bus) {
+        let peer = &self.peers[idx];
+        let mut count = BATCHES_MAX;
+
+        loop {
+            let message = Message::Commands {
+                ack: peer.received,
+                batches: peer.outgoing.iter().take(count).cloned().collect(),
+            };
+
+            wire::encode(&message, &mut self.packet);
+
+            if self.packet.len() <= PACKET_MAX || count == 1 {
+                break;
+            }
+
+            count /= 2;
+        }
+
+        self.transmit(peer.address, bus);
+    }
+
+    fn transmit(&mut self, address: SocketAddr, bus: &mut Bus) {
+        if !self.open {
+            return;
+        }
+
+        if self.packet.len() > PACKET_MAX {
+            bus.send(NetworkEvent::Error(io::Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "packet of {} bytes exceeds {}",
+                    self.packet.len(),
+                    PACKET_MAX
+                ),
+            )));
+            return;
+        }
+
+        // packets are allowed to get lost, whatever matters is resent
+        let _ = self.transport.send(address, &self.packet);
+    }
+
+    fn open(&mut self, address: SocketAddr, bus: &mut Bus) -> bool {
+        match self.transport.open(address) {
+            Ok(_) => {
+                self.open = true;
+                true
+            }
+            Err(e) => {
+                bus.send(NetworkEvent::Error(e));
+                false
+            }
+        }
+    }
+
+    fn reset(&mut self) {
+        self.transport.close();
+        self.open = false;
+        self.role = Role::Offline;
+        self.peers.clear();
+        self.launched = false;
+        self.started = false;
+        self.checksums.clear();
+    }
+}
+
+impl Default for Network {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Peer {
+    fn new(id: usize, address: SocketAddr, received: usize) -> Self {
+        Self {
+            id,
+            address,
+            silence: 0,
+            outgoing: Vec::new(),
+            received,
+            pending: BTreeMap::new(),
+            controls: Vec::new(),
+            control_seq: 0,
+            control_next: 0,
+            checksums: BTreeMap::new(),
+            desynced: false,
+        }
+    }
+
+    fn control(&mut self, control: Control) {
+        self.controls.push((self.control_seq, control));
+        self.control_seq += 1;
+    }
+}
+
+fn seed() -> u64 {
+    match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(duration) => duration.as_nanos() as u64,
+        Err(_) => 1337,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{net::UdpSocket, thread, time::Duration};
+
+    use korp_math::{Flint, Vec2};
+
+    use super::*;
+    use crate::ecs::{commands::SpawnKind, entities::Entity};
+
+    struct Node {
+        network: Network,
+        bus: Bus,
+        events: Vec<NetworkEvent>,
+    }
+
+    impl Node {
+        fn new(port: u16) -> Self {
+            Self {
+                network: Network::with_port(port),
+                bus: Bus::new(),
+                events: Vec::new(),
+            }
+        }
+
+        fn intent(&mut self, intent: NetworkIntent) {
+            self.network.event(&intent.into());
+        }
+
+        fn commands(&self, id: usize) -> Vec<(usize, String)> {
+            self.events
+                .iter()
+                .filter_map(|event| match event {
+                    NetworkEvent::Commands {
+                        id: from,
+                        tick,
+                        commands,
+                    } if *from == id => Some((*tick, format!("{:?}", commands))),
+                    _ => None,
+                })
+                .collect()
+        }
+    }
+
+    // a port nobody listens on right now
+    fn port() -> u16 {
+        let socket = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        socket.local_addr().unwrap().port()
+    }
+
+    fn until(nodes: &mut [&mut Node], done: impl Fn(&[&mut Node]) -> bool) {
+        for _ in 0..2000 {
+            for node in nodes.iter_mut() {
+                node.network.update(&mut node.bus);
+
+                for event in node.bus.drain() {
+                    if let Event::Network(IntentEvent::Event(event)) = event {
+                        assert!(!matches!(event, NetworkEvent::Error(_)), "{:?}", event);
+                        node.events.push(event);
+                    }
+                }
+            }
+
+            if done(nodes) {
+                return;
+            }
+
+            thread::sleep(Duration::from_millis(1));
+        }
+
+        panic!("timed out");
+    }
+
+    fn launch(host: &mut Node, client: &mut Node) -> usize {
+        host.intent(NetworkIntent::Host);
+        client.intent(NetworkIntent::Connect(IpAddr::V4(Ipv4Addr::LOCALHOST)));
+
+        until(&mut [host, client], |nodes| {
+            nodes[1]
+                .events
+                .iter()
+                .any(|event| matches!(event, NetworkEvent::Connected { .. }))
+        });
+
+        host.intent(NetworkIntent::Launch);
+
+        until(&mut [host, client], |nodes| {
+            nodes[1]
+                .events
+                .iter()
+                .any(|event| matches!(event, NetworkEvent::Launched { .. }))
+        });
+
+        let launched = |node: &Node| {
+            node.events.iter().find_map(|event| match event {
+                NetworkEvent::Launched { seed, delay, ids } => Some((*seed, *delay, ids.clone())),
+                _ => None,
+            })
+        };
+
+        let (seed, delay, ids) = launched(host).unwrap();
+        assert_eq!(launched(client), Some((seed, delay, ids.clone())));
+        assert_eq!(ids, vec![0, 1]);
+
+        delay
+    }
+
+    fn projectile(index: u32) -> Command {
+        let owner = Entity {
+            index,
+            generation: 7,
+        };
+
+        Command::Spawn {
+            id: Some(index as usize),
+            kind: SpawnKind::Projectile {
+                owner,
+                relative_speed: Flint::from_i16(3),
+                speed: Flint::from_i16(20),
+                damage: Flint::from_f64(2.5),
+                lifetime: 60,
+                centroid: Vec2::new(Flint::from_i16(-400), Flint::from_i16(300)),
+                rotation: Vec2::new(Flint::from_f64(0.6), Flint::from_f64(-0.8)),
+            },
+        }
+    }
+
+    #[test]
+    fn two_networks_exchange_commands_over_udp() {
+        let port = port();
+        let mut host = Node::new(port);
+        let mut client = Node::new(port);
+
+        let delay = launch(&mut host, &mut client);
+
+        assert!(
+            client
+                .events
+                .iter()
+                .any(|event| matches!(event, NetworkEvent::Connected { id: 1 }))
+        );
+
+        let entity = Entity {
+            index: 3,
+            generation: 1,
+        };
+
+        for tick in 0..10 {
+            host.intent(NetworkIntent::Commands {
+                tick,
+                commands: vec![Command::Accelerate(entity)],
+            });
+            client.intent(NetworkIntent::Commands {
+                tick,
+                commands: vec![Command::TurnLeft(entity), Command::Shoot(entity)],
+            });
+        }
+
+        until(&mut [&mut host, &mut client], |nodes| {
+            nodes[0].commands(1).len() == 10 && nodes[1].commands(0).len() == 10
+        });
+
+        for tick in 0..10 {
+            assert_eq!(
+                host.commands(1)[tick],
+                (
+                    tick + delay,
+                    format!("{:?}", [Command::TurnLeft(entity), Command::Shoot(entity)])
+                )
+            );
+            assert_eq!(
+                client.commands(0)[tick],
+                (tick + delay, format!("{:?}", [Command::Accelerate(entity)]))
+            );
+        }
+
+        client.intent(NetworkIntent::Disconnect);
+
+        until(&mut [&mut host, &mut client], |nodes| {
+            nodes[0]
+                .events
+                .iter()
+                .any(|event| matches!(event, NetworkEvent::Disconnected { id: 1 }))
+        });
+    }
+
+    #[test]
+    fn batches_fit_in_a_packet() {
+        let batch = Batch {
+            tick: usize::MAX,
+            commands: (0..64).map(|i| projectile(u32::MAX - i)).collect(),
+        };
+
+        let message = Message::Commands {
+            ack: usize::MAX,
+            batches: vec![batch; BATCHES_MAX],
+        };
+
+        let mut packet = Vec::new();
+        wire::encode(&message, &mut packet);
+
+        assert!(packet.len() <= PACKET_MAX, "{} bytes", packet.len());
+    }
+
+    #[test]
+    fn oversized_batches_are_split_across_packets() {
+        let port = port();
+        let mut host = Node::new(port);
+        let mut client = Node::new(port);
+
+        launch(&mut host, &mut client);
+
+        // a few of these add up to more than a packet can carry
+        let commands = (0..1500).map(projectile).collect::<Vec<_>>();
+        let mut packet = Vec::new();
+        wire::encode(
+            &Message::Commands {
+                ack: 0,
+                batches: vec![
+                    Batch {
+                        tick: 0,
+                        commands: commands.clone(),
+                    };
+                    4
+                ],
+            },
+            &mut packet,
+        );
+        assert!(packet.len() > PACKET_MAX);
+
+        // one tick per update like the game, unacknowledged batches pile up
+        for tick in 0..8 {
+            host.intent(NetworkIntent::Commands {
+                tick,
+                commands: commands.clone(),
+            });
+            client.intent(NetworkIntent::Commands {
+                tick,
+                commands: Vec::new(),
+            });
+
+            host.network.update(&mut host.bus);
+            client.network.update(&mut client.bus);
+        }
+
+        until(&mut [&mut host, &mut client], |nodes| {
+            nodes[1].commands(0).len() == 8
+        });
+
+        let expected = format!("{:?}", commands);
+        assert!(client.commands(0).iter().all(|(_, c)| *c == expected));
+    }
 }
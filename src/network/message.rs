@@ -0,0 +1,258 @@
+use std::net::SocketAddr;
+
+use crate::{
+    ecs::{checksum::Checksum, commands::Command},
+    wire::{Reader, Wire, WireError, Writer},
+};
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Hello,
+    Welcome { id: usize, host: usize },
+    Heartbeat,
+    Control { seq: u32, control: Control },
+    Acknowledge { seq: u32 },
+    Commands { ack: usize, batches: Vec<Batch> },
+    Disconnect,
+}
+
+#[derive(Debug, Clone)]
+pub enum Control {
+    Launch {
+        seed: u64,
+        delay: usize,
+        peers: Vec<(usize, Option<SocketAddr>)>,
+    },
+    Pause,
+    Resume,
+    Checksum {
+        tick: usize,
+        checksum: Checksum,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct Batch {
+    pub tick: usize,
+    pub commands: Vec<Command>,
+}
+
+impl Wire for Message {
+    fn write(&self, w: &mut Writer) {
+        match self {
+            Message::Hello => w.tag(0),
+            Message::Welcome { id, host } => {
+                w.tag(1);
+                id.write(w);
+                host.write(w);
+            }
+            Message::Heartbeat => w.tag(2),
+            Message::Control { seq, control } => {
+                w.tag(3);
+                seq.write(w);
+                control.write(w);
+            }
+            Message::Acknowledge { seq } => {
+                w.tag(4);
+                seq.write(w);
+            }
+            Message::Commands { ack, batches } => {
+                w.tag(5);
+                ack.write(w);
+                batches.write(w);
+            }
+            Message::Disconnect => w.tag(6),
+        }
+    }
+
+    fn read(r: &mut Reader) -> Result<Self, WireError> {
+        let message = match r.tag("message", 6)? {
+            0 => Message::Hello,
+            1 => Message::Welcome {
+                id: usize::read(r)?,
+                host: usize::read(r)?,
+            },
+            2 => Message::Heartbeat,
+            3 => Message::Control {
+                seq: u32::read(r)?,
+                control: Control::read(r)?,
+            },
+            4 => Message::Acknowledge { seq: u32::read(r)? },
+            5 => Message::Commands {
+                ack: usize::read(r)?,
+                batches: Vec::read(r)?,
+            },
+            _ => Message::Disconnect,
+        };
+
+        Ok(message)
+    }
+}
+
+impl Wire for Control {
+    fn write(&self, w: &mut Writer) {
+        match self {
+            Control::Launch { seed, delay, peers } => {
+                w.tag(0);
+                seed.write(w);
+                delay.write(w);
+                peers.write(w);
+            }
+            Control::Pause => w.tag(1),
+            Control::Resume => w.tag(2),
+            Control::Checksum { tick, checksum } => {
+                w.tag(3);
+                tick.write(w);
+                checksum.write(w);
+            }
+        }
+    }
+
+    fn read(r: &mut Reader) -> Result<Self, WireError> {
+        let control = match r.tag("control", 3)? {
+            0 => Control::Launch {
+                seed: u64::read(r)?,
+                delay: usize::read(r)?,
+                peers: Vec::read(r)?,
+            },
+            1 => Control::Pause,
+            2 => Control::Resume,
+            _ => Control::Checksum {
+                tick: usize::read(r)?,
+                checksum: Checksum::read(r)?,
+            },
+        };
+
+        Ok(control)
+    }
+}
+
+impl Wire for Batch {
+    fn write(&self, w: &mut Writer) {
+        self.tick.write(w);
+        self.commands.write(w);
+    }
+
+    fn read(r: &mut Reader) -> Result<Self, WireError> {
+        Ok(Batch {
+            tick: usize::read(r)?,
+            commands: Vec::read(r)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ecs::{checksum::Set, entities::Entity},
+        wire::{MAGIC, VERSION, assert_round_trip, decode, encode},
+    };
+
+    fn controls() -> Vec<Control> {
+        vec![
+            Control::Launch {
+                seed: u64::MAX,
+                delay: 2,
+                peers: vec![
+                    (0, None),
+                    (1, Some("192.168.1.20:50123".parse().unwrap())),
+                    (2, Some("[fe80::1]:7331".parse().unwrap())),
+                ],
+            },
+            Control::Pause,
+            Control::Resume,
+            Control::Checksum {
+                tick: 600,
+                checksum: Checksum {
+                    hashes: [0x0123_4567_89ab_cdef; Set::ALL.len()],
+                },
+            },
+        ]
+    }
+
+    #[test]
+    fn controls_round_trip() {
+        for control in controls() {
+            assert_round_trip(&control);
+        }
+    }
+
+    #[test]
+    fn messages_round_trip() {
+        let entity = Entity {
+            index: 4,
+            generation: 1,
+        };
+
+        let mut messages = vec![
+            Message::Hello,
+            Message::Welcome { id: 3, host: 0 },
+            Message::Heartbeat,
+            Message::Acknowledge { seq: u32::MAX },
+            Message::Commands {
+                ack: 0,
+                batches: Vec::new(),
+            },
+            Message::Commands {
+                ack: 1200,
+                batches: vec![
+                    Batch {
+                        tick: 1200,
+                        commands: Vec::new(),
+                    },
+                    Batch {
+                        tick: 1201,
+                        commands: vec![Command::Accelerate(entity), Command::Shoot(entity)],
+                    },
+                ],
+            },
+            Message::Disconnect,
+        ];
+
+        for (seq, control) in controls().into_iter().enumerate() {
+            messages.push(Message::Control {
+                seq: seq as u32,
+                control,
+            });
+        }
+
+        for message in messages.iter() {
+            assert_round_trip(message);
+        }
+    }
+
+    #[test]
+    fn malformed_messages_are_refused() {
+        let framed = |body: &[u8]| [&MAGIC[..], &[VERSION], body].concat();
+
+        assert_eq!(
+            decode::<Message>(&framed(&[7])).unwrap_err(),
+            WireError::InvalidTag {
+                kind: "message",
+                tag: 7
+            }
+        );
+        assert_eq!(
+            decode::<Message>(&framed(&[3, 0, 4])).unwrap_err(),
+            WireError::InvalidTag {
+                kind: "control",
+                tag: 4
+            }
+        );
+
+        // a batch count far beyond what follows
+        assert_eq!(
+            decode::<Message>(&framed(&[5, 0, 0xff, 0xff, 0x03, 0])).unwrap_err(),
+            WireError::UnexpectedEnd
+        );
+
+        let mut bytes = Vec::new();
+        encode(&Message::Welcome { id: 300, host: 0 }, &mut bytes);
+
+        assert_eq!(
+            decode::<Message>(&bytes[..bytes.len() - 1]).unwrap_err(),
+            WireError::UnexpectedEnd
+        );
+    }
+}
@@ -0,0 +1,20 @@
+use std::{io, net::SocketAddr};
+
+#[cfg(test)]
+mod loopback;
+mod udp;
+
+#[cfg(test)]
+pub use loopback::*;
+pub use udp::*;
+
+/// Moves datagrams between peers, the network doesn't care how.
+///
+/// Sending is fire-and-forget and receiving never blocks, anything the
+/// lockstep needs delivered is resent by the network until acknowledged.
+pub trait Transport {
+    fn open(&mut self, address: SocketAddr) -> io::Result<SocketAddr>;
+    fn close(&mut self);
+    fn send(&mut self, address: SocketAddr, bytes: &[u8]) -> io::Result<()>;
+    fn receive(&mut self, buffer: &mut [u8]) -> io::Result<Option<(usize, SocketAddr)>>;
+}
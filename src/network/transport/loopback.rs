@@ -0,0 +1,307 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    io::{self, ErrorKind},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    rc::Rc,
+};
+
+use korp_math::Random;
+
+use crate::network::transport::Transport;
+
+/// An in-memory medium connecting any number of [`LoopbackTransport`]s,
+/// which allows several networks to talk to each other in a single process.
+///
+/// Time only moves when [`Loopback::advance`] is called, so latency is
+/// measured in whatever the caller considers a tick. Every fault is drawn
+/// from a seeded [`Random`], running the same scenario twice delivers the
+/// same packets in the same order.
+#[derive(Clone)]
+pub struct Loopback {
+    medium: Rc<RefCell<Medium>>,
+}
+
+pub struct LoopbackTransport {
+    medium: Rc<RefCell<Medium>>,
+    address: Option<SocketAddr>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Faults {
+    pub seed: u64,
+    /// ticks every packet spends in flight
+    pub latency: u32,
+    /// additional random ticks in flight, up to and including this value
+    pub jitter: u32,
+    /// percentage of packets that never arrive
+    pub loss: u32,
+    /// percentage of packets held back so later packets overtake them
+    pub reorder: u32,
+}
+
+struct Medium {
+    faults: Faults,
+    random: Random,
+    time: u64,
+    seq: u64,
+    port: u16,
+    inboxes: HashMap<SocketAddr, Vec<Packet>>,
+}
+
+struct Packet {
+    arrival: u64,
+    seq: u64,
+    from: SocketAddr,
+    bytes: Vec<u8>,
+}
+
+impl Faults {
+    pub const NONE: Faults = Faults {
+        seed: 1337,
+        latency: 0,
+        jitter: 0,
+        loss: 0,
+        reorder: 0,
+    };
+}
+
+impl Loopback {
+    pub fn new() -> Self {
+        Self::with_faults(Faults::NONE)
+    }
+
+    pub fn with_faults(faults: Faults) -> Self {
+        Self {
+            medium: Rc::new(RefCell::new(Medium {
+                faults,
+                random: Random::new(faults.seed),
+                time: 0,
+                seq: 0,
+                port: 49152,
+                inboxes: HashMap::new(),
+            })),
+        }
+    }
+
+    pub fn transport(&self) -> LoopbackTransport {
+        LoopbackTransport {
+            medium: self.medium.clone(),
+            address: None,
+        }
+    }
+
+    pub fn advance(&self) {
+        self.medium.borrow_mut().time += 1;
+    }
+
+    /// Amount of packets still in flight, including those ready to be received.
+    pub fn in_flight(&self) -> usize {
+        self.medium.borrow().inboxes.values().map(Vec::len).sum()
+    }
+}
+
+impl Default for Loopback {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Medium {
+    fn chance(&mut self, percentage: u32) -> bool {
+        percentage > 0 && self.random.range(0, 100) < percentage as u64
+    }
+
+    fn ticks(&mut self, maximum: u32) -> u64 {
+        match maximum {
+            0 => 0,
+            _ => self.random.range(0, maximum as u64 + 1),
+        }
+    }
+}
+
+impl Transport for LoopbackTransport {
+    fn open(&mut self, address: SocketAddr) -> io::Result<SocketAddr> {
+        self.close();
+
+        let mut medium = self.medium.borrow_mut();
+
+        let ip = match address.ip() {
+            IpAddr::V4(ip) if ip.is_unspecified() => IpAddr::V4(Ipv4Addr::LOCALHOST),
+            IpAddr::V6(ip) if ip.is_unspecified() => IpAddr::V6(Ipv6Addr::LOCALHOST),
+            ip => ip,
+        };
+
+        let mut address = SocketAddr::new(ip, address.port());
+
+        if address.port() != 0 && medium.inboxes.contains_key(&address) {
+            return Err(ErrorKind::AddrInUse.into());
+        }
+
+        // mimic the operating system handing out a free ephemeral port
+        while address.port() == 0 || medium.inboxes.contains_key(&address) {
+            address.set_port(medium.port);
+            medium.port = medium.port.checked_add(1).unwrap_or(49152);
+        }
+
+        medium.inboxes.insert(address, Vec::new());
+        self.address = Some(address);
+
+        Ok(address)
+    }
+
+    fn close(&mut self) {
+        if let Some(address) = self.address.take() {
+            self.medium.borrow_mut().inboxes.remove(&address);
+        }
+    }
+
+    fn send(&mut self, address: SocketAddr, bytes: &[u8]) -> io::Result<()> {
+        let from = self.address.ok_or(ErrorKind::NotConnected)?;
+        let mut medium = self.medium.borrow_mut();
+        let faults = medium.faults;
+
+        if medium.chance(faults.loss) {
+            return Ok(());
+        }
+
+        let mut arrival = medium.time + faults.latency as u64 + medium.ticks(faults.jitter);
+
+        if medium.chance(faults.reorder) {
+            arrival += 1 + medium.ticks(faults.jitter);
+        }
+
+        let seq = medium.seq;
+        medium.seq += 1;
+
+        // like udp, sending to nobody is not an error
+        if let Some(inbox) = medium.inboxes.get_mut(&address) {
+            inbox.push(Packet {
+                arrival,
+                seq,
+                from,
+                bytes: bytes.to_vec(),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn receive(&mut self, buffer: &mut [u8]) -> io::Result<Option<(usize, SocketAddr)>> {
+        let address = self.address.ok_or(ErrorKind::NotConnected)?;
+        let mut medium = self.medium.borrow_mut();
+        let time = medium.time;
+
+        let Some(inbox) = medium.inboxes.get_mut(&address) else {
+            return Ok(None);
+        };
+
+        let Some(idx) = inbox
+            .iter()
+            .enumerate()
+            .filter(|(_, packet)| packet.arrival <= time)
+            .min_by_key(|(_, packet)| (packet.arrival, packet.seq))
+            .map(|(idx, _)| idx)
+        else {
+            return Ok(None);
+        };
+
+        let packet = inbox.remove(idx);
+
+        // like udp, whatever doesn't fit in the buffer is lost
+        let len = packet.bytes.len().min(buffer.len());
+        buffer[..len].copy_from_slice(&packet.bytes[..len]);
+
+        Ok(Some((len, packet.from)))
+    }
+}
+
+impl Drop for LoopbackTransport {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pair(loopback: &Loopback) -> (LoopbackTransport, LoopbackTransport, SocketAddr) {
+        let mut sender = loopback.transport();
+        let mut receiver = loopback.transport();
+
+        sender.open("0.0.0.0:0".parse().unwrap()).unwrap();
+        let address = receiver.open("0.0.0.0:7331".parse().unwrap()).unwrap();
+
+        (sender, receiver, address)
+    }
+
+    // the first byte of every packet received within the given ticks
+    fn deliver(faults: Faults, ticks: u32) -> Vec<u8> {
+        let loopback = Loopback::with_faults(faults);
+        let (mut sender, mut receiver, address) = pair(&loopback);
+        let mut buffer = [0; 8];
+        let mut received = Vec::new();
+
+        for i in 0..100 {
+            sender.send(address, &[i]).unwrap();
+        }
+
+        for _ in 0..ticks {
+            while let Some((len, _)) = receiver.receive(&mut buffer).unwrap() {
+                assert_eq!(len, 1);
+                received.push(buffer[0]);
+            }
+
+            loopback.advance();
+        }
+
+        received
+    }
+
+    #[test]
+    fn delivers_in_order_without_faults() {
+        assert_eq!(deliver(Faults::NONE, 1), (0..100).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn latency_holds_packets_back() {
+        let loopback = Loopback::with_faults(Faults {
+            latency: 3,
+            ..Faults::NONE
+        });
+        let (mut sender, mut receiver, address) = pair(&loopback);
+        let mut buffer = [0; 8];
+
+        sender.send(address, &[1, 2, 3]).unwrap();
+
+        for _ in 0..3 {
+            assert_eq!(receiver.receive(&mut buffer).unwrap(), None);
+            loopback.advance();
+        }
+
+        assert_eq!(loopback.in_flight(), 1);
+        assert_eq!(
+            receiver.receive(&mut buffer).unwrap(),
+            Some((3, sender.address.unwrap()))
+        );
+        assert_eq!(loopback.in_flight(), 0);
+    }
+
+    #[test]
+    fn faults_are_deterministic() {
+        let faults = Faults {
+            seed: 99,
+            latency: 1,
+            jitter: 4,
+            loss: 20,
+            reorder: 20,
+        };
+
+        let received = deliver(faults, 20);
+
+        assert_eq!(received, deliver(faults, 20));
+        assert!(received.len() < 100, "nothing was lost");
+        assert!(!received.is_sorted(), "nothing was reordered");
+    }
+}
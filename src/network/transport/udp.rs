@@ -0,0 +1,62 @@
+use std::{
+    io::{self, ErrorKind},
+    net::{SocketAddr, UdpSocket},
+};
+
+use crate::network::transport::Transport;
+
+pub struct Udp {
+    socket: Option<UdpSocket>,
+}
+
+impl Udp {
+    pub fn new() -> Self {
+        Self { socket: None }
+    }
+
+    fn socket(&self) -> io::Result<&UdpSocket> {
+        self.socket
+            .as_ref()
+            .ok_or_else(|| io::Error::from(ErrorKind::NotConnected))
+    }
+}
+
+impl Default for Udp {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Transport for Udp {
+    fn open(&mut self, address: SocketAddr) -> io::Result<SocketAddr> {
+        let socket = UdpSocket::bind(address)?;
+        socket.set_nonblocking(true)?;
+
+        let address = socket.local_addr()?;
+        self.socket = Some(socket);
+
+        Ok(address)
+    }
+
+    fn close(&mut self) {
+        self.socket = None;
+    }
+
+    fn send(&mut self, address: SocketAddr, bytes: &[u8]) -> io::Result<()> {
+        self.socket()?.send_to(bytes, address)?;
+
+        Ok(())
+    }
+
+    fn receive(&mut self, buffer: &mut [u8]) -> io::Result<Option<(usize, SocketAddr)>> {
+        loop {
+            match self.socket()?.recv_from(buffer) {
+                Ok(received) => return Ok(Some(received)),
+                Err(e) if e.kind() == ErrorKind::WouldBlock => return Ok(None),
+                // some platforms report unreachable peers as errors, the timeout handles those
+                Err(e) if e.kind() == ErrorKind::ConnectionReset => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
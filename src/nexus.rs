@@ -1,18 +1,24 @@
+use std::path::PathBuf;
+
 use korp_engine::{input::Input, renderer::Renderer};
 
 use crate::{
+    Options,
     bus::{
         Bus,
-        events::{Event, IntentEvent, NexusEvent, NexusIntent},
+        events::{Event, IntentEvent, NexusEvent, NexusIntent, ReplayEvent},
     },
-    nexus::{game::Game, lobby::Lobby, menu::Menu},
+    nexus::{game::Game, lobby::Lobby, menu::Menu, replay::Replay},
 };
 
 pub mod game;
 pub mod lobby;
 pub mod menu;
+pub mod replay;
+pub mod rig;
 
 pub struct Nexus {
+    options: Options,
     context: Context,
     actions: Vec<Action>,
 }
@@ -30,6 +36,9 @@ pub enum State {
         seed: u64,
         delay: usize,
     },
+    Replay {
+        path: PathBuf,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -37,23 +46,26 @@ pub enum Action {
     Transition(State),
 }
 
+#[allow(clippy::large_enum_variant)]
 enum Context {
     Menu(Menu),
     Lobby(Lobby),
     Game(Game),
+    Replay(Replay),
 }
 
 impl Nexus {
-    pub fn new() -> Self {
+    pub fn new(options: Options) -> Self {
         Self {
-            context: Context::Menu(Menu::new()),
+            context: Context::Menu(Menu::new(options.replay.clone())),
             actions: Vec::new(),
+            options,
         }
     }
 
     pub fn update(&mut self, bus: &mut Bus) {
         while let Some(action) = self.actions.pop() {
-            self.context.handle(action, bus);
+            self.context.handle(action, bus, &self.options);
         }
 
         self.context.update(bus);
@@ -83,22 +95,38 @@ impl Nexus {
 }
 
 impl Context {
-    fn handle(&mut self, action: Action, bus: &mut Bus) {
+    fn handle(&mut self, action: Action, bus: &mut Bus, options: &Options) {
         bus.send(NexusEvent::Action(action.clone()));
 
         match (&self, action) {
             (_, Action::Transition(state)) => {
-                *self = match state.clone() {
-                    State::Menu => Context::Menu(Menu::new()),
-                    State::Lobby { id, host } => Context::Lobby(Lobby::new(id, host)),
+                // the state we end up in, which is not the requested one when
+                // it could not be entered
+                let (context, state) = match state {
+                    State::Menu => (Context::Menu(Menu::new(options.replay.clone())), state),
+                    State::Lobby { id, host } => (Context::Lobby(Lobby::new(id, host)), state),
                     State::Game {
                         id,
-                        ids,
+                        ref ids,
                         seed,
                         delay,
-                    } => Context::Game(Game::new(id, ids, seed, delay)),
+                    } => (
+                        Context::Game(Game::new(id, ids.clone(), seed, delay, options)),
+                        state,
+                    ),
+                    State::Replay { ref path } => match Replay::new(path.clone()) {
+                        Ok(replay) => (Context::Replay(replay), state),
+                        Err(e) => {
+                            bus.send(ReplayEvent::Error(e));
+                            (
+                                Context::Menu(Menu::new(options.replay.clone())),
+                                State::Menu,
+                            )
+                        }
+                    },
                 };
 
+                *self = context;
                 bus.send(NexusEvent::Transitioned(state));
             }
         }
@@ -109,6 +137,7 @@ impl Context {
             Context::Menu(menu) => menu.update(bus),
             Context::Lobby(lobby) => lobby.update(bus),
             Context::Game(game) => game.update(bus),
+            Context::Replay(replay) => replay.update(bus),
         }
     }
 
@@ -117,6 +146,7 @@ impl Context {
             Context::Menu(menu) => menu.event(event),
             Context::Lobby(lobby) => lobby.event(event),
             Context::Game(game) => game.event(event),
+            Context::Replay(replay) => replay.event(event),
         }
     }
 
@@ -125,6 +155,7 @@ impl Context {
             Context::Menu(menu) => menu.render(renderer, alpha),
             Context::Lobby(lobby) => lobby.render(renderer, alpha),
             Context::Game(game) => game.render(renderer, alpha),
+            Context::Replay(replay) => replay.render(renderer, alpha),
         }
     }
 
@@ -133,6 +164,47 @@ impl Context {
             Context::Menu(menu) => menu.input(input),
             Context::Lobby(lobby) => lobby.input(input),
             Context::Game(game) => game.input(input),
+            Context::Replay(replay) => replay.input(input),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_replay_that_fails_to_load_transitions_to_the_menu() {
+        let mut nexus = Nexus::new(Options::default());
+        let mut bus = Bus::new();
+        let path = std::env::temp_dir().join(format!("korp-missing-{}", std::process::id()));
+
+        nexus.event(&Event::Nexus(IntentEvent::Intent(NexusIntent::Transition(
+            State::Replay { path },
+        ))));
+        nexus.update(&mut bus);
+
+        let events = bus.drain();
+
+        assert!(matches!(nexus.context, Context::Menu(_)));
+        assert!(
+            events
+                .iter()
+                .any(|event| matches!(event, Event::Replay(ReplayEvent::Error(_))))
+        );
+
+        let transitions = events
+            .iter()
+            .filter_map(|event| match event {
+                Event::Nexus(IntentEvent::Event(NexusEvent::Transitioned(state))) => Some(state),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        assert!(
+            matches!(transitions[..], [State::Menu]),
+            "{:?}",
+            transitions
+        );
+    }
+}
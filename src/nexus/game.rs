@@ -1,34 +1,37 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt::Write, fs, io, path::PathBuf};
 
 use korp_engine::{
+    CoreEvent,
     color::Color,
     input::{Input, KeyCode},
-    misc::Morph,
     renderer::{Camera, Renderer},
     shapes::Rectangle,
 };
-use korp_math::{Flint, Random, Vec2, lerp};
+use korp_math::{Flint, Random, Vec2};
 
 use crate::{
+    Options,
     bus::{
         Bus,
         events::{
             CosmosEvent, CosmosIntent, Event, GameEvent, IntentEvent, NetworkEvent, NetworkIntent,
+            NexusIntent,
         },
     },
     ecs::{
+        checksum::{Checksum, Dump, Set},
         commands::{Command, SpawnKind},
         cosmos::{Configure, Cosmos, Toggle},
         entities::Entity,
         tracker::Track,
     },
+    nexus::{self, replay::Record, rig::Rig},
 };
 
 pub struct Game {
     random: Random,
     cosmos: Cosmos,
-    camera: Camera,
-    camera_target: Morph<Vec2<f32>>,
+    rig: Rig,
     keybindings: KeyBindings,
     state: State,
     actions: Vec<Action>,
@@ -51,16 +54,30 @@ pub enum Action {
     Paused,
     Resume,
     Resumed,
+    SaveReplay,
+    Exit,
+    ReportDesync {
+        tick: usize,
+        id: usize,
+        ours: Checksum,
+        theirs: Checksum,
+        dump: Option<Dump>,
+    },
 }
 
 struct Data {
     id: usize,
+    seed: u64,
     pid: Option<Entity>,
     ids: Vec<usize>,
     id_idx: HashMap<usize, usize>,
     tick: usize,
     commands: Vec<Command>,
     commands_history: Vec<Vec<Vec<Command>>>,
+    // which slots delivered their commands, by tick
+    commands_received: Vec<Vec<bool>>,
+    desyncs: Option<PathBuf>,
+    replay: Option<PathBuf>,
 }
 
 struct KeyBindings {
@@ -73,27 +90,36 @@ struct KeyBindings {
     toggle_draw_hitbox: KeyCode,
     triangle: KeyCode,
     rectangle: KeyCode,
+    circle: KeyCode,
+    polygon: KeyCode,
     pause: KeyCode,
     shoot: KeyCode,
+    cycle_weapon: KeyCode,
+    save_replay: KeyCode,
+    exit: KeyCode,
+    zoom_in: KeyCode,
+    zoom_out: KeyCode,
+    toggle_camera: KeyCode,
 }
 
-fn init_cosmos(
-    cosmos: &mut Cosmos,
-    commands_history: &mut Vec<Vec<Vec<Command>>>,
-    id_idx: &mut HashMap<usize, usize>,
-    ids: &[usize],
-    delay: usize,
-    spawn: Vec2<Flint>,
-) {
-    for tick in 0..delay {
-        commands_history.push(Vec::with_capacity(ids.len()));
-
-        for _ in 0..ids.len() {
-            commands_history[tick].push(Vec::new());
-        }
-    }
-
-    for (idx, id) in ids.iter().enumerate() {
+// trauma added to the camera when the player hits something
+const SHAKE_COLLISION: f32 = 0.6;
+
+/// The cosmos every match starts from, with the players about to spawn.
+pub fn genesis(ids: &[usize]) -> (Cosmos, Vec2<Flint>) {
+    let bounds = Rectangle {
+        x: Flint::new(50, 0),
+        y: Flint::new(40, 0),
+        width: Flint::new(1700, 0),
+        height: Flint::new(1400, 0),
+    };
+    let spawn = Vec2::new(
+        bounds.x + bounds.width / Flint::from_i16(2),
+        bounds.y + bounds.height / Flint::from_i16(2),
+    );
+    let mut cosmos = Cosmos::new(bounds);
+
+    for id in ids.iter() {
         cosmos.event(
             &(CosmosIntent::Command(Command::Spawn {
                 id: Some(*id),
@@ -101,50 +127,47 @@ fn init_cosmos(
             }))
             .into(),
         );
-
-        id_idx.insert(*id, idx);
     }
+
+    (cosmos, spawn)
 }
 
 impl Game {
-    pub fn new(id: usize, ids: Vec<usize>, seed: u64, delay: usize) -> Self {
-        let bounds = Rectangle {
-            x: Flint::new(50, 0),
-            y: Flint::new(40, 0),
-            width: Flint::new(1700, 0),
-            height: Flint::new(1400, 0),
-        };
-        let spawn = Vec2::new(
-            bounds.x + bounds.width / Flint::from_i16(2),
-            bounds.y + bounds.height / Flint::from_i16(2),
-        );
-        let mut cosmos = Cosmos::new(bounds);
-        let mut id_idx = HashMap::new();
+    pub fn new(id: usize, ids: Vec<usize>, seed: u64, delay: usize, options: &Options) -> Self {
+        let (mut cosmos, spawn) = genesis(&ids);
+        let rig = Rig::new(Camera::new(800.0, 600.0), cosmos.bounds(), spawn.into());
+        let id_idx = ids.iter().enumerate().map(|(idx, id)| (*id, idx)).collect();
         let mut commands_history = Vec::with_capacity(1024);
+        let mut commands_received = Vec::with_capacity(1024);
 
-        init_cosmos(
-            &mut cosmos,
-            &mut commands_history,
-            &mut id_idx,
-            &ids,
-            delay,
-            spawn,
-        );
+        // nobody can send commands for the first ticks, so pretend everyone did
+        for _ in 0..delay {
+            commands_history.push(vec![Vec::new(); ids.len()]);
+            commands_received.push(vec![true; ids.len()]);
+        }
+
+        // a report is only worth writing with the state that went wrong
+        if options.desyncs.is_some() {
+            cosmos.event(&(CosmosIntent::Configure(Configure::Dump(true))).into());
+        }
 
         Self {
             data: Data {
                 id,
+                seed,
                 pid: None,
                 ids,
                 id_idx,
                 tick: 0,
                 commands: Vec::new(),
                 commands_history,
+                commands_received,
+                desyncs: options.desyncs.clone(),
+                replay: options.replay.clone(),
             },
             random: Random::new(seed),
             cosmos,
-            camera: Camera::new(800.0, 600.0),
-            camera_target: Morph::one(spawn.into()),
+            rig,
             keybindings: KeyBindings {
                 up: KeyCode::ArrowUp,
                 down: KeyCode::ArrowDown,
@@ -155,8 +178,16 @@ impl Game {
                 toggle_draw_hitbox: KeyCode::F3,
                 triangle: KeyCode::Digit1,
                 rectangle: KeyCode::Digit2,
+                circle: KeyCode::Digit3,
+                polygon: KeyCode::Digit4,
                 pause: KeyCode::KeyP,
                 shoot: KeyCode::Space,
+                cycle_weapon: KeyCode::KeyQ,
+                save_replay: KeyCode::F5,
+                exit: KeyCode::KeyE,
+                zoom_in: KeyCode::Equal,
+                zoom_out: KeyCode::Minus,
+                toggle_camera: KeyCode::F4,
             },
             state: State::Running,
             actions: Vec::new(),
@@ -171,9 +202,14 @@ impl Game {
 
         self.state
             .update(bus, &mut self.data, &mut self.cosmos, &mut self.random);
+
+        // the camera keeps moving even when the simulation does not
+        self.rig.update();
     }
 
     pub fn input(&mut self, input: &Input) {
+        self.input_camera(input);
+
         match self.state {
             State::Running => self.input_running(input),
             State::Paused => self.input_paused(input),
@@ -182,6 +218,8 @@ impl Game {
     }
 
     pub fn render(&mut self, renderer: &mut Renderer, alpha: f32) {
+        let camera = self.rig.render(alpha);
+
         let alpha = match self.state {
             State::Running => {
                 self.alpha = alpha;
@@ -191,15 +229,8 @@ impl Game {
         };
 
         {
-            if matches!(self.state, State::Running) {
-                self.camera.reposition(Vec2::new(
-                    lerp(self.camera_target.old.x, self.camera_target.new.x, alpha),
-                    lerp(self.camera_target.old.y, self.camera_target.new.y, alpha),
-                ));
-            }
-
             // render cosmos using the camera
-            let scope = renderer.begin(&self.camera);
+            let scope = renderer.begin(camera);
             self.cosmos.render(scope.renderer, alpha);
         }
 
@@ -215,6 +246,11 @@ impl Game {
     pub fn event(&mut self, event: &Event) {
         self.cosmos.event(event);
 
+        if let Event::Core(CoreEvent::Resize { width, height }) = event {
+            self.rig.resize(*width as f32, *height as f32);
+            return;
+        }
+
         if let Event::Network(IntentEvent::Event(event)) = event {
             self.event_network(event);
             return;
@@ -222,7 +258,6 @@ impl Game {
 
         if let Event::Cosmos(IntentEvent::Event(event)) = event {
             self.event_cosmos(event);
-            return;
         }
     }
 
@@ -235,6 +270,21 @@ impl Game {
             NetworkEvent::Commands { id, tick, commands } => {
                 self.commands(id, tick, commands);
             }
+            NetworkEvent::Desynced {
+                tick,
+                id,
+                ours,
+                theirs,
+                dump,
+            } if self.data.desyncs.is_some() => {
+                self.actions.push(Action::ReportDesync {
+                    tick: *tick,
+                    id: *id,
+                    ours: *ours,
+                    theirs: *theirs,
+                    dump: dump.clone(),
+                });
+            }
             NetworkEvent::Paused => {
                 self.actions.push(Action::Paused);
             }
@@ -258,39 +308,54 @@ impl Game {
                     .event(&(CosmosIntent::Track(Track::Movement(*entity)).into()));
 
                 if let Some(body) = self.cosmos.components().logic.bodies.get(entity) {
-                    self.camera_target.old = body.old.centroid.into();
-                    self.camera_target.new = body.new.centroid.into();
+                    self.rig.snap(body.new.centroid.into());
                 }
             }
             CosmosEvent::TrackedDeath(entity) if Some(*entity) == self.data.pid => {
-                // when player is dead, set the new as the old to prevent wobbling
                 self.data.pid = None;
-                self.camera_target.old = self.camera_target.new;
+                self.rig.release();
             }
-            CosmosEvent::TrackedMovement { entity, centroid } if Some(*entity) == self.data.pid => {
-                self.camera_target.old = self.camera_target.new;
-                self.camera_target.new = (*centroid).into();
+            CosmosEvent::TrackedMovement {
+                entity,
+                centroid,
+                rotation,
+                velocity,
+            } if Some(*entity) == self.data.pid => {
+                self.rig
+                    .track((*centroid).into(), (*rotation).into(), (*velocity).into());
+            }
+            CosmosEvent::Collided { alpha, beta, .. }
+                if self
+                    .data
+                    .pid
+                    .is_some_and(|pid| pid == *alpha || pid == *beta) =>
+            {
+                self.rig.shake(SHAKE_COLLISION);
             }
             _ => (),
         }
     }
 
     fn commands(&mut self, id: &usize, tick: &usize, commands: &[Command]) {
+        let Some(&idx) = self.data.id_idx.get(id) else {
+            return;
+        };
+
         // ensure we can support the requested tick
-        if self.data.commands_history.len() == *tick {
+        let slots = self.data.id_idx.len();
+        while self.data.commands_history.len() <= *tick {
+            let len = self.data.commands_history.len().max(1) * 2;
+
             self.data
                 .commands_history
-                .resize_with(self.data.commands_history.len() * 2, || {
-                    let mut v = Vec::with_capacity(self.data.ids.len());
-                    v.push(Vec::new());
-                    v
-                });
+                .resize_with(len, || vec![Vec::new(); slots]);
+            self.data
+                .commands_received
+                .resize_with(len, || vec![false; slots]);
         }
 
-        let tick_commands = &mut self.data.commands_history[*tick];
-        let idx = self.data.id_idx[id];
-
-        tick_commands[idx] = Vec::from(commands);
+        self.data.commands_history[*tick][idx] = Vec::from(commands);
+        self.data.commands_received[*tick][idx] = true;
     }
 
     fn input_running(&mut self, input: &Input) {
@@ -298,6 +363,14 @@ impl Game {
             self.actions.push(Action::Pause);
         }
 
+        if input.is_pressed(&self.keybindings.save_replay) {
+            self.actions.push(Action::SaveReplay);
+        }
+
+        if input.is_pressed(&self.keybindings.exit) {
+            self.actions.push(Action::Exit);
+        }
+
         if input.is_pressed(&self.keybindings.toggle_draw_filled) {
             self.actions.push(Action::Toggle(Toggle::DrawFilled));
         }
@@ -314,10 +387,7 @@ impl Game {
             self.data.commands.push(Command::Spawn {
                 id: None,
                 kind: SpawnKind::Triangle {
-                    centroid: Vec2::new(
-                        Flint::from_i16(input.mouse.x as i16),
-                        Flint::from_i16(input.mouse.y as i16),
-                    ),
+                    centroid: self.mouse(input),
                 },
             });
         }
@@ -326,10 +396,25 @@ impl Game {
             self.data.commands.push(Command::Spawn {
                 id: None,
                 kind: SpawnKind::Rectangle {
-                    centroid: Vec2::new(
-                        Flint::from_i16(input.mouse.x as i16),
-                        Flint::from_i16(input.mouse.y as i16),
-                    ),
+                    centroid: self.mouse(input),
+                },
+            });
+        }
+
+        if input.is_pressed(&self.keybindings.circle) {
+            self.data.commands.push(Command::Spawn {
+                id: None,
+                kind: SpawnKind::Circle {
+                    centroid: self.mouse(input),
+                },
+            });
+        }
+
+        if input.is_pressed(&self.keybindings.polygon) {
+            self.data.commands.push(Command::Spawn {
+                id: None,
+                kind: SpawnKind::Polygon {
+                    centroid: self.mouse(input),
                 },
             });
         }
@@ -357,6 +442,36 @@ impl Game {
         if input.is_down(&self.keybindings.shoot) {
             self.data.commands.push(Command::Shoot(pid));
         }
+
+        if input.is_pressed(&self.keybindings.cycle_weapon) {
+            self.data.commands.push(Command::CycleWeapon(pid));
+        }
+    }
+
+    fn input_camera(&mut self, input: &Input) {
+        if input.is_pressed(&self.keybindings.toggle_camera) {
+            self.rig.toggle();
+        }
+
+        let mut steps = input.scroll;
+
+        if input.is_pressed(&self.keybindings.zoom_in) {
+            steps += 1.0;
+        }
+
+        if input.is_pressed(&self.keybindings.zoom_out) {
+            steps -= 1.0;
+        }
+
+        if steps != 0.0 {
+            self.rig.zoom(steps);
+        }
+    }
+
+    fn mouse(&self, input: &Input) -> Vec2<Flint> {
+        let mouse = self.rig.camera().screen_to_world(input.mouse);
+
+        Vec2::new(Flint::from_f32(mouse.x), Flint::from_f32(mouse.y))
     }
 
     fn input_stalling(&mut self, _input: &Input) {}
@@ -368,6 +483,64 @@ impl Game {
     }
 }
 
+impl Data {
+    fn save_replay(&self) -> io::Result<()> {
+        let Some(path) = &self.replay else {
+            return Ok(());
+        };
+
+        // slots are ordered by index, peers that left keep theirs
+        let mut ids = vec![0; self.id_idx.len()];
+        for (id, idx) in self.id_idx.iter() {
+            ids[*idx] = *id;
+        }
+
+        let record = Record {
+            seed: self.seed,
+            ids,
+            commands: self.commands_history[..self.tick].to_vec(),
+        };
+
+        record.save(path)
+    }
+
+    /// Writes which sets differ and our state of them, the peer writes its
+    /// own so both reports can be compared.
+    fn report_desync(
+        &self,
+        tick: usize,
+        id: usize,
+        ours: &Checksum,
+        theirs: &Checksum,
+        dump: Option<&Dump>,
+    ) -> io::Result<()> {
+        let Some(directory) = &self.desyncs else {
+            return Ok(());
+        };
+
+        let mut report = String::new();
+        let _ = writeln!(report, "{} desynced from {} at tick {}", self.id, id, tick);
+
+        for set in Set::ALL {
+            let (our, their) = (ours.hashes[set as usize], theirs.hashes[set as usize]);
+            let mark = if our != their { " differs" } else { "" };
+            let _ = writeln!(report, "{:?} {:016x} {:016x}{}", set, our, their, mark);
+        }
+
+        if let Some(dump) = dump {
+            for set in ours.differing(theirs) {
+                let _ = write!(report, "\n{:?}:\n{}", set, dump.get(set));
+            }
+        }
+
+        fs::create_dir_all(directory)?;
+        fs::write(
+            directory.join(format!("desync-{}-{}-{}.txt", tick, self.id, id)),
+            report,
+        )
+    }
+}
+
 impl State {
     fn handle(&mut self, action: Action, bus: &mut Bus, data: &mut Data) {
         bus.send(GameEvent::Action(action.clone()));
@@ -392,6 +565,34 @@ impl State {
             (_, Action::Toggle(toggle)) => {
                 bus.send(CosmosIntent::Configure(Configure::Toggle(toggle)));
             }
+            (_, Action::SaveReplay) => {
+                if let Err(e) = data.save_replay() {
+                    bus.send(GameEvent::Error(e));
+                }
+            }
+            // the match ends for us, it is recorded up to the last simulated tick
+            (_, Action::Exit) => {
+                if let Err(e) = data.save_replay() {
+                    bus.send(GameEvent::Error(e));
+                }
+
+                bus.send(NetworkIntent::Disconnect);
+                bus.send(NexusIntent::Transition(nexus::State::Menu));
+            }
+            (
+                _,
+                Action::ReportDesync {
+                    tick,
+                    id,
+                    ours,
+                    theirs,
+                    dump,
+                },
+            ) => {
+                if let Err(e) = data.report_desync(tick, id, &ours, &theirs, dump.as_ref()) {
+                    bus.send(GameEvent::Error(e));
+                }
+            }
             (_, _) => (),
         }
     }
@@ -409,7 +610,7 @@ impl State {
             commands: std::mem::take(&mut data.commands),
         });
 
-        cosmos.update(bus, random, &data.commands_history[data.tick]);
+        cosmos.update(bus, random, data.tick, &data.commands_history[data.tick]);
         data.tick += 1;
     }
 
@@ -424,7 +625,10 @@ impl State {
             return;
         }
 
-        let has_commands = data.commands_history[data.tick].len() >= data.ids.len();
+        // peers that left no longer hold us up, everyone else has to have
+        // delivered, counting would let a departed peer stand in for another
+        let received = &data.commands_received[data.tick];
+        let has_commands = data.ids.iter().all(|id| received[data.id_idx[id]]);
         if !has_commands {
             if !matches!(self, State::Stalling) {
                 self.handle(Action::Transition(State::Stalling), bus, data);
@@ -438,3 +642,35 @@ impl State {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn desyncs_are_reported_to_a_file() {
+        let directory = std::env::temp_dir().join(format!("korp-desyncs-{}", std::process::id()));
+        let options = Options {
+            desyncs: Some(directory.clone()),
+            replay: None,
+        };
+        let game = Game::new(1, vec![1, 2], 7, 3, &options);
+
+        let ours = Checksum {
+            hashes: [1; Set::ALL.len()],
+        };
+        let mut theirs = ours;
+        theirs.hashes[Set::Motions as usize] = 2;
+
+        game.data
+            .report_desync(60, 2, &ours, &theirs, None)
+            .unwrap();
+
+        let report = fs::read_to_string(directory.join("desync-60-1-2.txt")).unwrap();
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert!(report.starts_with("1 desynced from 2 at tick 60"));
+        assert_eq!(report.matches("differs").count(), 1);
+        assert!(report.contains("Motions 0000000000000001 0000000000000002 differs"));
+    }
+}
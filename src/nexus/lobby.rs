@@ -30,7 +30,11 @@ pub enum State {
 pub enum Action {
     Transition(State),
     Launch,
-    Launched { seed: u64, delay: usize },
+    Launched {
+        seed: u64,
+        delay: usize,
+        ids: Vec<usize>,
+    },
     Leave,
 }
 
@@ -62,47 +66,44 @@ impl Lobby {
 
     pub fn update(&mut self, bus: &mut Bus) {
         while let Some(action) = self.actions.pop() {
-            self.state.handle(action, bus, &self.data);
+            self.state.handle(action, bus, &mut self.data);
         }
 
-        self.state.update(bus, &self.data);
+        self.state.update(bus, &mut self.data);
     }
 
     pub fn input(&mut self, input: &Input) {
-        match self.state {
-            State::Idle { .. } => {
-                if input.is_pressed(&self.keybindings.start) && self.host {
-                    self.actions.push(Action::Launch);
-                }
+        if let State::Idle = self.state {
+            if input.is_pressed(&self.keybindings.start) && self.host {
+                self.actions.push(Action::Launch);
+            }
 
-                if input.is_pressed(&self.keybindings.exit) {
-                    self.actions.push(Action::Leave);
-                }
+            if input.is_pressed(&self.keybindings.exit) {
+                self.actions.push(Action::Leave);
             }
-            _ => (),
         }
     }
 
     pub fn render(&mut self, _renderer: &mut Renderer, _alpha: f32) {}
 
     pub fn event(&mut self, event: &Event) {
-        match (&self.state, event) {
-            (
-                State::LaunchAwait { .. },
-                Event::Network(IntentEvent::Event(NetworkEvent::Launched { seed, delay })),
-            ) => {
-                self.actions.push(Action::Launched {
-                    seed: *seed,
-                    delay: *delay,
-                });
-            }
-            _ => (),
+        // clients are launched by the host, so they never await it
+        if let (
+            State::Idle | State::LaunchAwait { .. },
+            Event::Network(IntentEvent::Event(NetworkEvent::Launched { seed, delay, ids })),
+        ) = (&self.state, event)
+        {
+            self.actions.push(Action::Launched {
+                seed: *seed,
+                delay: *delay,
+                ids: ids.clone(),
+            });
         }
     }
 }
 
 impl State {
-    fn handle(&mut self, action: Action, bus: &mut Bus, data: &Data) {
+    fn handle(&mut self, action: Action, bus: &mut Bus, data: &mut Data) {
         bus.send(LobbyEvent::Action(action.clone()));
 
         match (&self, action) {
@@ -125,7 +126,9 @@ impl State {
                     data,
                 );
             }
-            (State::LaunchAwait { .. }, Action::Launched { seed, delay }) => {
+            (State::Idle | State::LaunchAwait { .. }, Action::Launched { seed, delay, ids }) => {
+                data.ids = ids;
+
                 bus.send(NexusIntent::Transition(nexus::State::Game {
                     id: data.id,
                     ids: data.ids.clone(),
@@ -147,7 +150,7 @@ impl State {
         }
     }
 
-    fn update(&mut self, bus: &mut Bus, data: &Data) {
+    fn update(&mut self, bus: &mut Bus, data: &mut Data) {
         match self {
             State::LaunchAwait { counter } | State::ExitAwait { counter } => {
                 *counter += 1;
@@ -1,4 +1,7 @@
-use std::net::{IpAddr, Ipv4Addr};
+use std::{
+    net::{IpAddr, Ipv4Addr},
+    path::PathBuf,
+};
 
 use korp_engine::{
     input::{Input, KeyCode},
@@ -14,6 +17,7 @@ use crate::{
 };
 
 pub struct Menu {
+    replay: Option<PathBuf>,
     actions: Vec<Action>,
     state: State,
     keybindings: KeyBindings,
@@ -33,23 +37,27 @@ pub enum Action {
     Hosted { id: usize },
     Connect,
     Connected { id: usize },
+    Replay(PathBuf),
 }
 
 struct KeyBindings {
     host: KeyCode,
     connect: KeyCode,
+    replay: KeyCode,
 }
 
 const TIMEOUT: u8 = 12;
 
 impl Menu {
-    pub fn new() -> Self {
+    pub fn new(replay: Option<PathBuf>) -> Self {
         Self {
+            replay,
             actions: Vec::new(),
             state: State::Idle,
             keybindings: KeyBindings {
                 host: KeyCode::KeyH,
                 connect: KeyCode::KeyC,
+                replay: KeyCode::KeyR,
             },
         }
     }
@@ -63,17 +71,20 @@ impl Menu {
     }
 
     pub fn input(&mut self, input: &Input) {
-        match self.state {
-            State::Idle => {
-                if input.is_pressed(&self.keybindings.host) {
-                    self.actions.push(Action::Host);
-                }
+        if let State::Idle = self.state {
+            if input.is_pressed(&self.keybindings.host) {
+                self.actions.push(Action::Host);
+            }
 
-                if input.is_pressed(&self.keybindings.connect) {
-                    self.actions.push(Action::Connect);
-                }
+            if input.is_pressed(&self.keybindings.connect) {
+                self.actions.push(Action::Connect);
+            }
+
+            if let Some(path) = &self.replay
+                && input.is_pressed(&self.keybindings.replay)
+            {
+                self.actions.push(Action::Replay(path.clone()));
             }
-            _ => (),
         }
     }
 
@@ -111,6 +122,11 @@ impl State {
                 bus.send(NetworkIntent::Connect(IpAddr::V4(Ipv4Addr::LOCALHOST)));
                 self.handle(Action::Transition(State::ConnectAwait { counter: 0 }), bus)
             }
+            (State::Idle, Action::Replay(path)) => {
+                bus.send(NexusIntent::Transition(nexus::State::Replay { path }));
+
+                self.handle(Action::Transition(State::ExitAwait { counter: 0 }), bus)
+            }
             (State::ConnectAwait { .. }, Action::Connected { id }) => {
                 bus.send(NexusIntent::Transition(nexus::State::Lobby {
                     id,
@@ -0,0 +1,398 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use korp_engine::{
+    CoreEvent,
+    input::{Input, KeyCode},
+    renderer::{Camera, Renderer},
+};
+use korp_math::Random;
+
+use crate::{
+    bus::{
+        Bus,
+        events::{Event, NexusIntent, ReplayEvent},
+    },
+    ecs::{commands::Command, cosmos::Cosmos},
+    nexus::{self, game, rig::Rig},
+    wire::{self, Reader, Wire, WireError, Writer},
+};
+
+// how many ticks are simulated per update at most when fast-forwarding
+const SPEED_MAXIMUM: usize = 8;
+
+// zoom steps taken at the start, enough to see the whole cosmos
+const ZOOM_OVERVIEW: f32 = -4.0;
+
+/// Everything needed to reproduce a match, the simulation does the rest.
+#[derive(Debug, Clone)]
+pub struct Record {
+    pub seed: u64,
+    pub ids: Vec<usize>,
+    pub commands: Vec<Vec<Vec<Command>>>,
+}
+
+pub struct Replay {
+    data: Data,
+    rig: Rig,
+    state: State,
+    actions: Vec<Action>,
+    keybindings: KeyBindings,
+    alpha: f32,
+}
+
+#[derive(Debug, Clone)]
+pub enum State {
+    Playing { speed: usize },
+    Paused,
+    Finished,
+}
+
+#[derive(Debug, Clone)]
+pub enum Action {
+    Transition(State),
+    Pause,
+    Resume,
+    Step,
+    FastForward,
+    Restart,
+    Exit,
+}
+
+struct Data {
+    record: Record,
+    random: Random,
+    cosmos: Cosmos,
+    tick: usize,
+}
+
+struct KeyBindings {
+    pause: KeyCode,
+    step: KeyCode,
+    fast_forward: KeyCode,
+    restart: KeyCode,
+    exit: KeyCode,
+}
+
+impl Record {
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let bytes = fs::read(path)?;
+
+        wire::decode(&bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut bytes = Vec::new();
+        wire::encode(self, &mut bytes);
+
+        fs::write(path, bytes)
+    }
+}
+
+impl Replay {
+    pub fn new(path: PathBuf) -> io::Result<Self> {
+        let record = Record::load(&path)?;
+        let (cosmos, spawn) = game::genesis(&record.ids);
+
+        let mut rig = Rig::new(Camera::new(800.0, 600.0), cosmos.bounds(), spawn.into());
+        rig.zoom(ZOOM_OVERVIEW);
+
+        Ok(Self {
+            data: Data {
+                random: Random::new(record.seed),
+                record,
+                cosmos,
+                tick: 0,
+            },
+            rig,
+            state: State::Playing { speed: 1 },
+            actions: Vec::new(),
+            keybindings: KeyBindings {
+                pause: KeyCode::KeyP,
+                step: KeyCode::KeyN,
+                fast_forward: KeyCode::KeyF,
+                restart: KeyCode::KeyR,
+                exit: KeyCode::KeyE,
+            },
+            alpha: 0.0,
+        })
+    }
+
+    pub fn update(&mut self, bus: &mut Bus) {
+        while let Some(action) = self.actions.pop() {
+            self.state.handle(action, bus, &mut self.data);
+        }
+
+        self.state.update(bus, &mut self.data);
+        self.rig.update();
+    }
+
+    pub fn input(&mut self, input: &Input) {
+        if input.scroll != 0.0 {
+            self.rig.zoom(input.scroll);
+        }
+
+        if input.is_pressed(&self.keybindings.restart) {
+            self.actions.push(Action::Restart);
+        }
+
+        if input.is_pressed(&self.keybindings.exit) {
+            self.actions.push(Action::Exit);
+        }
+
+        match self.state {
+            State::Playing { .. } => {
+                if input.is_pressed(&self.keybindings.pause) {
+                    self.actions.push(Action::Pause);
+                }
+
+                if input.is_pressed(&self.keybindings.fast_forward) {
+                    self.actions.push(Action::FastForward);
+                }
+            }
+            State::Paused => {
+                if input.is_pressed(&self.keybindings.pause) {
+                    self.actions.push(Action::Resume);
+                }
+
+                if input.is_pressed(&self.keybindings.step) {
+                    self.actions.push(Action::Step);
+                }
+            }
+            State::Finished => (),
+        }
+    }
+
+    pub fn render(&mut self, renderer: &mut Renderer, alpha: f32) {
+        let camera = self.rig.render(alpha);
+
+        let alpha = match self.state {
+            State::Playing { .. } => {
+                self.alpha = alpha;
+                alpha
+            }
+            State::Paused | State::Finished => self.alpha,
+        };
+
+        let scope = renderer.begin(camera);
+        self.data.cosmos.render(scope.renderer, alpha);
+    }
+
+    pub fn event(&mut self, event: &Event) {
+        self.data.cosmos.event(event);
+
+        if let Event::Core(CoreEvent::Resize { width, height }) = event {
+            self.rig.resize(*width as f32, *height as f32);
+        }
+    }
+}
+
+impl Data {
+    fn restart(&mut self) {
+        self.cosmos = game::genesis(&self.record.ids).0;
+        self.random = Random::new(self.record.seed);
+        self.tick = 0;
+    }
+
+    fn advance(&mut self, bus: &mut Bus) -> bool {
+        let Some(commands) = self.record.commands.get(self.tick) else {
+            return false;
+        };
+
+        self.cosmos
+            .update(bus, &mut self.random, self.tick, commands);
+        self.tick += 1;
+
+        true
+    }
+}
+
+impl State {
+    fn handle(&mut self, action: Action, bus: &mut Bus, data: &mut Data) {
+        bus.send(ReplayEvent::Action(action.clone()));
+
+        match (&self, action) {
+            (State::Playing { .. }, Action::Pause) => {
+                self.handle(Action::Transition(State::Paused), bus, data);
+            }
+            (State::Playing { speed }, Action::FastForward) => {
+                let speed = match *speed * 2 {
+                    speed if speed > SPEED_MAXIMUM => 1,
+                    speed => speed,
+                };
+
+                self.handle(Action::Transition(State::Playing { speed }), bus, data);
+            }
+            (State::Paused, Action::Resume) => {
+                self.handle(Action::Transition(State::Playing { speed: 1 }), bus, data);
+            }
+            (State::Paused, Action::Step) => {
+                if !data.advance(bus) {
+                    self.handle(Action::Transition(State::Finished), bus, data);
+                }
+            }
+            (_, Action::Restart) => {
+                data.restart();
+                self.handle(Action::Transition(State::Playing { speed: 1 }), bus, data);
+            }
+            (_, Action::Exit) => {
+                bus.send(NexusIntent::Transition(nexus::State::Menu));
+            }
+            (_, Action::Transition(state)) => {
+                *self = state;
+                bus.send(ReplayEvent::Transitioned(self.clone()));
+            }
+            (_, _) => (),
+        }
+    }
+
+    fn update(&mut self, bus: &mut Bus, data: &mut Data) {
+        let State::Playing { speed } = *self else {
+            return;
+        };
+
+        for _ in 0..speed {
+            if !data.advance(bus) {
+                self.handle(Action::Transition(State::Finished), bus, data);
+                return;
+            }
+        }
+    }
+}
+
+impl Wire for Record {
+    fn write(&self, w: &mut Writer) {
+        self.seed.write(w);
+        self.ids.write(w);
+        self.commands.write(w);
+    }
+
+    fn read(r: &mut Reader) -> Result<Self, WireError> {
+        Ok(Record {
+            seed: u64::read(r)?,
+            ids: Vec::read(r)?,
+            commands: Vec::read(r)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process};
+
+    use super::*;
+    use crate::{
+        bus::events::{IntentEvent, NetworkIntent},
+        ecs::{
+            checksum::{CHECKSUM_INTERVAL, Checksum},
+            entities::Entity,
+        },
+    };
+
+    fn record() -> Record {
+        let pilot = Entity {
+            index: 0,
+            generation: 0,
+        };
+
+        let commands = (0..240)
+            .map(|tick| {
+                let commands = match tick % 4 {
+                    0 => vec![Command::Accelerate(pilot), Command::Shoot(pilot)],
+                    1 => vec![Command::TurnLeft(pilot)],
+                    _ => Vec::new(),
+                };
+
+                vec![commands, Vec::new()]
+            })
+            .collect();
+
+        Record {
+            seed: 7,
+            ids: vec![1, 2],
+            commands,
+        }
+    }
+
+    fn path(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("korp-{}-{}.korp", name, process::id()))
+    }
+
+    fn checksums(bus: &mut Bus) -> Vec<(usize, Checksum)> {
+        bus.drain()
+            .into_iter()
+            .filter_map(|event| match event {
+                Event::Network(IntentEvent::Intent(NetworkIntent::Checksum {
+                    tick,
+                    checksum,
+                    ..
+                })) => Some((tick, checksum)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn records_round_trip() {
+        wire::assert_round_trip(&record());
+        wire::assert_round_trip(&Record {
+            seed: 0,
+            ids: Vec::new(),
+            commands: Vec::new(),
+        });
+    }
+
+    #[test]
+    fn records_are_saved_and_loaded() {
+        let path = path("saved");
+        let record = record();
+
+        record.save(&path).unwrap();
+        let loaded = Record::load(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(format!("{:?}", loaded.unwrap()), format!("{:?}", record));
+    }
+
+    #[test]
+    fn malformed_records_are_rejected() {
+        let path = path("malformed");
+        let mut bytes = Vec::new();
+        wire::encode(&record(), &mut bytes);
+        bytes.truncate(bytes.len() / 2);
+
+        fs::write(&path, &bytes).unwrap();
+        let loaded = Record::load(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn replays_reproduce_the_match() {
+        let record = record();
+        let mut bus = Bus::new();
+
+        // the match as it was played
+        let (mut cosmos, _) = game::genesis(&record.ids);
+        let mut random = Random::new(record.seed);
+        for (tick, commands) in record.commands.iter().enumerate() {
+            cosmos.update(&mut bus, &mut random, tick, commands);
+        }
+        let played = checksums(&mut bus);
+
+        let path = path("replayed");
+        record.save(&path).unwrap();
+        let replay = Replay::new(path.clone());
+        fs::remove_file(&path).unwrap();
+
+        let mut replay = replay.unwrap();
+        while replay.data.advance(&mut bus) {}
+        let replayed = checksums(&mut bus);
+
+        assert_eq!(played.len(), record.commands.len() / CHECKSUM_INTERVAL);
+        assert_eq!(played, replayed);
+    }
+}
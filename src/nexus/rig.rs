@@ -0,0 +1,259 @@
+use std::f32::consts::FRAC_PI_2;
+
+use korp_engine::{misc::Morph, renderer::Camera, shapes::Rectangle};
+use korp_math::{Flint, Random, Vec2, lerp, lerp_angle};
+
+// fraction of the remaining distance covered every tick
+const FOLLOW_DAMPING: f32 = 0.1;
+const ROTATION_DAMPING: f32 = 0.08;
+const ZOOM_DAMPING: f32 = 0.2;
+
+// how many ticks of velocity the camera looks ahead of its target
+const LOOK_AHEAD: f32 = 30.0;
+
+const ZOOM_STEP: f32 = 1.25;
+const ZOOM_MINIMUM: f32 = 0.25;
+const ZOOM_MAXIMUM: f32 = 4.0;
+
+// displacement at full trauma, shaking scales with trauma squared
+const SHAKE_MAXIMUM: f32 = 12.0;
+const SHAKE_DECAY: f32 = 0.04;
+
+/// Drives a camera around the cosmos, everything is smoothed per tick
+/// and interpolated when rendering.
+pub struct Rig {
+    // where the newest tick is looked at from, without shaking
+    camera: Camera,
+    // what is rendered, interpolated between ticks and shaken
+    view: Camera,
+    bounds: Rectangle<f32>,
+    mode: Mode,
+    target: Target,
+    position: Morph<Vec2<f32>>,
+    rotation: Morph<f32>,
+    zoom: Morph<f32>,
+    zoom_target: f32,
+    shake: Morph<Vec2<f32>>,
+    trauma: f32,
+    // purely cosmetic, must never be shared with the simulation
+    random: Random,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    // up is always up
+    Fixed,
+    // the heading of the target points up
+    Heading,
+}
+
+struct Target {
+    centroid: Vec2<f32>,
+    heading: f32,
+    velocity: Vec2<f32>,
+}
+
+impl Rig {
+    pub fn new(camera: Camera, bounds: Rectangle<Flint>, centroid: Vec2<f32>) -> Self {
+        let mut rig = Self {
+            view: camera.clone(),
+            camera,
+            bounds: bounds.into(),
+            mode: Mode::Fixed,
+            target: Target {
+                centroid,
+                heading: -FRAC_PI_2,
+                velocity: Vec2::new(0.0, 0.0),
+            },
+            position: Morph::one(centroid),
+            rotation: Morph::one(0.0),
+            zoom: Morph::one(1.0),
+            zoom_target: 1.0,
+            shake: Morph::one(Vec2::new(0.0, 0.0)),
+            trauma: 0.0,
+            random: Random::new(0x5eed),
+        };
+
+        rig.snap(centroid);
+        rig
+    }
+
+    /// Where the newest tick is looked at from, never shaken so whatever is
+    /// mapped through it lands where the player aimed.
+    pub fn camera(&self) -> &Camera {
+        &self.camera
+    }
+
+    pub fn resize(&mut self, width: f32, height: f32) {
+        self.camera.resize(width, height);
+    }
+
+    /// Follows the target from where it is now, without easing into it.
+    pub fn snap(&mut self, centroid: Vec2<f32>) {
+        self.target.centroid = centroid;
+        self.target.velocity = Vec2::new(0.0, 0.0);
+
+        let position = self.clamp(centroid, self.zoom.new, self.rotation.new);
+        self.position = Morph::one(position);
+        self.place();
+    }
+
+    pub fn track(&mut self, centroid: Vec2<f32>, rotation: Vec2<f32>, velocity: Vec2<f32>) {
+        self.target = Target {
+            centroid,
+            heading: rotation.angle(),
+            velocity,
+        };
+    }
+
+    /// Stops looking ahead, the camera settles where the target was last seen.
+    pub fn release(&mut self) {
+        self.target.velocity = Vec2::new(0.0, 0.0);
+    }
+
+    pub fn toggle(&mut self) {
+        self.mode = match self.mode {
+            Mode::Fixed => Mode::Heading,
+            Mode::Heading => Mode::Fixed,
+        };
+    }
+
+    /// Zooms in by the given amount of steps, negative zooms out.
+    pub fn zoom(&mut self, steps: f32) {
+        self.zoom_target =
+            (self.zoom_target * ZOOM_STEP.powf(steps)).clamp(ZOOM_MINIMUM, ZOOM_MAXIMUM);
+    }
+
+    /// Adds trauma, where 1 is the most violent shaking.
+    pub fn shake(&mut self, trauma: f32) {
+        self.trauma = (self.trauma + trauma).min(1.0);
+    }
+
+    pub fn update(&mut self) {
+        let zoom = lerp(self.zoom.new, self.zoom_target, ZOOM_DAMPING);
+        self.zoom = Morph::new(self.zoom.new, zoom);
+
+        let heading = match self.mode {
+            Mode::Fixed => 0.0,
+            Mode::Heading => self.target.heading + FRAC_PI_2,
+        };
+        let rotation = lerp_angle(self.rotation.new, heading, ROTATION_DAMPING);
+        self.rotation = Morph::new(self.rotation.new, rotation);
+
+        let look = self.target.centroid + self.target.velocity * LOOK_AHEAD;
+        let position = self.position.new + (look - self.position.new) * FOLLOW_DAMPING;
+        let position = self.clamp(position, zoom, rotation);
+        self.position = Morph::new(self.position.new, position);
+
+        let shake = if self.trauma > 0.0 {
+            let magnitude = SHAKE_MAXIMUM * self.trauma * self.trauma;
+            self.trauma = (self.trauma - SHAKE_DECAY).max(0.0);
+
+            Vec2::new(self.unit(), self.unit()) * magnitude
+        } else {
+            Vec2::new(0.0, 0.0)
+        };
+        self.shake = Morph::new(self.shake.new, shake);
+
+        self.place();
+    }
+
+    pub fn render(&mut self, alpha: f32) -> &Camera {
+        let position = self.position.old.lerp(&self.position.new, alpha);
+        let shake = self.shake.old.lerp(&self.shake.new, alpha);
+
+        self.view.clone_from(&self.camera);
+        self.view.reposition(position);
+        self.view.displace(shake);
+        self.view.zoom(lerp(self.zoom.old, self.zoom.new, alpha));
+        self.view
+            .rotate(lerp_angle(self.rotation.old, self.rotation.new, alpha));
+
+        &self.view
+    }
+
+    // input is mapped through the camera between renders, or without them at all
+    fn place(&mut self) {
+        self.camera.reposition(self.position.new);
+        self.camera.zoom(self.zoom.new);
+        self.camera.rotate(self.rotation.new);
+    }
+
+    // keeps the view inside the bounds, or centered when it is larger than them
+    fn clamp(&self, position: Vec2<f32>, zoom: f32, rotation: f32) -> Vec2<f32> {
+        let view = self.camera.size() * (0.5 / zoom);
+        let (sin, cos) = rotation.sin_cos();
+        let (sin, cos) = (sin.abs(), cos.abs());
+        let half = Vec2::new(view.x * cos + view.y * sin, view.x * sin + view.y * cos);
+
+        Vec2::new(
+            clamp_axis(position.x, self.bounds.x, self.bounds.width, half.x),
+            clamp_axis(position.y, self.bounds.y, self.bounds.height, half.y),
+        )
+    }
+
+    // uniformly distributed in [-1, 1]
+    fn unit(&mut self) -> f32 {
+        (self.random.next() as f64 / u64::MAX as f64 * 2.0 - 1.0) as f32
+    }
+}
+
+fn clamp_axis(value: f32, start: f32, length: f32, half: f32) -> f32 {
+    if half * 2.0 >= length {
+        return start + length * 0.5;
+    }
+
+    value.clamp(start + half, start + length - half)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rig() -> Rig {
+        let bounds = Rectangle {
+            x: Flint::ZERO,
+            y: Flint::ZERO,
+            width: Flint::from_i16(2000),
+            height: Flint::from_i16(2000),
+        };
+
+        Rig::new(Camera::new(800.0, 600.0), bounds, Vec2::new(1000.0, 1000.0))
+    }
+
+    #[test]
+    fn shaking_does_not_move_the_aim() {
+        let mut rig = rig();
+        let center = Vec2::new(400.0, 300.0);
+
+        rig.shake(1.0);
+        rig.update();
+
+        let view = rig.render(0.5).screen_to_world(center);
+        assert!((view - Vec2::new(1000.0, 1000.0)).len() > 1.0);
+
+        assert_eq!(
+            rig.camera().screen_to_world(center),
+            Vec2::new(1000.0, 1000.0)
+        );
+    }
+
+    #[test]
+    fn aim_follows_the_newest_tick() {
+        let mut rig = rig();
+        rig.track(
+            Vec2::new(1200.0, 1000.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(0.0, 0.0),
+        );
+        rig.update();
+
+        // the view is still between the ticks, the aim is not
+        let position = rig.position.new;
+        assert!(rig.render(0.0).screen_to_world(Vec2::new(400.0, 300.0)).x < position.x);
+        assert_eq!(
+            rig.camera().screen_to_world(Vec2::new(400.0, 300.0)),
+            position
+        );
+    }
+}
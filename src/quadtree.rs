@@ -122,7 +122,7 @@ impl Node {
         match self.kind {
             NodeKind::Leaf {
                 content: ref entities_hitboxes,
-            } => &entities_hitboxes,
+            } => entities_hitboxes,
             NodeKind::Branch { .. } => &[],
         }
     }
@@ -0,0 +1,495 @@
+use std::{
+    fmt,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+};
+
+mod ecs;
+mod math;
+
+pub const MAGIC: [u8; 2] = *b"kp";
+pub const VERSION: u8 = 9;
+
+pub trait Wire: Sized {
+    fn write(&self, w: &mut Writer);
+    fn read(r: &mut Reader) -> Result<Self, WireError>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WireError {
+    Magic([u8; 2]),
+    Version(u8),
+    UnexpectedEnd,
+    TrailingBytes(usize),
+    InvalidTag { kind: &'static str, tag: u8 },
+    Overflow { kind: &'static str },
+}
+
+pub struct Writer<'a> {
+    buffer: &'a mut Vec<u8>,
+}
+
+pub struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+/// Writes the header followed by the value, replacing whatever was in the buffer.
+pub fn encode<T: Wire>(value: &T, buffer: &mut Vec<u8>) {
+    buffer.clear();
+
+    let mut w = Writer { buffer };
+
+    w.bytes(&MAGIC);
+    w.u8(VERSION);
+    value.write(&mut w);
+}
+
+/// Reads a value written by [`encode`], the value must span the remaining bytes.
+pub fn decode<T: Wire>(bytes: &[u8]) -> Result<T, WireError> {
+    let mut r = Reader { bytes };
+
+    let magic = r.take::<2>()?;
+    if magic != MAGIC {
+        return Err(WireError::Magic(magic));
+    }
+
+    let version = r.u8()?;
+    if version != VERSION {
+        return Err(WireError::Version(version));
+    }
+
+    let value = T::read(&mut r)?;
+
+    if !r.bytes.is_empty() {
+        return Err(WireError::TrailingBytes(r.bytes.len()));
+    }
+
+    Ok(value)
+}
+
+impl Writer<'_> {
+    pub fn u8(&mut self, value: u8) {
+        self.buffer.push(value);
+    }
+
+    pub fn bytes(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    pub fn u16(&mut self, value: u16) {
+        self.bytes(&value.to_le_bytes());
+    }
+
+    pub fn i32(&mut self, value: i32) {
+        self.bytes(&value.to_le_bytes());
+    }
+
+    pub fn u64(&mut self, value: u64) {
+        self.bytes(&value.to_le_bytes());
+    }
+
+    /// Variable length, small values (ids, ticks, lengths) take a single byte.
+    pub fn varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.u8((value as u8) | 0x80);
+            value >>= 7;
+        }
+
+        self.u8(value as u8);
+    }
+
+    pub fn tag(&mut self, tag: u8) {
+        self.u8(tag);
+    }
+}
+
+impl Reader<'_> {
+    pub fn take<const N: usize>(&mut self) -> Result<[u8; N], WireError> {
+        let (head, tail) = self
+            .bytes
+            .split_first_chunk::<N>()
+            .ok_or(WireError::UnexpectedEnd)?;
+
+        self.bytes = tail;
+
+        Ok(*head)
+    }
+
+    pub fn u8(&mut self) -> Result<u8, WireError> {
+        Ok(self.take::<1>()?[0])
+    }
+
+    pub fn u16(&mut self) -> Result<u16, WireError> {
+        Ok(u16::from_le_bytes(self.take()?))
+    }
+
+    pub fn i32(&mut self) -> Result<i32, WireError> {
+        Ok(i32::from_le_bytes(self.take()?))
+    }
+
+    pub fn u64(&mut self) -> Result<u64, WireError> {
+        Ok(u64::from_le_bytes(self.take()?))
+    }
+
+    pub fn varint(&mut self) -> Result<u64, WireError> {
+        let mut value = 0u64;
+
+        for shift in (0..64).step_by(7) {
+            let byte = self.u8()?;
+            let bits = (byte & 0x7f) as u64;
+
+            // the last byte may only carry the bits that are left
+            if shift == 63 && bits > 1 {
+                return Err(WireError::Overflow { kind: "varint" });
+            }
+
+            value |= bits << shift;
+
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+
+        Err(WireError::Overflow { kind: "varint" })
+    }
+
+    pub fn tag(&mut self, kind: &'static str, max: u8) -> Result<u8, WireError> {
+        let tag = self.u8()?;
+
+        if tag > max {
+            return Err(WireError::InvalidTag { kind, tag });
+        }
+
+        Ok(tag)
+    }
+
+    /// Reads a collection length, refusing anything that can't possibly fit
+    /// in the remaining bytes so a malicious length can't allocate the world.
+    pub fn len(&mut self) -> Result<usize, WireError> {
+        let len = usize::read(self)?;
+
+        if len > self.bytes.len() {
+            return Err(WireError::UnexpectedEnd);
+        }
+
+        Ok(len)
+    }
+}
+
+impl Wire for u8 {
+    fn write(&self, w: &mut Writer) {
+        w.u8(*self);
+    }
+
+    fn read(r: &mut Reader) -> Result<Self, WireError> {
+        r.u8()
+    }
+}
+
+impl Wire for u32 {
+    fn write(&self, w: &mut Writer) {
+        w.varint(*self as u64);
+    }
+
+    fn read(r: &mut Reader) -> Result<Self, WireError> {
+        u32::try_from(r.varint()?).map_err(|_| WireError::Overflow { kind: "u32" })
+    }
+}
+
+impl Wire for u64 {
+    fn write(&self, w: &mut Writer) {
+        w.u64(*self);
+    }
+
+    fn read(r: &mut Reader) -> Result<Self, WireError> {
+        r.u64()
+    }
+}
+
+impl Wire for usize {
+    fn write(&self, w: &mut Writer) {
+        w.varint(*self as u64);
+    }
+
+    fn read(r: &mut Reader) -> Result<Self, WireError> {
+        usize::try_from(r.varint()?).map_err(|_| WireError::Overflow { kind: "usize" })
+    }
+}
+
+impl Wire for bool {
+    fn write(&self, w: &mut Writer) {
+        w.tag(*self as u8);
+    }
+
+    fn read(r: &mut Reader) -> Result<Self, WireError> {
+        Ok(r.tag("bool", 1)? == 1)
+    }
+}
+
+impl<T: Wire> Wire for Option<T> {
+    fn write(&self, w: &mut Writer) {
+        match self {
+            None => w.tag(0),
+            Some(value) => {
+                w.tag(1);
+                value.write(w);
+            }
+        }
+    }
+
+    fn read(r: &mut Reader) -> Result<Self, WireError> {
+        match r.tag("option", 1)? {
+            0 => Ok(None),
+            _ => Ok(Some(T::read(r)?)),
+        }
+    }
+}
+
+impl<T: Wire> Wire for Vec<T> {
+    fn write(&self, w: &mut Writer) {
+        self.len().write(w);
+
+        for value in self.iter() {
+            value.write(w);
+        }
+    }
+
+    fn read(r: &mut Reader) -> Result<Self, WireError> {
+        let len = r.len()?;
+        let mut values = Vec::with_capacity(len);
+
+        for _ in 0..len {
+            values.push(T::read(r)?);
+        }
+
+        Ok(values)
+    }
+}
+
+impl<A: Wire, B: Wire> Wire for (A, B) {
+    fn write(&self, w: &mut Writer) {
+        self.0.write(w);
+        self.1.write(w);
+    }
+
+    fn read(r: &mut Reader) -> Result<Self, WireError> {
+        Ok((A::read(r)?, B::read(r)?))
+    }
+}
+
+impl Wire for SocketAddr {
+    fn write(&self, w: &mut Writer) {
+        match self {
+            SocketAddr::V4(address) => {
+                w.tag(0);
+                w.bytes(&address.ip().octets());
+                w.u16(address.port());
+            }
+            SocketAddr::V6(address) => {
+                w.tag(1);
+                w.bytes(&address.ip().octets());
+                w.u16(address.port());
+            }
+        }
+    }
+
+    fn read(r: &mut Reader) -> Result<Self, WireError> {
+        let ip = match r.tag("address", 1)? {
+            0 => IpAddr::V4(Ipv4Addr::from(r.take::<4>()?)),
+            _ => IpAddr::V6(Ipv6Addr::from(r.take::<16>()?)),
+        };
+
+        Ok(SocketAddr::new(ip, r.u16()?))
+    }
+}
+
+impl fmt::Display for WireError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WireError::Magic(magic) => write!(f, "unknown magic {:?}", magic),
+            WireError::Version(version) => {
+                write!(f, "unsupported version {}, expected {}", version, VERSION)
+            }
+            WireError::UnexpectedEnd => write!(f, "unexpected end of input"),
+            WireError::TrailingBytes(len) => write!(f, "{} trailing bytes", len),
+            WireError::InvalidTag { kind, tag } => write!(f, "invalid {} tag {}", kind, tag),
+            WireError::Overflow { kind } => write!(f, "{} overflow", kind),
+        }
+    }
+}
+
+impl std::error::Error for WireError {}
+
+/// Asserts the value survives being encoded and decoded again.
+#[cfg(test)]
+pub fn assert_round_trip<T: Wire + fmt::Debug>(value: &T) {
+    let mut bytes = Vec::new();
+    encode(value, &mut bytes);
+
+    let decoded = decode::<T>(&bytes).unwrap();
+    assert_eq!(format!("{:?}", decoded), format!("{:?}", value));
+
+    let mut again = Vec::new();
+    encode(&decoded, &mut again);
+    assert_eq!(again, bytes);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a header followed by the given body
+    fn framed(body: &[u8]) -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.push(VERSION);
+        bytes.extend_from_slice(body);
+        bytes
+    }
+
+    fn varint(value: u64) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        Writer { buffer: &mut bytes }.varint(value);
+        bytes
+    }
+
+    #[test]
+    fn primitives_round_trip() {
+        for value in [0, 1, 0x7f, 0x80, 0x3fff, 0x4000, u32::MAX - 1, u32::MAX] {
+            assert_round_trip(&value);
+        }
+
+        for value in [0, 1, 0x80, u32::MAX as usize, usize::MAX] {
+            assert_round_trip(&value);
+        }
+
+        for value in [0, 1, u64::MAX] {
+            assert_round_trip(&value);
+        }
+
+        assert_round_trip(&0xabu8);
+        assert_round_trip(&true);
+        assert_round_trip(&false);
+        assert_round_trip(&None::<u32>);
+        assert_round_trip(&Some(300u32));
+        assert_round_trip(&Vec::<u32>::new());
+        assert_round_trip(&vec![(1usize, Some(2u32)), (3, None)]);
+        assert_round_trip(&"127.0.0.1:7331".parse::<SocketAddr>().unwrap());
+        assert_round_trip(&"[::1]:65535".parse::<SocketAddr>().unwrap());
+    }
+
+    #[test]
+    fn varints_take_as_many_bytes_as_needed() {
+        assert_eq!(varint(0), [0x00]);
+        assert_eq!(varint(0x7f), [0x7f]);
+        assert_eq!(varint(0x80), [0x80, 0x01]);
+        assert_eq!(varint(u64::MAX).len(), 10);
+    }
+
+    #[test]
+    fn header_is_checked() {
+        assert_eq!(decode::<u8>(b"xx\x01\x00"), Err(WireError::Magic(*b"xx")));
+        assert_eq!(decode::<u8>(&[]), Err(WireError::UnexpectedEnd));
+        assert_eq!(decode::<u8>(&MAGIC), Err(WireError::UnexpectedEnd));
+
+        let mut bytes = framed(&[0]);
+        bytes[2] = VERSION.wrapping_add(1);
+
+        assert_eq!(
+            decode::<u8>(&bytes),
+            Err(WireError::Version(VERSION.wrapping_add(1)))
+        );
+    }
+
+    #[test]
+    fn truncated_input_ends_unexpectedly() {
+        let mut bytes = Vec::new();
+        encode(&vec![(u64::MAX, Some(u32::MAX))], &mut bytes);
+
+        for len in 0..bytes.len() {
+            assert_eq!(
+                decode::<Vec<(u64, Option<u32>)>>(&bytes[..len]),
+                Err(WireError::UnexpectedEnd),
+                "{} of {} bytes",
+                len,
+                bytes.len()
+            );
+        }
+    }
+
+    #[test]
+    fn trailing_bytes_are_refused() {
+        assert_eq!(
+            decode::<u8>(&framed(&[1, 2, 3])),
+            Err(WireError::TrailingBytes(2))
+        );
+    }
+
+    #[test]
+    fn unknown_tags_are_refused() {
+        assert_eq!(
+            decode::<bool>(&framed(&[2])),
+            Err(WireError::InvalidTag {
+                kind: "bool",
+                tag: 2
+            })
+        );
+        assert_eq!(
+            decode::<Option<u8>>(&framed(&[0xff, 0])),
+            Err(WireError::InvalidTag {
+                kind: "option",
+                tag: 0xff
+            })
+        );
+        assert_eq!(
+            decode::<SocketAddr>(&framed(&[2, 127, 0, 0, 1, 0, 0])),
+            Err(WireError::InvalidTag {
+                kind: "address",
+                tag: 2
+            })
+        );
+    }
+
+    #[test]
+    fn varints_overflowing_are_refused() {
+        // the tenth byte may only carry the highest bit
+        let mut bytes = [0xff; 10];
+        bytes[9] = 0x01;
+        assert_eq!(decode::<usize>(&framed(&bytes)), Ok(usize::MAX));
+
+        bytes[9] = 0x02;
+        assert_eq!(
+            decode::<usize>(&framed(&bytes)),
+            Err(WireError::Overflow { kind: "varint" })
+        );
+
+        // never ending
+        assert_eq!(
+            decode::<usize>(&framed(&[0xff; 11])),
+            Err(WireError::Overflow { kind: "varint" })
+        );
+
+        assert_eq!(
+            decode::<u32>(&framed(&varint(u32::MAX as u64 + 1))),
+            Err(WireError::Overflow { kind: "u32" })
+        );
+    }
+
+    #[test]
+    fn lengths_beyond_the_input_are_refused() {
+        // refused before allocating anything
+        let mut body = varint(u64::MAX);
+        body.extend_from_slice(&[0; 4]);
+
+        assert_eq!(
+            decode::<Vec<u64>>(&framed(&body)),
+            Err(WireError::UnexpectedEnd)
+        );
+
+        let mut body = varint(5);
+        body.extend_from_slice(&[0; 4]);
+
+        assert_eq!(
+            decode::<Vec<u8>>(&framed(&body)),
+            Err(WireError::UnexpectedEnd)
+        );
+    }
+}
@@ -0,0 +1,323 @@
+use korp_math::{Flint, Vec2};
+
+use crate::{
+    ecs::{
+        checksum::{Checksum, Set},
+        commands::{Command, SpawnKind},
+        entities::Entity,
+    },
+    wire::{Reader, Wire, WireError, Writer},
+};
+
+impl Wire for Entity {
+    fn write(&self, w: &mut Writer) {
+        self.index.write(w);
+        self.generation.write(w);
+    }
+
+    fn read(r: &mut Reader) -> Result<Self, WireError> {
+        Ok(Entity {
+            index: u32::read(r)?,
+            generation: u32::read(r)?,
+        })
+    }
+}
+
+impl Wire for Command {
+    fn write(&self, w: &mut Writer) {
+        match self {
+            Command::Accelerate(entity) => {
+                w.tag(0);
+                entity.write(w);
+            }
+            Command::Decelerate(entity) => {
+                w.tag(1);
+                entity.write(w);
+            }
+            Command::TurnLeft(entity) => {
+                w.tag(2);
+                entity.write(w);
+            }
+            Command::TurnRight(entity) => {
+                w.tag(3);
+                entity.write(w);
+            }
+            Command::Shoot(entity) => {
+                w.tag(4);
+                entity.write(w);
+            }
+            Command::Kill(entity) => {
+                w.tag(5);
+                entity.write(w);
+            }
+            Command::Spawn { id, kind } => {
+                w.tag(6);
+                id.write(w);
+                kind.write(w);
+            }
+            Command::CycleWeapon(entity) => {
+                w.tag(7);
+                entity.write(w);
+            }
+        }
+    }
+
+    fn read(r: &mut Reader) -> Result<Self, WireError> {
+        let command = match r.tag("command", 7)? {
+            0 => Command::Accelerate(Entity::read(r)?),
+            1 => Command::Decelerate(Entity::read(r)?),
+            2 => Command::TurnLeft(Entity::read(r)?),
+            3 => Command::TurnRight(Entity::read(r)?),
+            4 => Command::Shoot(Entity::read(r)?),
+            5 => Command::Kill(Entity::read(r)?),
+            6 => Command::Spawn {
+                id: Option::read(r)?,
+                kind: SpawnKind::read(r)?,
+            },
+            _ => Command::CycleWeapon(Entity::read(r)?),
+        };
+
+        Ok(command)
+    }
+}
+
+impl Wire for SpawnKind {
+    fn write(&self, w: &mut Writer) {
+        match self {
+            SpawnKind::Triangle { centroid } => {
+                w.tag(0);
+                centroid.write(w);
+            }
+            SpawnKind::Rectangle { centroid } => {
+                w.tag(1);
+                centroid.write(w);
+            }
+            SpawnKind::Projectile {
+                owner,
+                relative_speed,
+                speed,
+                damage,
+                lifetime,
+                centroid,
+                rotation,
+            } => {
+                w.tag(2);
+                owner.write(w);
+                relative_speed.write(w);
+                speed.write(w);
+                damage.write(w);
+                lifetime.write(w);
+                centroid.write(w);
+                rotation.write(w);
+            }
+            SpawnKind::Particle {
+                centroid,
+                direction,
+                speed,
+                lifetime,
+            } => {
+                w.tag(3);
+                centroid.write(w);
+                direction.write(w);
+                speed.write(w);
+                lifetime.write(w);
+            }
+            SpawnKind::Circle { centroid } => {
+                w.tag(4);
+                centroid.write(w);
+            }
+            SpawnKind::Polygon { centroid } => {
+                w.tag(5);
+                centroid.write(w);
+            }
+        }
+    }
+
+    fn read(r: &mut Reader) -> Result<Self, WireError> {
+        let kind = match r.tag("spawn kind", 5)? {
+            0 => SpawnKind::Triangle {
+                centroid: Vec2::read(r)?,
+            },
+            1 => SpawnKind::Rectangle {
+                centroid: Vec2::read(r)?,
+            },
+            2 => SpawnKind::Projectile {
+                owner: Entity::read(r)?,
+                relative_speed: Flint::read(r)?,
+                speed: Flint::read(r)?,
+                damage: Flint::read(r)?,
+                lifetime: u32::read(r)?,
+                centroid: Vec2::read(r)?,
+                rotation: Vec2::read(r)?,
+            },
+            3 => SpawnKind::Particle {
+                centroid: Vec2::read(r)?,
+                direction: Vec2::read(r)?,
+                speed: Flint::read(r)?,
+                lifetime: u32::read(r)?,
+            },
+            4 => SpawnKind::Circle {
+                centroid: Vec2::read(r)?,
+            },
+            _ => SpawnKind::Polygon {
+                centroid: Vec2::read(r)?,
+            },
+        };
+
+        Ok(kind)
+    }
+}
+
+impl Wire for Checksum {
+    fn write(&self, w: &mut Writer) {
+        for hash in self.hashes.iter() {
+            hash.write(w);
+        }
+    }
+
+    fn read(r: &mut Reader) -> Result<Self, WireError> {
+        let mut hashes = [0; Set::ALL.len()];
+
+        for hash in hashes.iter_mut() {
+            *hash = u64::read(r)?;
+        }
+
+        Ok(Checksum { hashes })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wire::{MAGIC, VERSION, assert_round_trip, decode, encode};
+
+    const ENTITY: Entity = Entity {
+        index: 300,
+        generation: 2,
+    };
+
+    fn spawn_kinds() -> Vec<SpawnKind> {
+        let point = Vec2::new(Flint::from_f64(-12.25), Flint::from_i16(640));
+
+        vec![
+            SpawnKind::Triangle { centroid: point },
+            SpawnKind::Rectangle { centroid: point },
+            SpawnKind::Circle { centroid: point },
+            SpawnKind::Polygon { centroid: point },
+            SpawnKind::Projectile {
+                owner: ENTITY,
+                relative_speed: Flint::from_f64(1.5),
+                speed: Flint::from_i16(20),
+                damage: Flint::from_f64(0.75),
+                lifetime: 90,
+                centroid: point,
+                rotation: Vec2::new(Flint::from_f64(0.6), Flint::from_f64(-0.8)),
+            },
+            SpawnKind::Particle {
+                centroid: point,
+                direction: Vec2::new(Flint::ZERO, Flint::NEG_ONE),
+                speed: Flint::MAX,
+                lifetime: u32::MAX,
+            },
+        ]
+    }
+
+    #[test]
+    fn entities_round_trip() {
+        assert_round_trip(&ENTITY);
+        assert_round_trip(&Entity {
+            index: u32::MAX,
+            generation: u32::MAX,
+        });
+    }
+
+    #[test]
+    fn spawn_kinds_round_trip() {
+        for kind in spawn_kinds() {
+            assert_round_trip(&kind);
+        }
+    }
+
+    #[test]
+    fn commands_round_trip() {
+        let mut commands = vec![
+            Command::Accelerate(ENTITY),
+            Command::Decelerate(ENTITY),
+            Command::TurnLeft(ENTITY),
+            Command::TurnRight(ENTITY),
+            Command::Shoot(ENTITY),
+            Command::Kill(ENTITY),
+            Command::CycleWeapon(ENTITY),
+        ];
+
+        for (id, kind) in spawn_kinds().into_iter().enumerate() {
+            commands.push(Command::Spawn { id: Some(id), kind });
+            commands.push(Command::Spawn { id: None, kind });
+        }
+
+        for command in commands.iter() {
+            assert_round_trip(command);
+        }
+
+        assert_round_trip(&commands);
+    }
+
+    #[test]
+    fn checksums_round_trip() {
+        let mut checksum = Checksum {
+            hashes: [0; Set::ALL.len()],
+        };
+
+        for (i, hash) in checksum.hashes.iter_mut().enumerate() {
+            *hash = u64::MAX - i as u64;
+        }
+
+        assert_round_trip(&checksum);
+    }
+
+    #[test]
+    fn unknown_tags_are_refused() {
+        let framed = |body: &[u8]| [&MAGIC[..], &[VERSION], body].concat();
+
+        assert_eq!(
+            decode::<Command>(&framed(&[8, 0, 0])).unwrap_err(),
+            WireError::InvalidTag {
+                kind: "command",
+                tag: 8
+            }
+        );
+        assert_eq!(
+            decode::<SpawnKind>(&framed(&[6])).unwrap_err(),
+            WireError::InvalidTag {
+                kind: "spawn kind",
+                tag: 6
+            }
+        );
+        assert_eq!(
+            decode::<Command>(&framed(&[6, 0, 7])).unwrap_err(),
+            WireError::InvalidTag {
+                kind: "spawn kind",
+                tag: 7
+            }
+        );
+    }
+
+    #[test]
+    fn truncated_commands_end_unexpectedly() {
+        let mut bytes = Vec::new();
+        encode(
+            &Command::Spawn {
+                id: Some(1),
+                kind: spawn_kinds()[2],
+            },
+            &mut bytes,
+        );
+
+        for len in 0..bytes.len() {
+            assert_eq!(
+                decode::<Command>(&bytes[..len]).unwrap_err(),
+                WireError::UnexpectedEnd
+            );
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use korp_math::{Flint, Random, Vec2};
+
+use crate::wire::{Reader, Wire, WireError, Writer};
+
+impl Wire for Flint {
+    fn write(&self, w: &mut Writer) {
+        w.i32(self.raw);
+    }
+
+    fn read(r: &mut Reader) -> Result<Self, WireError> {
+        Ok(Flint::from_raw(r.i32()?))
+    }
+}
+
+impl Wire for Vec2<Flint> {
+    fn write(&self, w: &mut Writer) {
+        self.x.write(w);
+        self.y.write(w);
+    }
+
+    fn read(r: &mut Reader) -> Result<Self, WireError> {
+        Ok(Vec2::new(Flint::read(r)?, Flint::read(r)?))
+    }
+}
+
+impl Wire for Random {
+    fn write(&self, w: &mut Writer) {
+        for word in self.state() {
+            word.write(w);
+        }
+    }
+
+    fn read(r: &mut Reader) -> Result<Self, WireError> {
+        Ok(Random::from_state([
+            u64::read(r)?,
+            u64::read(r)?,
+            u64::read(r)?,
+            u64::read(r)?,
+        ]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wire::{assert_round_trip, decode, encode};
+
+    #[test]
+    fn flints_round_trip() {
+        for raw in [0, 1, -1, i32::MIN, i32::MAX, 0x0001_8000] {
+            assert_round_trip(&Flint::from_raw(raw));
+        }
+    }
+
+    #[test]
+    fn vectors_round_trip() {
+        assert_round_trip(&Vec2::new(Flint::MIN, Flint::MAX));
+        assert_round_trip(&Vec2::new(Flint::from_f64(-0.5), Flint::from_i16(3)));
+    }
+
+    #[test]
+    fn random_round_trips_with_its_state() {
+        let mut random = Random::new(42);
+        random.next();
+
+        let mut bytes = Vec::new();
+        encode(&random, &mut bytes);
+
+        let mut decoded = decode::<Random>(&bytes).unwrap();
+        assert_eq!(decoded, random);
+
+        // continues the same sequence
+        for _ in 0..16 {
+            assert_eq!(decoded.next(), random.next());
+        }
+    }
+}